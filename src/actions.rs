@@ -1,12 +1,59 @@
+use crate::appraisal;
+#[cfg(feature = "cli")]
+use crate::bank;
+pub use crate::bestiary::Monster;
+use crate::character;
+#[cfg(feature = "cli")]
+use crate::character::{Background, CharacterClass};
+#[cfg(feature = "cli")]
+use crate::clock::{
+    APPRAISAL_MINUTES, BANK_CLOSE_HOUR, BANK_OPEN_HOUR, BANK_VISIT_MINUTES, SHOP_VISIT_MINUTES,
+    TAVERN_GAMBLE_MINUTES, TAVERN_HIRE_MINUTES, TAVERN_OPEN_HOUR,
+};
+use crate::clock::{
+    FIGHT_ROUND_MINUTES, PICKPOCKET_MINUTES, TAVERN_DRINK_MINUTES, TAVERN_FLIRT_MINUTES,
+    TAVERN_FOOD_MINUTES, TAVERN_TIP_MINUTES, WAKE_HOUR,
+};
+use crate::combat::{self, Encounter, Fall, FightStatus};
+use crate::companion;
+#[cfg(feature = "cli")]
+use crate::companion::CompanionKind;
+#[cfg(feature = "cli")]
+use crate::crafting;
+#[cfg(feature = "cli")]
+use crate::currency;
+use crate::difficulty;
+#[cfg(feature = "cli")]
+use crate::difficulty::Difficulty;
+#[cfg(feature = "cli")]
+use crate::dungeon::{self, Door, RoomContent};
+use crate::encumbrance;
+#[cfg(feature = "cli")]
+use crate::gamble::{DiceWager, GAMBLE_MAX_BET_CP, GAMBLE_MIN_BET_CP, play_rune_dice};
+use crate::graveyard::Grave;
+#[cfg(feature = "cli")]
+use crate::haggle::{HaggleContext, HaggleStep, Negotiation};
+#[cfg(feature = "cli")]
+use crate::hotseat::{self, Bundle, HotSeat, Trade, TradeError};
 use crate::inventory::Inventory;
 #[cfg(feature = "cli")]
 use crate::inventory::format_cp;
-use crate::loot::{currency_regex, parse_and_format_loot_cached};
+use crate::loot::{currency_regex, format_items_for_display, parse_and_format_loot_cached};
+use crate::money::Money;
+#[cfg(feature = "cli")]
+use crate::quest;
+use crate::quest::QuestEvent;
 use crate::rng::with_rng;
 #[cfg(feature = "cli")]
-use dialoguer::{Confirm, MultiSelect, Select};
+use crate::shop::{self, ShopKind};
+use crate::stats::StatEvent;
+#[cfg(feature = "cli")]
+use crate::ui::{prompt_difficulty, prompt_hardcore};
+#[cfg(feature = "cli")]
+use dialoguer::{Confirm, Input, MultiSelect, Select};
 use rand::Rng;
 use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 
 // Probabilities
 pub const EVENT_CHANCE: f64 = 0.05;
@@ -20,24 +67,173 @@ pub const TAVERN_TIP_COST_GP: u32 = 5;
 pub const TAVERN_LUCK_CHANCE: f64 = 0.40;
 pub const TAVERN_FLIRT_COST_GP: u32 = 10; // cost to flirt with barmaid
 pub const TAVERN_FLIRT_KISS_CHANCE: f64 = 0.05; // 5% chance to gain luck via kiss
+pub const TAVERN_DRINK_PRICE: Money = Money::sp(TAVERN_DRINK_COST_SP as u64);
+pub const TAVERN_FOOD_PRICE: Money = Money::sp(TAVERN_FOOD_COST_SP as u64);
+pub const TAVERN_STAY_PRICE: Money = Money::gp(TAVERN_STAY_COST_GP as u64);
+pub const TAVERN_TIP_PRICE: Money = Money::gp(TAVERN_TIP_COST_GP as u64);
+pub const TAVERN_FLIRT_PRICE: Money = Money::gp(TAVERN_FLIRT_COST_GP as u64);
 
-#[derive(Clone, Copy)]
-pub struct Monster {
-    pub name: &'static str,
-    pub strength: u8, // influences hp & damage
+/// Pickpocket odds at the current hour: darkness helps, daylight hurts, a lookout helps
+/// and a heavy load hurts.
+pub fn pickpocket_success_chance(inv: &Inventory) -> f64 {
+    let class = character::class_of(inv).map_or(0.0, |c| c.pickpocket_bonus());
+    let load = if encumbrance::is_overloaded(inv) {
+        encumbrance::OVERLOAD_PICKPOCKET_PENALTY
+    } else {
+        0.0
+    };
+    let lookout = companion::lookout_bonus(inv);
+    (PICKPOCKET_SUCCESS + inv.clock.time_of_day().pickpocket_modifier() + class + lookout - load)
+        .clamp(0.05, 0.95)
+}
+
+// Bards get on better with a tavern crowd
+fn tavern_class_factor(inv: &Inventory) -> f64 {
+    character::class_of(inv).map_or(1.0, |c| c.tavern_factor())
+}
+
+/// Tip luck chance adjusted for how busy the bartender is.
+pub fn tavern_luck_chance(inv: &Inventory) -> f64 {
+    (TAVERN_LUCK_CHANCE * inv.clock.tavern_crowd().tip_luck_factor() * tavern_class_factor(inv))
+        .min(1.0)
+}
+
+/// Kiss chance adjusted for the crowd; a packed room gives the barmaid cover.
+pub fn tavern_kiss_chance(inv: &Inventory) -> f64 {
+    (TAVERN_FLIRT_KISS_CHANCE * inv.clock.tavern_crowd().flirt_factor() * tavern_class_factor(inv))
+        .min(1.0)
+}
+
+/// Pay the tavern the player's way: exact coins only, or making change (`Inventory::pay_mode`).
+pub fn tavern_pay(inv: &mut Inventory, price: Money) -> bool {
+    inv.pay(price, inv.pay_mode).is_ok()
 }
 
-impl Monster {
-    pub fn max_hp(&self) -> u32 {
-        5 + (self.strength as u32 * 5)
+/// The simple bar orders, for front ends that take them in one keypress (web, TUI).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TavernOrder {
+    Drink,
+    Food,
+    Stay,
+    Tip,
+    Flirt,
+}
+
+impl TavernOrder {
+    pub const ALL: [TavernOrder; 5] = [
+        TavernOrder::Drink,
+        TavernOrder::Food,
+        TavernOrder::Stay,
+        TavernOrder::Tip,
+        TavernOrder::Flirt,
+    ];
+
+    pub fn parse(s: &str) -> Option<TavernOrder> {
+        match s.trim().to_lowercase().as_str() {
+            "drink" => Some(TavernOrder::Drink),
+            "food" | "eat" => Some(TavernOrder::Food),
+            "stay" | "room" => Some(TavernOrder::Stay),
+            "tip" => Some(TavernOrder::Tip),
+            "flirt" => Some(TavernOrder::Flirt),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TavernOrder::Drink => "Buy Drink",
+            TavernOrder::Food => "Buy Food",
+            TavernOrder::Stay => "Stay The Night",
+            TavernOrder::Tip => "Tip Bartender",
+            TavernOrder::Flirt => "Flirt With Barmaid",
+        }
+    }
+}
+
+/// Pay for an order, roll for any luck and let the time pass. The first line says what
+/// happened; the rest are the world's notes from the time spent.
+pub fn tavern_order(inv: &mut Inventory, order: TavernOrder) -> Vec<String> {
+    let (msg, minutes): (String, u64) = match order {
+        TavernOrder::Drink => {
+            if !tavern_pay(inv, TAVERN_DRINK_PRICE) {
+                ("Not enough coin for drink".into(), 0)
+            } else {
+                ("Enjoyed a stiff drink".into(), TAVERN_DRINK_MINUTES)
+            }
+        }
+        TavernOrder::Food => {
+            if !tavern_pay(inv, TAVERN_FOOD_PRICE) {
+                ("Can't afford meal".into(), 0)
+            } else {
+                ("A hearty meal restores you".into(), TAVERN_FOOD_MINUTES)
+            }
+        }
+        TavernOrder::Stay => {
+            if !tavern_pay(inv, TAVERN_STAY_PRICE) {
+                return vec!["Not enough for room".into()];
+            }
+            let notes = rest_until_morning(inv);
+            let mut lines = vec![format!(
+                "You rest peacefully and wake at {}",
+                inv.clock.label()
+            )];
+            lines.extend(notes);
+            return lines;
+        }
+        TavernOrder::Tip => {
+            if inv.luck_boost {
+                ("Luck already stored".into(), 0)
+            } else if !tavern_pay(inv, TAVERN_TIP_PRICE) {
+                ("Need more gold to tip".into(), 0)
+            } else {
+                inv.stats.record(StatEvent::TavernTip);
+                let chance = tavern_luck_chance(inv);
+                if with_rng(|r| r.gen_bool(chance)) {
+                    inv.luck_boost = true;
+                    ("Luck granted from generous tip".into(), TAVERN_TIP_MINUTES)
+                } else {
+                    ("Tip given, no luck".into(), TAVERN_TIP_MINUTES)
+                }
+            }
+        }
+        TavernOrder::Flirt => {
+            let chance = tavern_kiss_chance(inv);
+            if !tavern_pay(inv, TAVERN_FLIRT_PRICE) {
+                ("Can't afford to flirt".into(), 0)
+            } else if with_rng(|r| r.gen_bool(chance)) {
+                if !inv.luck_boost {
+                    inv.luck_boost = true;
+                    ("A kiss grants you luck".into(), TAVERN_FLIRT_MINUTES)
+                } else {
+                    ("Another kiss, luck unchanged".into(), TAVERN_FLIRT_MINUTES)
+                }
+            } else {
+                ("No luck this time".into(), TAVERN_FLIRT_MINUTES)
+            }
+        }
+    };
+    let mut lines = vec![msg];
+    lines.extend(inv.advance_time(minutes));
+    if minutes > 0 {
+        inv.save_after_pickup();
     }
-    pub fn damage_range(&self) -> std::ops::RangeInclusive<u32> {
-        let base = self.strength as u32;
-        base..=base + 4
+    lines
+}
+
+/// Sleep until morning: the clock runs on to the wake hour and hit points are restored.
+pub fn rest_until_morning(inv: &mut Inventory) -> Vec<String> {
+    let minutes = inv.clock.minutes_until_hour(WAKE_HOUR);
+    let notes = inv.advance_time(minutes);
+    if inv.max_hp == 0 {
+        inv.max_hp = 20;
     }
+    inv.current_hp = inv.max_hp;
+    inv.party.rest();
+    inv.save_after_pickup();
+    notes
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct FightOutcome {
     pub monster: &'static str,
     pub victory: bool,
@@ -45,147 +241,124 @@ pub struct FightOutcome {
     pub loss_gp: u32,
     pub player_hp_end: u32,
     pub monster_hp_end: u32,
+    pub grave: Option<Grave>, // hardcore death: `inv` now holds a fresh character
 }
 
 // Legacy outcome helper retained for deterministic tests: runs an auto-resolve fight with no fleeing.
 pub fn fight_monster_outcome(inv: &mut Inventory) -> FightOutcome {
-    const MONSTERS: &[Monster] = &[
-        Monster {
-            name: "Goblin Sneak",
-            strength: 1,
-        },
-        Monster {
-            name: "Cave Rat",
-            strength: 1,
-        },
-        Monster {
-            name: "Skeleton Guard",
-            strength: 2,
-        },
-        Monster {
-            name: "Orc Marauder",
-            strength: 3,
-        },
-        Monster {
-            name: "Ghoul",
-            strength: 4,
-        },
-        Monster {
-            name: "Ogre Brute",
-            strength: 5,
-        },
-        Monster {
-            name: "Wyvern",
-            strength: 6,
-        },
-        Monster {
-            name: "Vampire Stalker",
-            strength: 7,
-        },
-        Monster {
-            name: "Stone Golem",
-            strength: 8,
-        },
-        Monster {
-            name: "Ancient Lich",
-            strength: 9,
-        },
-        Monster {
-            name: "Dragon Wyrm",
-            strength: 10,
-        },
-    ];
-    let monster = with_rng(|r| *MONSTERS.choose(r).unwrap());
-    // Ensure player hp initialized
-    if inv.max_hp == 0 {
-        inv.max_hp = 20;
-    }
-    if inv.current_hp == 0 || inv.current_hp > inv.max_hp {
-        inv.current_hp = inv.max_hp;
-    }
-    let mut m_hp = monster.max_hp();
-    // Auto-resolve: alternate blows until one drops
-    let mut turn_player = true;
-    while inv.current_hp > 0 && m_hp > 0 {
-        if turn_player {
-            let dmg = with_rng(|r| r.gen_range(2..=6));
-            m_hp = m_hp.saturating_sub(dmg);
-        } else {
-            let dmg = with_rng(|r| r.gen_range(monster.damage_range()));
-            inv.current_hp = inv.current_hp.saturating_sub(dmg);
+    combat::prepare_player(inv);
+    let monster = difficulty::scaled_monster(inv, inv.clock.time_of_day());
+    let mut enc = Encounter::new(vec![monster]);
+    // Auto-resolve: trade blows until one side drops
+    let mut exchanges = 0u64;
+    let status = loop {
+        exchanges += 1;
+        let (_, status) = enc.round(inv, None);
+        if status != FightStatus::Ongoing {
+            break status;
         }
-        turn_player = !turn_player;
+    };
+    // Notices from the legacy helper have nowhere to go; quest state still updates.
+    inv.advance_time(exchanges * FIGHT_ROUND_MINUTES);
+    if status == FightStatus::Escaped {
+        return FightOutcome {
+            monster: monster.name,
+            victory: false,
+            reward_gp: 0,
+            loss_gp: 0,
+            player_hp_end: inv.current_hp,
+            monster_hp_end: enc.foes[0].hp,
+            grave: None,
+        };
     }
-    if m_hp == 0 {
-        let min_gp = (10 * (monster.strength as u32).max(1)).max(5);
-        let max_gp = (40 * monster.strength as u32).min(400).max(min_gp + 5);
-        let reward = with_rng(|r| r.gen_range(min_gp..=max_gp));
-        inv.gold_pieces = inv.gold_pieces.saturating_add(reward);
-        inv.save_after_pickup();
+    if status == FightStatus::Victory {
+        let spoils = combat::settle_victory(inv, &enc);
         FightOutcome {
             monster: monster.name,
             victory: true,
-            reward_gp: reward,
+            reward_gp: spoils.gold,
             loss_gp: 0,
             player_hp_end: inv.current_hp,
-            monster_hp_end: m_hp,
+            monster_hp_end: 0,
+            grave: None,
         }
     } else {
-        // Player defeated: apply new penalty (10% gold, 3 items) and restore hp to full after
-        let before_gp = inv.gold_pieces;
-        let loss = ((before_gp as f64) * 0.10).round() as u32;
-        let loss = loss.clamp(0, inv.gold_pieces);
-        inv.gold_pieces -= loss;
-        // Remove up to 3 random items
-        let mut removed = Vec::new();
-        for _ in 0..3 {
-            if inv.items.is_empty() {
-                break;
-            }
-            let idx = with_rng(|r| r.gen_range(0..inv.items.len()));
-            removed.push(inv.items.remove(idx));
-        }
-        inv.current_hp = inv.max_hp; // restore
-        inv.save_after_pickup();
+        let monster_hp_end = enc.foes[0].hp;
+        let (loss, grave) = match combat::settle_fall(inv, &enc) {
+            Fall::KnockedOut { loss, .. } => (loss, None),
+            Fall::Perished(grave) => (0, Some(grave)),
+        };
         FightOutcome {
             monster: monster.name,
             victory: false,
             reward_gp: 0,
             loss_gp: loss,
             player_hp_end: inv.current_hp,
-            monster_hp_end: m_hp,
+            monster_hp_end,
+            grave,
         }
     }
 }
 
-pub fn pick_pocket(inv: &mut Inventory, loot_items: &[String]) {
-    let before = inv.clone();
+/// What came of a pickpocket attempt, for front ends that present it their own way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickpocketReport {
+    pub title: String,
+    pub found: Vec<String>, // items taken, coins aside
+    pub lines: Vec<String>,
+}
+
+/// Try a pocket: roll for the mysterious figure, success or getting caught, and let the
+/// hour pass. Nothing is printed.
+pub fn pickpocket_attempt(inv: &mut Inventory, loot_items: &[String]) -> PickpocketReport {
     let mut non_currency_added: Vec<String> = Vec::with_capacity(4);
     let mut narrative: Vec<String> = Vec::with_capacity(2);
     let mut title = String::from("Pickpocket");
+    let success_chance = pickpocket_success_chance(inv);
+    let time_notes = inv.advance_time(PICKPOCKET_MINUTES);
+    inv.stats.record(StatEvent::PickpocketAttempt);
     let boosted = inv.luck_boost;
     let event_chance = if boosted { 0.90 } else { EVENT_CHANCE };
     if with_rng(|r| r.gen_bool(event_chance)) {
         title = "Mysterious Figure".into();
         narrative.push("A mysterious figure emerges from the shadows...".into());
         inv.add_item("1000 gp");
-    } else if with_rng(|r| r.gen_bool(PICKPOCKET_SUCCESS)) {
+        inv.stats.record(StatEvent::MysteriousFigure);
+        inv.stats.record(StatEvent::CoinEarned(100_000));
+    } else if with_rng(|r| r.gen_bool(success_chance)) {
         if let Some(desc) = with_rng(|r| loot_items.choose(r).cloned()) {
-            let (items, formatted) = parse_and_format_loot_cached(&desc);
+            let (items, _) = parse_and_format_loot_cached(&desc);
+            // Magic trinkets often can't be told apart until someone appraises them
+            let looted: Vec<(String, Option<String>)> =
+                items.iter().map(|it| appraisal::disguise(it)).collect();
+            let items: Vec<String> = looted.iter().map(|(it, _)| it.clone()).collect();
+            let formatted = format_items_for_display(&items);
             title = "Successful Pickpocket".into();
             narrative.push(format!("You found: {}", formatted));
             let cre = currency_regex();
-            for it in items.iter() {
-                inv.add_item(it);
-                if !cre.is_match(it) {
+            let purse_before = inv.total_cp();
+            for (it, true_name) in looted {
+                if cre.is_match(&it) {
+                    inv.add_item(&it);
+                } else {
+                    inv.push_disguised(it.clone(), true_name, true);
+                    narrative.extend(inv.quests.record(QuestEvent::ItemStolen(&it)));
                     non_currency_added.push(it.clone());
                 }
             }
+            inv.stats.record(StatEvent::PickpocketSuccess);
+            inv.stats.record(StatEvent::CoinEarned(
+                inv.total_cp().saturating_sub(purse_before),
+            ));
         }
     } else {
         title = "Caught Pickpocketing".into();
+        inv.notoriety = inv.notoriety.saturating_add(1);
         let loss_percent = with_rng(|r| r.gen_range(5..=11)); // inclusive upper bound mimic 5..=10
         let loss = crate::apply_pickpocket_penalty(&mut inv.gold_pieces, loss_percent);
+        inv.stats.record(StatEvent::Caught);
+        inv.stats
+            .record(StatEvent::CoinLost(loss.saturating_mul(100)));
         narrative.push(if loss > 0 {
             format!(
                 "You drop {} gold pieces ({}%) while fleeing!",
@@ -199,84 +372,217 @@ pub fn pick_pocket(inv: &mut Inventory, loot_items: &[String]) {
         inv.luck_boost = false;
         narrative.push("(Your stored luck dissipates.)".into());
     }
+    narrative.extend(time_notes);
     inv.save_after_pickup();
-    crate::print_event_summary(&title, &before, inv, &non_currency_added, &[]);
-    for line in narrative {
+    PickpocketReport {
+        title,
+        found: non_currency_added,
+        lines: narrative,
+    }
+}
+
+pub fn pick_pocket(inv: &mut Inventory, loot_items: &[String]) {
+    let before = inv.clone();
+    let report = pickpocket_attempt(inv, loot_items);
+    crate::print_event_summary(&report.title, &before, inv, &report.found, &[]);
+    for line in report.lines {
         println!("  • {}", line);
     }
 }
 
+// Let time pass in the CLI, printing whatever the world has to say about it
+#[cfg(feature = "cli")]
+fn spend_time(inv: &mut Inventory, minutes: u64) {
+    for note in inv.advance_time(minutes) {
+        println!("{}", note);
+    }
+}
+
+/// Character creation wizard: name, class and background, then the starting kit.
+#[cfg(feature = "cli")]
+pub fn create_character(difficulty: Difficulty, hardcore: bool) -> Inventory {
+    let mut inv = prompt_character(difficulty, hardcore);
+    inv.save_after_pickup();
+    inv
+}
+
+// A new character from the keyboard (nothing saved yet)
+#[cfg(feature = "cli")]
+fn prompt_character(difficulty: Difficulty, hardcore: bool) -> Inventory {
+    let mut inv = Inventory::new();
+    inv.difficulty = difficulty;
+    inv.hardcore = hardcore;
+    prompt_take_over(&mut inv);
+    inv
+}
+
+// Name, class and background from the keyboard, taken up in `inv` (nothing saved yet)
+#[cfg(feature = "cli")]
+fn prompt_take_over(inv: &mut Inventory) {
+    crate::print_simple_header("Create Your Character");
+    let name = loop {
+        let raw: String = Input::new()
+            .with_prompt("Name")
+            .allow_empty(true)
+            .interact_text()
+            .unwrap_or_default();
+        match character::clean_name(&raw) {
+            Ok(name) => break name,
+            Err(e) => println!("{}", e),
+        }
+    };
+    let classes: Vec<String> = CharacterClass::ALL
+        .iter()
+        .map(|c| format!("{} — {}", c.label(), c.description()))
+        .collect();
+    let class = Select::new()
+        .with_prompt("Class")
+        .items(&classes)
+        .default(0)
+        .interact()
+        .map_or(CharacterClass::Rogue, |i| CharacterClass::ALL[i]);
+    let backgrounds: Vec<String> = Background::ALL
+        .iter()
+        .map(|b| format!("{} — {}", b.label(), b.description()))
+        .collect();
+    let background = Select::new()
+        .with_prompt("Background")
+        .items(&backgrounds)
+        .default(0)
+        .interact()
+        .map_or(Background::Wanderer, |i| Background::ALL[i]);
+    character::take_over(inv, &name, class, background).expect("name was validated above");
+    if let Some(c) = &inv.character {
+        println!("🧙 {} sets out with {} HP.", c.title(), inv.max_hp);
+    }
+    println!("🎒 Starting kit: {}", inv.items.join(", "));
+}
+
+/// Resume the saved hot-seat table, or seat a new one: how many players, one difficulty
+/// for everyone, and a character each.
+#[cfg(feature = "cli")]
+pub fn setup_hot_seat() -> HotSeat {
+    crate::print_simple_header("Hot Seat");
+    if let Ok(table) = HotSeat::load() {
+        let names: Vec<String> = (0..table.players.len()).map(|i| table.name_of(i)).collect();
+        if Confirm::new()
+            .with_prompt(format!(
+                "Resume the table of {} (round {})?",
+                names.join(", "),
+                table.round
+            ))
+            .default(true)
+            .interact()
+            .unwrap_or(false)
+        {
+            return table;
+        }
+    }
+    let counts: Vec<String> = (hotseat::MIN_PLAYERS..=hotseat::MAX_PLAYERS)
+        .map(|n| format!("{} players", n))
+        .collect();
+    let count = Select::new()
+        .with_prompt("How many at the table?")
+        .items(&counts)
+        .default(0)
+        .interact()
+        .unwrap_or(0)
+        + hotseat::MIN_PLAYERS;
+    let difficulty = prompt_difficulty();
+    let hardcore = prompt_hardcore();
+    let players = (1..=count)
+        .map(|n| {
+            println!("\n🪑 Player {} takes the keyboard.", n);
+            prompt_character(difficulty, hardcore)
+        })
+        .collect();
+    HotSeat::new(players)
+}
+
 #[cfg(feature = "cli")]
 pub fn fight_monster(inv: &mut Inventory) {
-    use std::io::{self, Write};
     crate::print_simple_header("Battle");
-    // Pick monster
-    const MONSTERS: &[Monster] = &[
-        Monster {
-            name: "Goblin Sneak",
-            strength: 1,
-        },
-        Monster {
-            name: "Cave Rat",
-            strength: 1,
-        },
-        Monster {
-            name: "Skeleton Guard",
-            strength: 2,
-        },
-        Monster {
-            name: "Orc Marauder",
-            strength: 3,
-        },
-        Monster {
-            name: "Ghoul",
-            strength: 4,
-        },
-        Monster {
-            name: "Ogre Brute",
-            strength: 5,
-        },
-        Monster {
-            name: "Wyvern",
-            strength: 6,
-        },
-        Monster {
-            name: "Vampire Stalker",
-            strength: 7,
-        },
-        Monster {
-            name: "Stone Golem",
-            strength: 8,
-        },
-        Monster {
-            name: "Ancient Lich",
-            strength: 9,
-        },
-        Monster {
-            name: "Dragon Wyrm",
-            strength: 10,
-        },
-    ];
-    let monster = with_rng(|r| *MONSTERS.choose(r).unwrap());
-    if inv.max_hp == 0 {
-        inv.max_hp = 20;
+    let foes = difficulty::scaled_encounter(inv, inv.clock.time_of_day());
+    fight_encounter(inv, Encounter::new(foes));
+}
+
+/// How an interactive battle ended.
+#[cfg(feature = "cli")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BattleEnd {
+    Victory,
+    Escaped, // every foe ran off before one fell
+    Defeat,
+    Fled,
+    Withdrew,
+    Perished, // hardcore death; the inventory is now a fresh character
+}
+
+// Pick which living foe to strike; no prompt when only one remains
+#[cfg(feature = "cli")]
+fn choose_target(enc: &Encounter) -> Option<usize> {
+    let living = enc.living();
+    if living.len() <= 1 {
+        return living.first().copied();
     }
-    if inv.current_hp == 0 || inv.current_hp > inv.max_hp {
-        inv.current_hp = inv.max_hp;
+    let labels: Vec<String> = living
+        .iter()
+        .map(|&i| format!("{} ({} HP)", enc.foes[i].monster.name, enc.foes[i].hp))
+        .collect();
+    Select::new()
+        .with_prompt("Strike which foe?")
+        .items(&labels)
+        .default(0)
+        .interact()
+        .ok()
+        .map(|c| living[c])
+}
+
+// Interactive fight (random encounter or dungeon room)
+#[cfg(feature = "cli")]
+fn fight_encounter(inv: &mut Inventory, mut enc: Encounter) -> BattleEnd {
+    use std::io::{self, Write};
+    combat::prepare_player(inv);
+    println!("🕰️  {}", inv.clock.label());
+    println!("⚔️  You face {}!", enc.title());
+    for f in &enc.foes {
+        let ability = f.monster.ability.label();
+        println!(
+            "  • {} (HP {} / Damage {:?}){}",
+            f.monster.name,
+            f.hp,
+            f.monster.damage_range(),
+            if ability.is_empty() {
+                String::new()
+            } else {
+                format!(" — {}", ability)
+            }
+        );
+    }
+    println!("🛡️  Your armor class: {}", combat::player_armor_class(inv));
+    for c in &inv.party.companions {
+        println!("🤝 {} fights beside you ({}).", c.title(), c.status_label());
     }
-    let mut m_hp = monster.max_hp();
-    println!(
-        "⚔️  A {} appears! (HP {} / Damage {:?})",
-        monster.name,
-        m_hp,
-        monster.damage_range()
-    );
     loop {
+        let foes: Vec<String> = enc
+            .living()
+            .iter()
+            .map(|&i| format!("{}: {} HP", enc.foes[i].monster.name, enc.foes[i].hp))
+            .collect();
+        let allies: String = inv
+            .party
+            .companions
+            .iter()
+            .map(|c| format!("   {}: {} HP", c.name, c.hp))
+            .collect();
         println!(
-            "You: {}/{} HP   {}: {} HP",
-            inv.current_hp, inv.max_hp, monster.name, m_hp
+            "You: {}/{} HP{}   {}",
+            inv.current_hp,
+            inv.max_hp,
+            allies,
+            foes.join("   ")
         );
-        print!("[A]ttack, [F]lee, or [Q]uit fight? ");
+        print!("[A]ttack, [D]efend, [F]lee, or [Q]uit fight? ");
         let _ = io::stdout().flush();
         let mut line = String::new();
         if io::stdin().read_line(&mut line).is_err() {
@@ -290,90 +596,178 @@ pub fn fight_monster(inv: &mut Inventory) {
             .unwrap_or('a')
             .to_ascii_lowercase();
         match action {
-            'a' => {
-                // Player attack
-                let dmg = with_rng(|r| r.gen_range(2..=6));
-                m_hp = m_hp.saturating_sub(dmg);
-                println!("You strike the {} for {} damage!", monster.name, dmg);
-                if m_hp == 0 {
-                    println!("You slew the {}!", monster.name);
+            'a' | 'd' => {
+                let (lines, status) = if action == 'a' {
+                    let target = choose_target(&enc);
+                    spend_time(inv, FIGHT_ROUND_MINUTES);
+                    enc.round(inv, target)
+                } else {
+                    spend_time(inv, FIGHT_ROUND_MINUTES);
+                    enc.defend(inv)
+                };
+                for l in &lines {
+                    println!("{}", l);
+                }
+                match status {
+                    FightStatus::Ongoing => {}
+                    FightStatus::Victory => {
+                        let before = inv.clone();
+                        let spoils = combat::settle_victory(inv, &enc);
+                        crate::print_event_summary("Victory", &before, inv, &spoils.items(), &[]);
+                        println!("Loot: {} gp", spoils.gold);
+                        if !spoils.drops.is_empty() {
+                            println!("Drops: {}", format_items_for_display(&spoils.drops));
+                        }
+                        for note in spoils.notes {
+                            println!("{}", note);
+                        }
+                        return BattleEnd::Victory;
+                    }
+                    FightStatus::Escaped => {
+                        println!("💨 {} got away: no spoils this time.", enc.title());
+                        return BattleEnd::Escaped;
+                    }
+                    FightStatus::Defeat => {
+                        let before = inv.clone();
+                        match combat::settle_fall(inv, &enc) {
+                            Fall::KnockedOut { loss, removed } => {
+                                crate::print_event_summary("Defeat", &before, inv, &[], &removed);
+                                println!("Lost {} gp and {} item(s).", loss, removed.len());
+                                return BattleEnd::Defeat;
+                            }
+                            Fall::Perished(grave) => {
+                                crate::print_simple_header("You Have Died");
+                                println!("{}", grave.epitaph());
+                                println!(
+                                    "Your tale is carved into {}. A new adventurer steps forward...",
+                                    crate::graveyard::path().display()
+                                );
+                                // `bury` left a fresh inventory that keeps the stats and
+                                // any hot-seat world; the successor moves into it
+                                prompt_take_over(inv);
+                                inv.save_after_pickup();
+                                return BattleEnd::Perished;
+                            }
+                        }
+                    }
                 }
             }
             'f' => {
-                // Flee penalty: lose random item (1) and 5% gold
-                let before_gold = inv.gold_pieces;
-                let gold_loss = ((before_gold as f64) * 0.05).round() as u32;
-                let gold_loss = gold_loss.clamp(0, inv.gold_pieces);
-                inv.gold_pieces -= gold_loss;
-                let mut removed: Vec<String> = Vec::new();
-                if !inv.items.is_empty() {
-                    let idx = with_rng(|r| r.gen_range(0..inv.items.len()));
-                    removed.push(inv.items.remove(idx));
+                if let Some(why) = encumbrance::flee_blocked(inv) {
+                    println!("{}", why);
+                    continue;
                 }
-                let before_inv = inv.clone(); // after removal modifications captured below
-                inv.save_after_pickup();
-                crate::print_event_summary("Fled Battle", &before_inv, inv, &[], &removed);
+                spend_time(inv, FIGHT_ROUND_MINUTES);
+                let before = inv.clone();
+                let (gold_loss, removed) = combat::settle_flee(inv);
+                crate::print_event_summary("Fled Battle", &before, inv, &[], &removed);
                 println!(
                     "You fled, losing {} gp and {} item(s).",
                     gold_loss,
                     removed.len()
                 );
-                return;
+                return BattleEnd::Fled;
             }
             'q' => {
+                if let Some(why) = encumbrance::flee_blocked(inv) {
+                    println!("{}", why);
+                    continue;
+                }
                 println!("You withdraw from the battle.");
-                return;
+                return BattleEnd::Withdrew;
             }
             _ => {
                 println!("Action not recognized.");
                 continue;
             }
         }
-        // Monster turn if still alive
-        if m_hp > 0 {
-            let dmg = with_rng(|r| r.gen_range(monster.damage_range()));
-            inv.current_hp = inv.current_hp.saturating_sub(dmg);
-            println!("The {} hits you for {} damage!", monster.name, dmg);
+    }
+}
+
+#[cfg(feature = "cli")]
+pub fn explore_dungeon(inv: &mut Inventory, loot_items: &[String]) {
+    dungeon::enter(inv);
+    loop {
+        let Some(d) = inv.dungeon.as_ref() else {
+            return;
+        };
+        crate::print_simple_header(&format!("Dungeon — Level {}", d.depth));
+        println!(
+            "🕰️  {}   ❤️  {}/{} HP",
+            inv.clock.label(),
+            inv.current_hp,
+            inv.max_hp
+        );
+        println!(
+            "You stand in the {} ({} of {} rooms explored).",
+            d.room().label(),
+            d.explored_count(),
+            d.rooms.len()
+        );
+        let exits = d.exits();
+        let stairs = d.room().content == RoomContent::Stairs;
+        let mut options: Vec<String> = exits
+            .iter()
+            .map(|e| {
+                format!(
+                    "Go {} to the {}{}",
+                    e.direction,
+                    e.label,
+                    if e.door == Door::Locked {
+                        " (locked door)"
+                    } else {
+                        ""
+                    }
+                )
+            })
+            .collect();
+        if stairs {
+            options.push("Descend the stairs".to_string());
         }
-        if m_hp == 0 {
-            // Reward
-            let min_gp = (10 * (monster.strength as u32).max(1)).max(5);
-            let max_gp = (40 * monster.strength as u32).min(400).max(min_gp + 5);
-            let reward = with_rng(|r| r.gen_range(min_gp..=max_gp));
-            let before = inv.clone();
-            inv.gold_pieces = inv.gold_pieces.saturating_add(reward);
-            inv.save_after_pickup();
-            crate::print_event_summary("Victory", &before, inv, &[], &[]);
-            println!("Loot: {} gp", reward);
+        options.push("Leave the dungeon".to_string());
+        let Ok(choice) = Select::new().items(&options).default(0).interact() else {
             return;
-        } else if inv.current_hp == 0 {
-            println!("You fall unconscious! The {} defeats you...", monster.name);
-            let before = inv.clone();
-            let before_gp = inv.gold_pieces;
-            let loss = ((before_gp as f64) * 0.10).round() as u32;
-            let loss = loss.clamp(0, inv.gold_pieces);
-            inv.gold_pieces -= loss;
-            // Remove up to 3 random items
-            let mut removed = Vec::new();
-            for _ in 0..3 {
-                if inv.items.is_empty() {
-                    break;
+        };
+        if let Some(exit) = exits.get(choice) {
+            match dungeon::move_to(inv, exit.room, loot_items) {
+                Ok(report) => {
+                    for line in &report.lines {
+                        println!("{}", line);
+                    }
+                    if let Some(monster) = report.encounter {
+                        match fight_encounter(inv, Encounter::new(vec![monster])) {
+                            BattleEnd::Victory => dungeon::clear_current(inv),
+                            BattleEnd::Escaped => {
+                                println!("The guardian may yet return to this room.")
+                            }
+                            BattleEnd::Defeat => {
+                                dungeon::return_to_entrance(inv);
+                                println!("You come to at the dungeon entrance.");
+                            }
+                            BattleEnd::Fled | BattleEnd::Withdrew => {
+                                dungeon::retreat(inv);
+                                println!("You fall back the way you came.");
+                            }
+                            BattleEnd::Perished => return,
+                        }
+                    }
                 }
-                let idx = with_rng(|r| r.gen_range(0..inv.items.len()));
-                removed.push(inv.items.remove(idx));
+                Err(e) => println!("{}", e),
             }
-            inv.current_hp = inv.max_hp; // restore after defeat
+        } else if stairs && choice == exits.len() {
+            match dungeon::descend(inv) {
+                Ok(depth) => println!("🪜 You descend to level {}.", depth),
+                Err(e) => println!("{}", e),
+            }
+        } else {
+            println!("You climb back into daylight.");
             inv.save_after_pickup();
-            crate::print_event_summary("Defeat", &before, inv, &[], &removed);
-            println!("Lost {} gp and {} item(s).", loss, removed.len());
             return;
         }
     }
 }
 
-pub const HAGGLE_SUCCESS_CHANCE: f64 = 0.50; // 50% default base chance
-
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Rarity {
     Common,
     Uncommon,
@@ -406,58 +800,128 @@ impl Rarity {
 
 #[cfg(feature = "cli")]
 pub fn visit_shop(inv: &mut Inventory) {
+    crate::print_simple_header("Market Street");
+    println!("Which shop would you like to enter?");
+    let mut options: Vec<String> = ShopKind::ALL
+        .iter()
+        .map(|k| k.label().to_string())
+        .collect();
+    options.push("Leave Market".to_string());
+    let Ok(choice) = Select::new().items(&options).default(0).interact() else {
+        println!("You wander off.");
+        return;
+    };
+    let Some(&kind) = ShopKind::ALL.get(choice) else {
+        println!("You leave the market.");
+        return;
+    };
+    spend_time(inv, SHOP_VISIT_MINUTES);
     loop {
-        crate::print_simple_header("Shop");
-        println!("🛒 You enter a cluttered shop filled with wares.");
+        crate::print_simple_header(kind.label());
+        println!("{}", kind.catalog().greeting);
+        println!(
+            "👛 Merchant purse: {}",
+            format_cp(inv.market.purse_cp(kind))
+        );
         println!("What would you like to do?");
-        let mut options = vec![
-            "Buy Items".to_string(),
-            "Sell Items".to_string(),
-            "Leave Shop".to_string(),
-        ];
+        let fee = kind.catalog().appraisal_fee_cp;
+        let mut options = vec!["Buy Items".to_string(), "Sell Items".to_string()];
         if inv.items.is_empty() {
             options[1] = "Sell Items (none to sell)".into();
         }
+        if let Some(fee) = fee {
+            options.push(format!("Appraise an Item ({} each)", format_cp(fee)));
+        }
+        options.push("Leave Shop".to_string());
         let choice = Select::new().items(&options).default(0).interact();
         let Ok(choice) = choice else {
             println!("You step back from the shop.");
             return;
         };
         match choice {
-            0 => buy_items(inv),
+            0 => buy_items(inv, kind),
             1 => {
                 if !inv.items.is_empty() {
-                    sell_items(inv)
+                    sell_items(inv, kind)
                 } else {
                     println!("You have nothing to sell.")
                 }
             }
-            2 => {
+            2 if fee.is_some() => appraise_items(inv, kind),
+            _ => {
                 println!("You leave the shop.");
                 return;
             }
-            _ => unreachable!(),
         }
     }
 }
 
+// Pick one carried mystery item and pay to have it identified
 #[cfg(feature = "cli")]
-fn sell_items(inv: &mut Inventory) {
+fn appraise_items(inv: &mut Inventory, kind: ShopKind) {
+    let mystery = appraisal::unidentified_indices(inv);
+    if mystery.is_empty() {
+        println!("You carry nothing that needs appraising.");
+        return;
+    }
+    println!("Which item should they take a look at?");
+    let names: Vec<&str> = mystery.iter().map(|&i| inv.items[i].as_str()).collect();
+    let Ok(pick) = Select::new().items(&names).default(0).interact() else {
+        println!("You keep your things to yourself.");
+        return;
+    };
+    match appraisal::appraise_at_shop(inv, kind, mystery[pick]) {
+        Ok(found) => {
+            spend_time(inv, APPRAISAL_MINUTES);
+            println!("🔍 {}.", found.summary());
+        }
+        Err(e) => println!("{}.", e.message()),
+    }
+}
+
+#[cfg(feature = "cli")]
+fn sell_items(inv: &mut Inventory, kind: ShopKind) {
     if inv.items.is_empty() {
         return;
     }
-    let prices_cp: Vec<u32> =
-        with_rng(|r| inv.items.iter().map(|_| r.gen_range(30..=1000)).collect());
-    let display: Vec<String> = inv
-        .items
+    let offers = shop::sell_offers(kind, inv);
+    // Only list items the shop is willing to take
+    let sellable: Vec<usize> = (0..inv.items.len())
+        .filter(|&i| offers[i].is_some())
+        .collect();
+    let refused = inv.items.len() - sellable.len();
+    if refused > 0 {
+        println!(
+            "🤨 The shopkeeper won't touch {} item(s) that look stolen. Try a fence.",
+            refused
+        );
+    }
+    if sellable.is_empty() {
+        println!("Nothing here they will buy.");
+        return;
+    }
+    if sellable
+        .iter()
+        .any(|&i| appraisal::is_unidentified(&inv.items[i]))
+    {
+        println!("💡 Nobody pays much for goods they can't name. An appraisal might help.");
+    }
+    let display: Vec<String> = sellable
         .iter()
-        .zip(&prices_cp)
-        .map(|(it, p)| format!("{} (offers {})", it, format_cp(*p)))
+        .map(|&i| {
+            let stolen = if inv.is_stolen_at(i) { " [hot]" } else { "" };
+            format!(
+                "{}{} (offers {})",
+                inv.items[i],
+                stolen,
+                format_cp(offers[i].unwrap_or(0))
+            )
+        })
         .collect();
     println!("Select items to sell:");
     let selections = MultiSelect::new().items(&display).interact();
-    let selected = match selections {
-        Ok(v) if !v.is_empty() => v,
+    let selected: Vec<usize> = match selections {
+        Ok(v) if !v.is_empty() => v.into_iter().map(|i| sellable[i]).collect(),
         Ok(_) => {
             println!("Nothing sold.");
             return;
@@ -467,9 +931,16 @@ fn sell_items(inv: &mut Inventory) {
             return;
         }
     };
-    let mut total_cp: u32 = 0;
-    for &idx in &selected {
-        total_cp = total_cp.saturating_add(prices_cp[idx]);
+    let listed_cp = selected
+        .iter()
+        .fold(0u32, |acc, &i| acc.saturating_add(offers[i].unwrap_or(0)));
+    let total_cp = shop::sale_total_cp(kind, inv, &selected, &offers);
+    if total_cp < listed_cp {
+        println!(
+            "👛 The merchant only has {} to spare (you asked {}).",
+            format_cp(total_cp),
+            format_cp(listed_cp)
+        );
     }
     println!(
         "💰 Offer: {} for {} item(s).",
@@ -486,86 +957,25 @@ fn sell_items(inv: &mut Inventory) {
         return;
     }
     let before = inv.clone();
-    let set: std::collections::HashSet<usize> = selected.iter().copied().collect();
-    let mut removed = Vec::new();
-    inv.items = inv
-        .items
-        .iter()
-        .enumerate()
-        .filter_map(|(i, it)| {
-            if set.contains(&i) {
-                removed.push(it.clone());
-                None
-            } else {
-                Some(it.clone())
-            }
-        })
-        .collect();
-    inv.add_copper(total_cp);
+    let (removed, _) = shop::sell_selected(inv, kind, &selected, &offers);
     inv.save_after_pickup();
     crate::print_event_summary("Shop Sale", &before, inv, &[], &removed);
     println!("✅ Sold {} item(s).", removed.len());
 }
 
 #[cfg(feature = "cli")]
-fn buy_items(inv: &mut Inventory) {
-    // Stock generation (fresh each visit to Buy screen) with rarity tiers
-    const STOCK: &[(&str, Rarity)] = &[
-        // Mundane (Common / Uncommon)
-        ("Rope (50ft)", Rarity::Common),
-        ("Torch", Rarity::Common),
-        ("Lantern", Rarity::Common),
-        ("Oil Flask", Rarity::Common),
-        ("Iron Rations", Rarity::Common),
-        ("Waterskin", Rarity::Common),
-        ("Lockpicks", Rarity::Uncommon),
-        ("Bedroll", Rarity::Common),
-        ("Backpack", Rarity::Common),
-        ("Shovel", Rarity::Common),
-        ("Grappling Hook", Rarity::Uncommon),
-        ("Hammer & Pitons", Rarity::Common),
-        ("Herb Bundle", Rarity::Common),
-        ("Ink & Quill", Rarity::Common),
-        ("Chalk Pouch", Rarity::Common),
-        // Magical lower tier
-        ("Potion of Healing", Rarity::Uncommon),
-        ("Potion of Invisibility", Rarity::Rare),
-        ("Scroll of Fireball", Rarity::Rare),
-        ("Scroll of Shielding", Rarity::Uncommon),
-        ("Ring of Protection", Rarity::Epic),
-        ("Amulet of Light", Rarity::Epic),
-        ("Wand of Sparks", Rarity::Uncommon),
-        ("Boots of Silence", Rarity::Rare),
-        ("Cloak of Shadows", Rarity::Epic),
-        ("Elixir of Luck", Rarity::Rare),
-        ("Orb of Annihilation Shard", Rarity::Legendary),
-    ];
-    // Shuffle and take slice
-    let mut pool: Vec<(&str, Rarity)> = STOCK.to_vec();
-    with_rng(|r| pool.shuffle(r));
-    let stock_count = with_rng(|r| r.gen_range(6..=10).min(pool.len() as u32)) as usize;
-    let stock: Vec<(&str, Rarity)> = pool.into_iter().take(stock_count).collect();
-    // Generate prices per rarity
-    let prices_cp: Vec<u32> = with_rng(|r| {
-        stock
-            .iter()
-            .map(|(_, rar)| {
-                let range = rar.price_range_cp();
-                // Weight slightly toward lower end by sampling two and taking min for higher rarities
-                let sample =
-                    |r: &mut rand::rngs::SmallRng| r.gen_range(*range.start()..=*range.end());
-                match rar {
-                    Rarity::Epic | Rarity::Legendary => sample(r).min(sample(r)),
-                    _ => sample(r),
-                }
-            })
-            .collect()
-    });
+fn buy_items(inv: &mut Inventory, kind: ShopKind) {
+    // Stock generation (fresh each visit to Buy screen) following the shop's rarity weights
+    let stock = shop::generate_stock(kind, &inv.market);
     let display: Vec<String> = stock
         .iter()
-        .enumerate()
-        .map(|(i, (name, rar))| {
-            format!("{} [{}] ({} )", name, rar.label(), format_cp(prices_cp[i]))
+        .map(|it| {
+            format!(
+                "{} [{}] ({} )",
+                it.name,
+                it.rarity.label(),
+                format_cp(it.price_cp)
+            )
         })
         .collect();
     println!("Select items to buy (rarity influences price):");
@@ -583,44 +993,28 @@ fn buy_items(inv: &mut Inventory) {
     };
     let mut total_cp: u32 = 0;
     for &idx in &selected {
-        total_cp = total_cp.saturating_add(prices_cp[idx]);
+        total_cp = total_cp.saturating_add(stock[idx].price_cp);
     }
     println!(
         "🧾 Base total for {} item(s): {}",
         selected.len(),
         format_cp(total_cp)
     );
-    // Haggle (luck increases success chance and is consumed if present)
+    // Haggle (luck softens the merchant and is consumed if present)
     let mut final_cp = total_cp;
     if Confirm::new()
-        .with_prompt("Attempt to haggle? (-25% success, +10% failure)")
+        .with_prompt("Attempt to haggle? (multi-round, the merchant may walk away)")
         .default(false)
         .interact()
         .unwrap_or(false)
     {
-        let mut success_chance = HAGGLE_SUCCESS_CHANCE;
-        if inv.luck_boost {
-            success_chance = 0.85;
-        }
-        let success = with_rng(|r| r.gen_bool(success_chance));
-        if success {
-            final_cp = ((final_cp as f64) * 0.75).round() as u32;
-            println!(
-                "🤑 Haggle success! New price: {} (chance {:.0}%)",
-                format_cp(final_cp),
-                success_chance * 100.0
-            );
-        } else {
-            final_cp = ((final_cp as f64) * 1.10).round() as u32;
-            println!(
-                "😬 Haggle failed. Merchant raises price to {} (chance {:.0}%)",
-                format_cp(final_cp),
-                success_chance * 100.0
-            );
-        }
-        if inv.luck_boost {
-            inv.luck_boost = false;
-            println!("✨ Your stored luck is spent in the negotiation.");
+        match negotiate(inv, total_cp) {
+            Some(price) => final_cp = price,
+            None => {
+                println!("🚪 The merchant refuses to deal with you any further.");
+                inv.save_after_pickup();
+                return;
+            }
         }
     }
     println!(
@@ -637,26 +1031,71 @@ fn buy_items(inv: &mut Inventory) {
         println!("You decide not to buy.");
         return;
     }
-    if inv.total_cp() < final_cp {
-        println!("Not enough funds.");
-        return;
-    }
-    if !inv.try_spend_cp(final_cp) {
-        println!("Payment processing error.");
-        return;
-    }
     let before = inv.clone();
-    let mut added: Vec<String> = Vec::new();
-    for &idx in &selected {
-        let name = stock[idx].0.to_string();
-        inv.add_item(&name);
-        added.push(name);
+    let added: Vec<String> = selected.iter().map(|&i| stock[i].name.clone()).collect();
+    match shop::purchase(inv, kind, &added, final_cp) {
+        Ok(payment) if !payment.change.is_zero() => {
+            println!("🪙 The shopkeeper counts out {} in change.", payment.change)
+        }
+        Ok(_) => {}
+        Err(e) => {
+            println!("{}", e.message());
+            return;
+        }
     }
-    inv.save_after_pickup();
     crate::print_event_summary("Shop Purchase", &before, inv, &added, &[]);
     println!("✅ Purchased {} item(s).", added.len());
 }
 
+// Interactive counter-offer loop; returns the agreed price or None if the merchant walks away.
+#[cfg(feature = "cli")]
+fn negotiate(inv: &mut Inventory, total_cp: u32) -> Option<u32> {
+    let ctx = HaggleContext::from_inventory(inv, true);
+    if ctx.luck {
+        inv.luck_boost = false;
+        println!("✨ Your stored luck is spent in the negotiation.");
+    }
+    let mut deal = Negotiation::new(total_cp, ctx);
+    println!(
+        "🧔 The merchant eyes you ({} mood, patience {}).",
+        deal.mood.label(),
+        deal.patience
+    );
+    loop {
+        println!("Merchant asks {}.", format_cp(deal.current_ask_cp));
+        let offer: u32 = Input::new()
+            .with_prompt("Your offer in cp (0 accepts the asking price)")
+            .default(0)
+            .interact_text()
+            .unwrap_or(0);
+        let step = if offer == 0 {
+            deal.accept()
+        } else {
+            deal.offer(offer)
+        };
+        match step {
+            HaggleStep::Accepted { price_cp } => {
+                println!(
+                    "🤝 Deal at {} after {} round(s).",
+                    format_cp(price_cp),
+                    deal.round
+                );
+                return Some(price_cp);
+            }
+            HaggleStep::Countered { ask_cp } => {
+                println!(
+                    "😤 \"{}? Not a chance.\" Counter: {} ({} mood, patience {})",
+                    format_cp(offer),
+                    format_cp(ask_cp),
+                    deal.mood.label(),
+                    deal.patience
+                );
+            }
+            HaggleStep::WalkedAway => return None,
+        }
+    }
+}
+
 #[cfg(feature = "cli")]
 pub fn visit_tavern(inv: &mut Inventory) {
     loop {
@@ -667,17 +1106,48 @@ pub fn visit_tavern(inv: &mut Inventory) {
             format!(
                 "Tip Bartender ({} gp, {}% luck)",
                 TAVERN_TIP_COST_GP,
-                (TAVERN_LUCK_CHANCE * 100.0) as u32
+                (tavern_luck_chance(inv) * 100.0).round() as u32
             ),
             format!(
                 "Flirt With Barmaid ({} gp, {}% kiss for luck)",
                 TAVERN_FLIRT_COST_GP,
-                (TAVERN_FLIRT_KISS_CHANCE * 100.0) as u32
+                (tavern_kiss_chance(inv) * 100.0).round() as u32
+            ),
+            format!(
+                "Play Rune Dice ({}-{} cp bets)",
+                GAMBLE_MIN_BET_CP, GAMBLE_MAX_BET_CP
+            ),
+            "Rumor Board & Quests".to_string(),
+            format!(
+                "Hire Companions ({}/{})",
+                inv.party.companions.len(),
+                companion::PARTY_MAX
             ),
             "Leave Tavern".to_string(),
         ];
         crate::print_simple_header("Tavern");
-        println!("🍺 You enter a bustling tavern.");
+        println!("🕰️  {}", inv.clock.label());
+        if !inv.clock.tavern_open() {
+            println!(
+                "🚪 The tavern is shuttered until {:02}:00.",
+                TAVERN_OPEN_HOUR
+            );
+            if Confirm::new()
+                .with_prompt("Wait outside until it opens?")
+                .default(false)
+                .interact()
+                .unwrap_or(false)
+            {
+                let minutes = inv.clock.minutes_until_hour(TAVERN_OPEN_HOUR as u64);
+                spend_time(inv, minutes);
+                continue;
+            }
+            return;
+        }
+        println!(
+            "🍺 You enter the tavern. The room is {}.",
+            inv.clock.tavern_crowd().label().to_lowercase()
+        );
         if inv.luck_boost {
             println!("✨ Stored luck awaits.");
         }
@@ -692,7 +1162,10 @@ pub fn visit_tavern(inv: &mut Inventory) {
             2 => stay_night(inv),
             3 => tip_bartender(inv),
             4 => flirt_barmaid(inv),
-            5 => {
+            5 => gamble_dice(inv),
+            6 => rumor_board(inv),
+            7 => hire_companions(inv),
+            8 => {
                 println!("You leave the tavern.");
                 return;
             }
@@ -716,11 +1189,12 @@ fn buy_drink(inv: &mut Inventory) {
     {
         return;
     }
-    if !inv.try_spend_cp(TAVERN_DRINK_COST_SP * 10) {
+    if !tavern_pay(inv, TAVERN_DRINK_PRICE) {
         println!("Not enough coin.");
         return;
     }
     println!("🥃 You savor a drink.");
+    spend_time(inv, TAVERN_DRINK_MINUTES);
     inv.save_after_pickup();
 }
 
@@ -739,11 +1213,12 @@ fn buy_food(inv: &mut Inventory) {
     {
         return;
     }
-    if !inv.try_spend_cp(TAVERN_FOOD_COST_SP * 10) {
+    if !tavern_pay(inv, TAVERN_FOOD_PRICE) {
         println!("Can't afford meal.");
         return;
     }
     println!("🍖 Warm meal restores you.");
+    spend_time(inv, TAVERN_FOOD_MINUTES);
     inv.save_after_pickup();
 }
 
@@ -762,12 +1237,19 @@ fn stay_night(inv: &mut Inventory) {
     {
         return;
     }
-    if !inv.try_spend_cp(TAVERN_STAY_COST_GP * 100) {
+    if !tavern_pay(inv, TAVERN_STAY_PRICE) {
         println!("Can't afford room.");
         return;
     }
-    println!("🛏️  You rest deeply (benefit TBD).");
-    inv.save_after_pickup();
+    for note in rest_until_morning(inv) {
+        println!("{}", note);
+    }
+    println!(
+        "🛏️  You rest deeply and wake refreshed ({}/{} HP). {}",
+        inv.current_hp,
+        inv.max_hp,
+        inv.clock.label()
+    );
 }
 
 #[cfg(feature = "cli")]
@@ -780,7 +1262,7 @@ fn tip_bartender(inv: &mut Inventory) {
         "Tip costs {} ({} gp) for {}% luck chance.",
         format_cp(TAVERN_TIP_COST_GP * 100),
         TAVERN_TIP_COST_GP,
-        (TAVERN_LUCK_CHANCE * 100.0) as u32
+        (tavern_luck_chance(inv) * 100.0).round() as u32
     );
     if !Confirm::new()
         .with_prompt("Leave tip?")
@@ -790,16 +1272,19 @@ fn tip_bartender(inv: &mut Inventory) {
     {
         return;
     }
-    if !inv.try_spend_cp(TAVERN_TIP_COST_GP * 100) {
+    if !tavern_pay(inv, TAVERN_TIP_PRICE) {
         println!("Need more gold.");
         return;
     }
-    if with_rng(|r| r.gen_bool(TAVERN_LUCK_CHANCE)) {
+    inv.stats.record(StatEvent::TavernTip);
+    let chance = tavern_luck_chance(inv);
+    if with_rng(|r| r.gen_bool(chance)) {
         inv.luck_boost = true;
         println!("🍀 Luck boon gained for next pickpocket.");
     } else {
         println!("🍂 No luck this time.");
     }
+    spend_time(inv, TAVERN_TIP_MINUTES);
     inv.save_after_pickup();
 }
 
@@ -818,11 +1303,12 @@ fn flirt_barmaid(inv: &mut Inventory) {
     {
         return;
     }
-    if !inv.try_spend_cp(TAVERN_FLIRT_COST_GP * 100) {
+    if !tavern_pay(inv, TAVERN_FLIRT_PRICE) {
         println!("You can't afford her attention right now.");
         return;
     }
-    if with_rng(|r| r.gen_bool(TAVERN_FLIRT_KISS_CHANCE)) {
+    let chance = tavern_kiss_chance(inv);
+    if with_rng(|r| r.gen_bool(chance)) {
         if !inv.luck_boost {
             inv.luck_boost = true;
             println!(
@@ -834,5 +1320,474 @@ fn flirt_barmaid(inv: &mut Inventory) {
     } else {
         println!("🙂 She laughs and shakes her head politely. Maybe next time.");
     }
+    spend_time(inv, TAVERN_FLIRT_MINUTES);
     inv.save_after_pickup();
 }
+
+#[cfg(feature = "cli")]
+fn gamble_dice(inv: &mut Inventory) {
+    println!("🎲 A grizzled dealer rattles a pair of dice engraved with runes.");
+    let labels: Vec<&str> = DiceWager::ALL.iter().map(|w| w.label()).collect();
+    let Ok(choice) = Select::new().items(&labels).default(0).interact() else {
+        return;
+    };
+    let wager = DiceWager::ALL[choice];
+    let bet: u32 = Input::new()
+        .with_prompt(format!(
+            "Bet in cp ({}-{}, you have {})",
+            GAMBLE_MIN_BET_CP,
+            GAMBLE_MAX_BET_CP,
+            format_cp(inv.total_cp())
+        ))
+        .default(GAMBLE_MIN_BET_CP)
+        .interact_text()
+        .unwrap_or(0);
+    let spend_luck = inv.luck_boost
+        && Confirm::new()
+            .with_prompt("Spend stored luck for a reroll if you lose?")
+            .default(false)
+            .interact()
+            .unwrap_or(false);
+    match play_rune_dice(inv, bet, wager, spend_luck) {
+        Ok(outcome) => {
+            println!(
+                "{} {}",
+                if outcome.won { "🤑" } else { "💸" },
+                outcome.summary()
+            );
+            if outcome.luck_spent {
+                println!("✨ Your stored luck is spent on the reroll.");
+            }
+            spend_time(inv, TAVERN_GAMBLE_MINUTES);
+        }
+        Err(e) => println!("{}", e.message()),
+    }
+}
+
+#[cfg(feature = "cli")]
+fn rumor_board(inv: &mut Inventory) {
+    let before = inv.clone();
+    let claimed = quest::claim_rewards(inv);
+    if !claimed.is_empty() {
+        for note in &claimed {
+            println!("{}", note);
+        }
+        crate::print_event_summary("Quest Rewards", &before, inv, &[], &[]);
+    }
+    inv.quests.refresh_board();
+    inv.quests.sync_inventory(&inv.items);
+    if !inv.quests.active.is_empty() {
+        println!("📜 Your quests:");
+        for q in &inv.quests.active {
+            println!("  • {} — {}", q.title(), q.progress_label());
+        }
+    }
+    println!("📌 Rumors pinned to the board:");
+    let mut options: Vec<String> = inv
+        .quests
+        .board
+        .iter()
+        .map(|q| {
+            format!(
+                "{} — {} (reward {})",
+                q.title(),
+                q.description(),
+                q.reward_label()
+            )
+        })
+        .collect();
+    options.push("Step away from the board".to_string());
+    let Ok(choice) = Select::new().items(&options).default(0).interact() else {
+        return;
+    };
+    let Some(id) = inv.quests.board.get(choice).map(|q| q.id) else {
+        return;
+    };
+    match inv.quests.accept(id) {
+        Ok(q) => println!(
+            "✍️  Quest accepted: {} ({}h to finish)",
+            q.title(),
+            q.hours_left
+        ),
+        Err(e) => println!("{}", e),
+    }
+    inv.save_after_pickup();
+}
+
+#[cfg(feature = "cli")]
+fn hire_companions(inv: &mut Inventory) {
+    if !inv.party.is_empty() {
+        println!("🤝 Your companions:");
+        for c in &inv.party.companions {
+            println!("  • {} — {}", c.title(), c.status_label());
+        }
+    }
+    let mut options: Vec<String> = CompanionKind::ALL
+        .iter()
+        .map(|k| {
+            format!(
+                "Hire a {} ({}/day) — {}",
+                k.label(),
+                format_cp(k.daily_wage_cp()),
+                k.description()
+            )
+        })
+        .collect();
+    options.extend(
+        inv.party
+            .companions
+            .iter()
+            .map(|c| format!("Dismiss {}", c.title())),
+    );
+    options.push("Step away".to_string());
+    let Ok(choice) = Select::new().items(&options).default(0).interact() else {
+        return;
+    };
+    let kinds = CompanionKind::ALL.len();
+    if let Some(&kind) = CompanionKind::ALL.get(choice) {
+        match companion::hire(inv, kind) {
+            Ok(title) => {
+                println!("🤝 {} joins you, pocketing the first day's wage.", title);
+                spend_time(inv, TAVERN_HIRE_MINUTES);
+            }
+            Err(e) => println!("{}", e.message()),
+        }
+    } else if let Ok(c) = companion::dismiss(inv, choice - kinds) {
+        // Past the dismiss entries is "Step away", which dismiss refuses
+        println!("👋 {} takes their leave.", c.title());
+    }
+}
+
+/// Look through the pack, offering to read a Scroll of Identify over a mystery item.
+#[cfg(feature = "cli")]
+pub fn show_inventory(inv: &mut Inventory) {
+    inv.show();
+    let mystery = appraisal::unidentified_indices(inv);
+    let has_scroll = inv
+        .items
+        .iter()
+        .any(|i| i.eq_ignore_ascii_case(appraisal::IDENTIFY_SCROLL));
+    if mystery.is_empty() || !has_scroll {
+        return;
+    }
+    let read = Confirm::new()
+        .with_prompt(format!("Read a {}?", appraisal::IDENTIFY_SCROLL))
+        .default(false)
+        .interact()
+        .unwrap_or(false);
+    if !read {
+        return;
+    }
+    let names: Vec<&str> = mystery.iter().map(|&i| inv.items[i].as_str()).collect();
+    let Ok(pick) = Select::new().items(&names).default(0).interact() else {
+        return;
+    };
+    match appraisal::read_identify_scroll(inv, mystery[pick]) {
+        Ok(found) => {
+            println!(
+                "📜 The scroll crumbles as its glyphs fade. {}.",
+                found.summary()
+            );
+            spend_time(inv, APPRAISAL_MINUTES);
+        }
+        Err(e) => println!("{}.", e.message()),
+    }
+}
+
+/// Recipe book and workbench: make known recipes or experiment with any items carried.
+#[cfg(feature = "cli")]
+pub fn craft_items(inv: &mut Inventory) {
+    loop {
+        crate::print_simple_header("Crafting");
+        println!(
+            "📖 Recipes discovered: {}/{}",
+            inv.recipes.discovered.len(),
+            crafting::recipes().len()
+        );
+        for r in crafting::recipes() {
+            if inv.recipes.knows(&r.id) {
+                println!("  • {} ← {}", r.makes, r.formula());
+            } else {
+                println!("  • ??? — {}", r.hint);
+            }
+        }
+        let ready = crafting::craftable(inv);
+        let mut options: Vec<String> = ready.iter().map(|r| format!("Make {}", r.makes)).collect();
+        options.push("Experiment with items".to_string());
+        options.push("Done".to_string());
+        let Ok(choice) = Select::new().items(&options).default(0).interact() else {
+            return;
+        };
+        let result = if let Some(r) = ready.get(choice) {
+            crafting::craft(inv, &r.id)
+        } else if choice == ready.len() {
+            if inv.items.is_empty() {
+                println!("You have nothing to tinker with.");
+                continue;
+            }
+            println!("Select items to combine:");
+            match MultiSelect::new().items(&inv.items).interact() {
+                Ok(picked) if !picked.is_empty() => crafting::combine(inv, &picked),
+                _ => continue,
+            }
+        } else {
+            return;
+        };
+        match result {
+            Ok(made) => {
+                println!("🛠️  {}.", made.summary());
+                spend_time(inv, made.minutes);
+            }
+            Err(e) => println!("{}.", e.message()),
+        }
+    }
+}
+
+#[cfg(feature = "cli")]
+pub fn visit_bank(inv: &mut Inventory) {
+    loop {
+        crate::print_simple_header("Bank");
+        println!("🕰️  {}", inv.clock.label());
+        if !inv.clock.bank_open() {
+            println!(
+                "🚪 The bank keeps hours from {:02}:00 to {:02}:00.",
+                BANK_OPEN_HOUR, BANK_CLOSE_HOUR
+            );
+            if Confirm::new()
+                .with_prompt("Wait on the steps until it opens?")
+                .default(false)
+                .interact()
+                .unwrap_or(false)
+            {
+                let minutes = inv.clock.minutes_until_hour(BANK_OPEN_HOUR as u64);
+                spend_time(inv, minutes);
+                continue;
+            }
+            return;
+        }
+        println!(
+            "🏦 Purse: {} — account: {} — vault: {}/{} item(s) ({} a day)",
+            format_cp(inv.total_cp()),
+            format_cp(inv.bank.balance_cp),
+            inv.bank.vault.len(),
+            bank::VAULT_CAPACITY,
+            format_cp(inv.bank.daily_fee_cp())
+        );
+        if inv.bank.fees_owed_cp > 0 {
+            println!(
+                "⚠️  Overdue vault fees: {}",
+                format_cp(inv.bank.fees_owed_cp)
+            );
+        }
+        let options = [
+            "Deposit Coin",
+            "Withdraw Coin",
+            "Store Item In Vault",
+            "Take Item From Vault",
+            "Money Changer",
+            "Leave Bank",
+        ];
+        let Ok(choice) = Select::new().items(options).default(0).interact() else {
+            return;
+        };
+        let result = match choice {
+            0 => {
+                let cp = prompt_cp("Deposit", inv.total_cp());
+                bank::deposit(inv, cp)
+                    .map(|banked| format!("Deposited {}.", format_cp(banked)))
+                    .map_err(|e| e.message())
+            }
+            1 => {
+                let cp = prompt_cp("Withdraw", inv.bank.balance_cp);
+                bank::withdraw(inv, cp)
+                    .map(|cp| format!("Withdrew {}.", format_cp(cp)))
+                    .map_err(|e| e.message())
+            }
+            2 => {
+                if inv.items.is_empty() {
+                    println!("You carry nothing to store.");
+                    continue;
+                }
+                let Ok(i) = Select::new().items(&inv.items).default(0).interact() else {
+                    continue;
+                };
+                bank::store_item(inv, i)
+                    .map(|name| format!("The {} is locked away.", name))
+                    .map_err(|e| e.message())
+            }
+            3 => {
+                if inv.bank.vault.is_empty() {
+                    println!("The vault is empty.");
+                    continue;
+                }
+                let names: Vec<&str> = inv.bank.vault.iter().map(|v| v.name.as_str()).collect();
+                let Ok(i) = Select::new().items(&names).default(0).interact() else {
+                    continue;
+                };
+                bank::retrieve_item(inv, i)
+                    .map(|name| format!("You take back the {}.", name))
+                    .map_err(|e| e.message())
+            }
+            4 => {
+                let Some((from, amount, to)) = prompt_exchange(inv) else {
+                    continue;
+                };
+                currency::exchange(inv, &from, amount, &to)
+                    .map(|x| x.summary())
+                    .map_err(|e| e.message())
+            }
+            _ => return,
+        };
+        match result {
+            Ok(msg) => {
+                println!("{}", msg);
+                spend_time(inv, BANK_VISIT_MINUTES);
+            }
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+// Pick which carried coin to change, how many, and what to change it into
+#[cfg(feature = "cli")]
+fn prompt_exchange(inv: &Inventory) -> Option<(String, u32, String)> {
+    let table = currency::active();
+    println!(
+        "💱 The changer keeps {}% (at least {} cp) of every trade.",
+        (currency::EXCHANGE_FEE * 100.0).round() as u32,
+        currency::EXCHANGE_MIN_FEE_CP
+    );
+    let carried: Vec<&currency::Denomination> = table
+        .denominations
+        .iter()
+        .filter(|d| inv.coin_count(&d.code) > 0)
+        .collect();
+    if carried.is_empty() {
+        println!("You have no coin to change.");
+        return None;
+    }
+    let labels: Vec<String> = carried
+        .iter()
+        .map(|d| d.describe(inv.coin_count(&d.code)))
+        .collect();
+    let from = carried[Select::new()
+        .with_prompt("Change which coins?")
+        .items(&labels)
+        .default(0)
+        .interact()
+        .ok()?];
+    let have = inv.coin_count(&from.code);
+    let amount: u32 = Input::new()
+        .with_prompt(format!("How many (up to {})", have))
+        .default(have)
+        .interact_text()
+        .ok()?;
+    let targets: Vec<&currency::Denomination> = table
+        .denominations
+        .iter()
+        .filter(|d| d.code != from.code)
+        .collect();
+    let names: Vec<&str> = targets.iter().map(|d| d.plural.as_str()).collect();
+    let to = targets[Select::new()
+        .with_prompt("Into")
+        .items(&names)
+        .default(0)
+        .interact()
+        .ok()?];
+    Some((from.code.clone(), amount, to.code.clone()))
+}
+
+// Ask for an amount in cp, offering everything available as the default
+#[cfg(feature = "cli")]
+fn prompt_cp(verb: &str, available_cp: u32) -> u32 {
+    Input::new()
+        .with_prompt(format!(
+            "{} how much, in cp (up to {})",
+            verb,
+            format_cp(available_cp)
+        ))
+        .default(available_cp)
+        .interact_text()
+        .unwrap_or(0)
+}
+
+/// Offer another player at the table coin and items for theirs. The other player takes
+/// the keyboard to accept or decline.
+#[cfg(feature = "cli")]
+pub fn trade_goods(table: &mut HotSeat) {
+    crate::print_simple_header("Trade");
+    let from = table.turn;
+    let others: Vec<usize> = (0..table.players.len()).filter(|&i| i != from).collect();
+    let names: Vec<String> = others.iter().map(|&i| table.name_of(i)).collect();
+    let Ok(pick) = Select::new()
+        .with_prompt("Trade with")
+        .items(&names)
+        .default(0)
+        .interact()
+    else {
+        return;
+    };
+    let to = others[pick];
+    println!("You offer:");
+    let give = prompt_bundle(&table.players[from], "Give");
+    println!("You ask {} for:", table.name_of(to));
+    let take = prompt_bundle(&table.players[to], "Ask for");
+    let trade = Trade {
+        from,
+        to,
+        give,
+        take,
+    };
+    if let Err(e) = table.check_trade(&trade) {
+        println!("{}", e.message());
+        return;
+    }
+    println!(
+        "🤝 {} offers {} for {}.",
+        table.name_of(from),
+        trade.give.label(&table.players[from]),
+        trade.take.label(&table.players[to])
+    );
+    println!("Pass the keyboard to {}.", table.name_of(to));
+    let accepted = Confirm::new()
+        .with_prompt(format!("{}, do you accept?", table.name_of(to)))
+        .default(false)
+        .interact()
+        .unwrap_or(false);
+    let result = if accepted {
+        table.execute_trade(&trade)
+    } else {
+        Err(TradeError::Declined)
+    };
+    match result {
+        Ok(()) => println!("The deal is done."),
+        Err(e) => println!("{}", e.message()),
+    }
+}
+
+// One side of a trade, picked from what `inv` carries
+#[cfg(feature = "cli")]
+fn prompt_bundle(inv: &Inventory, verb: &str) -> Bundle {
+    let coin_cp = Input::new()
+        .with_prompt(format!(
+            "{} how much coin, in cp (up to {})",
+            verb,
+            format_cp(inv.total_cp())
+        ))
+        .default(0)
+        .interact_text()
+        .unwrap_or(0);
+    let items = if inv.items.is_empty() {
+        Vec::new()
+    } else {
+        MultiSelect::new()
+            .with_prompt(format!(
+                "{} which items? (space to pick, enter when done)",
+                verb
+            ))
+            .items(&inv.items)
+            .interact()
+            .unwrap_or_default()
+    };
+    Bundle { coin_cp, items }
+}
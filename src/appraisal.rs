@@ -0,0 +1,234 @@
+use crate::actions::Rarity;
+use crate::inventory::{Inventory, format_cp};
+use crate::money::{Money, MoneyError};
+use crate::rng::with_rng;
+use crate::shop::ShopKind;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+pub const IDENTIFY_SCROLL: &str = "Scroll of Identify";
+// Share of looted magic items that turn up looking like their plain appearance
+pub const UNIDENTIFIED_CHANCE: f64 = 0.5;
+// Merchants won't gamble much on something nobody has looked at properly
+pub const UNIDENTIFIED_OFFER_CP: std::ops::RangeInclusive<u32> = 5..=40;
+
+/// Something that arrives unidentified: what it looks like, and what it may turn out to be
+/// (name, rarity, weight). Disguised loot keeps the identity it had; anything found already
+/// looking plain has its identity settled when it is appraised.
+pub struct Mystery {
+    pub appearance: &'static str,
+    pub identities: &'static [(&'static str, Rarity, u32)],
+}
+
+pub const MYSTERIES: &[Mystery] = &[
+    Mystery {
+        appearance: "Murky potion",
+        identities: &[
+            ("Potion of Healing", Rarity::Uncommon, 4),
+            ("Potion of Climbing", Rarity::Uncommon, 3),
+            ("Potion of Water Breathing", Rarity::Uncommon, 3),
+            ("Spoiled tonic", Rarity::Common, 3),
+            ("Potion of Invisibility", Rarity::Rare, 2),
+            ("Elixir of Luck", Rarity::Rare, 1),
+            ("Philter of Love", Rarity::Rare, 1),
+            ("Potion of Giant Strength", Rarity::Epic, 1),
+        ],
+    },
+    Mystery {
+        appearance: "Unusual ring",
+        identities: &[
+            ("Copper ring", Rarity::Common, 4),
+            ("Moonstone ring", Rarity::Uncommon, 2),
+            ("Ring of Protection", Rarity::Epic, 1),
+        ],
+    },
+    Mystery {
+        appearance: "Vellum scroll",
+        identities: &[
+            ("Scroll of bad poetry", Rarity::Common, 3),
+            ("Scroll of Shielding", Rarity::Uncommon, 3),
+            ("Scroll of Fireball", Rarity::Rare, 2),
+            ("Forged Papers", Rarity::Uncommon, 1),
+            ("Smuggler's Map", Rarity::Rare, 1),
+        ],
+    },
+    Mystery {
+        appearance: "Jeweled clasp",
+        identities: &[
+            ("Paste-jeweled clasp", Rarity::Common, 4),
+            ("Gold jeweled clasp", Rarity::Rare, 3),
+            ("Jeweled clasp of Warding", Rarity::Epic, 1),
+        ],
+    },
+    Mystery {
+        appearance: "Strange wand",
+        identities: &[
+            ("Carved stick", Rarity::Common, 3),
+            ("Wand of Sparks", Rarity::Uncommon, 2),
+        ],
+    },
+];
+
+fn mystery(item: &str) -> Option<&'static Mystery> {
+    MYSTERIES
+        .iter()
+        .find(|m| m.appearance.eq_ignore_ascii_case(item.trim()))
+}
+
+pub fn is_unidentified(item: &str) -> bool {
+    mystery(item).is_some()
+}
+
+/// Rarity of anything a mystery item can turn out to be, for pricing.
+pub fn identity_rarity(name: &str) -> Option<Rarity> {
+    MYSTERIES
+        .iter()
+        .flat_map(|m| m.identities.iter())
+        .find(|(n, _, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, r, _)| *r)
+}
+
+/// Looted magic items sometimes turn up unrecognised: "Potion of Climbing" may arrive as
+/// "Murky potion". Returns what the item looks like and, when disguised, its true name
+/// (for `Inventory::push_disguised`). Anything else passes through unchanged.
+pub fn disguise(item: &str) -> (String, Option<String>) {
+    let Some(m) = MYSTERIES.iter().find(|m| {
+        m.identities
+            .iter()
+            .any(|(n, r, _)| *r != Rarity::Common && n.eq_ignore_ascii_case(item))
+    }) else {
+        return (item.to_string(), None);
+    };
+    if with_rng(|r| r.gen_bool(UNIDENTIFIED_CHANCE)) {
+        (m.appearance.to_string(), Some(item.to_string()))
+    } else {
+        (item.to_string(), None)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppraisalError {
+    NoSuchItem,
+    AlreadyKnown, // nothing mysterious about it
+    NoAppraiser,  // this shop doesn't appraise
+    NoScroll,     // no Scroll of Identify to read
+    InsufficientFunds,
+    NoExactChange,
+}
+
+impl AppraisalError {
+    pub fn message(&self) -> String {
+        match self {
+            AppraisalError::NoSuchItem => "There's no such item".into(),
+            AppraisalError::AlreadyKnown => "You already know exactly what that is".into(),
+            AppraisalError::NoAppraiser => "Nobody here appraises goods".into(),
+            AppraisalError::NoScroll => format!("You need a {} for that", IDENTIFY_SCROLL),
+            AppraisalError::InsufficientFunds => "You can't afford the appraisal fee".into(),
+            AppraisalError::NoExactChange => {
+                "You don't have the exact coins for the appraisal fee".into()
+            }
+        }
+    }
+}
+
+/// What an unidentified item turned out to be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Appraisal {
+    pub was: String,
+    pub now: String,
+    pub rarity: Rarity,
+    pub value_cp: u32, // rough worth: the middle of its rarity's price range
+    pub fee_cp: u32,
+}
+
+impl Appraisal {
+    pub fn summary(&self) -> String {
+        let mut s = format!(
+            "The {} turns out to be: {} ({}, worth about {})",
+            self.was.to_lowercase(),
+            self.now,
+            self.rarity.label(),
+            format_cp(self.value_cp)
+        );
+        if self.fee_cp > 0 {
+            s.push_str(&format!(" — appraisal fee {}", format_cp(self.fee_cp)));
+        }
+        s
+    }
+}
+
+// Show what the item at `idx` really is and put it in the pack in its place. Disguised loot
+// is what it always was; a find that only ever looked plain is settled by a roll.
+fn reveal(inv: &mut Inventory, idx: usize, fee_cp: u32) -> Appraisal {
+    let was = inv.items[idx].clone();
+    let m = mystery(&was).expect("caller checked the item is unidentified");
+    let known = inv.true_name_at(idx).and_then(|t| {
+        m.identities
+            .iter()
+            .find(|(n, _, _)| n.eq_ignore_ascii_case(t))
+    });
+    let &(name, rarity, _) = known.unwrap_or_else(|| {
+        with_rng(|r| m.identities.choose_weighted(r, |(_, _, w)| *w)).unwrap_or(&m.identities[0])
+    });
+    let stolen = inv.is_stolen_at(idx);
+    inv.remove_item_at(idx);
+    inv.insert_item(idx, name.to_string(), stolen);
+    inv.save_after_pickup();
+    let range = rarity.price_range_cp();
+    Appraisal {
+        was,
+        now: name.to_string(),
+        rarity,
+        value_cp: (range.start() + range.end()) / 2,
+        fee_cp,
+    }
+}
+
+fn check(inv: &Inventory, idx: usize) -> Result<(), AppraisalError> {
+    let item = inv.items.get(idx).ok_or(AppraisalError::NoSuchItem)?;
+    if !is_unidentified(item) {
+        return Err(AppraisalError::AlreadyKnown);
+    }
+    Ok(())
+}
+
+/// Have a shop appraise the item at `idx` for its fee (see `appraisal_fee_cp` in the
+/// shop catalogs).
+pub fn appraise_at_shop(
+    inv: &mut Inventory,
+    kind: ShopKind,
+    idx: usize,
+) -> Result<Appraisal, AppraisalError> {
+    let fee = kind
+        .catalog()
+        .appraisal_fee_cp
+        .ok_or(AppraisalError::NoAppraiser)?;
+    check(inv, idx)?;
+    inv.pay(Money::from(fee), inv.pay_mode)
+        .map_err(|e| match e {
+            MoneyError::NoExactChange => AppraisalError::NoExactChange,
+            MoneyError::InsufficientFunds => AppraisalError::InsufficientFunds,
+        })?;
+    inv.market.take_in(kind, fee);
+    Ok(reveal(inv, idx, fee))
+}
+
+/// Read a Scroll of Identify over the item at `idx`; the scroll crumbles.
+pub fn read_identify_scroll(inv: &mut Inventory, idx: usize) -> Result<Appraisal, AppraisalError> {
+    check(inv, idx)?;
+    let scroll = inv
+        .items
+        .iter()
+        .position(|i| i.eq_ignore_ascii_case(IDENTIFY_SCROLL))
+        .ok_or(AppraisalError::NoScroll)?;
+    inv.remove_item_at(scroll);
+    let idx = if scroll < idx { idx - 1 } else { idx };
+    Ok(reveal(inv, idx, 0))
+}
+
+/// Carried items still waiting to be identified, by inventory index.
+pub fn unidentified_indices(inv: &Inventory) -> Vec<usize> {
+    (0..inv.items.len())
+        .filter(|&i| is_unidentified(&inv.items[i]))
+        .collect()
+}
@@ -0,0 +1,157 @@
+use crate::inventory::{Inventory, format_cp};
+use serde::{Deserialize, Serialize};
+
+// Savings earn a little each day; every vault slot costs a little each day
+pub const BANK_DAILY_INTEREST: f64 = 0.005;
+pub const VAULT_FEE_CP_PER_DAY: u32 = 10; // per stored item
+pub const VAULT_CAPACITY: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BankError {
+    ZeroAmount,
+    InsufficientFunds,   // not enough carried coin to deposit
+    InsufficientBalance, // not enough banked coin to withdraw
+    NoSuchItem,
+    VaultFull,
+    FeesOwed(u32), // overdue vault fees (cp) must be settled before taking items out
+}
+
+impl BankError {
+    pub fn message(&self) -> String {
+        match self {
+            BankError::ZeroAmount => "Name an amount first".into(),
+            BankError::InsufficientFunds => "You aren't carrying that much".into(),
+            BankError::InsufficientBalance => "Your account doesn't hold that much".into(),
+            BankError::NoSuchItem => "There's no such item".into(),
+            BankError::VaultFull => format!("The vault holds at most {} items", VAULT_CAPACITY),
+            BankError::FeesOwed(cp) => format!(
+                "The clerk wants the {} in overdue vault fees first",
+                format_cp(*cp)
+            ),
+        }
+    }
+}
+
+/// An item in the vault; stolen goods stay stolen while they wait.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultItem {
+    pub name: String,
+    #[serde(default)]
+    pub stolen: bool,
+    #[serde(default)]
+    pub true_name: Option<String>, // what a disguised item really is
+}
+
+/// Saved bank account: coin and vault items here are safe from fights and pickpocket mishaps.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bank {
+    pub balance_cp: u32,
+    pub vault: Vec<VaultItem>,
+    pub fees_owed_cp: u32,
+    hours_pending: u32, // hours since the last daily settlement
+}
+
+impl Bank {
+    /// Let time pass: each full day pays interest on the balance and charges vault fees
+    /// (from the balance, or owed when it runs dry). Returns notes worth telling the player.
+    pub fn pass_time(&mut self, hours: u32) -> Vec<String> {
+        self.hours_pending = self.hours_pending.saturating_add(hours);
+        let days = self.hours_pending / 24;
+        self.hours_pending %= 24;
+        let mut interest = 0u32;
+        let mut fees = 0u32;
+        for _ in 0..days {
+            let earned = ((self.balance_cp as f64) * BANK_DAILY_INTEREST).floor() as u32;
+            self.balance_cp = self.balance_cp.saturating_add(earned);
+            interest = interest.saturating_add(earned);
+            let fee = VAULT_FEE_CP_PER_DAY * self.vault.len() as u32;
+            let paid = fee.min(self.balance_cp);
+            self.balance_cp -= paid;
+            self.fees_owed_cp = self.fees_owed_cp.saturating_add(fee - paid);
+            fees = fees.saturating_add(fee);
+        }
+        let mut notes = Vec::new();
+        if interest > 0 {
+            notes.push(format!(
+                "🏦 Your savings earned {} in interest.",
+                format_cp(interest)
+            ));
+        }
+        if fees > 0 && self.fees_owed_cp > 0 {
+            notes.push(format!(
+                "🏦 Vault fees are overdue: you owe the bank {}.",
+                format_cp(self.fees_owed_cp)
+            ));
+        }
+        notes
+    }
+
+    /// Daily fee for what's in the vault now.
+    pub fn daily_fee_cp(&self) -> u32 {
+        VAULT_FEE_CP_PER_DAY * self.vault.len() as u32
+    }
+}
+
+/// Move carried coin into the account. Overdue fees are settled out of the deposit first.
+pub fn deposit(inv: &mut Inventory, cp: u32) -> Result<u32, BankError> {
+    if cp == 0 {
+        return Err(BankError::ZeroAmount);
+    }
+    if !inv.try_spend_cp(cp) {
+        return Err(BankError::InsufficientFunds);
+    }
+    let settled = cp.min(inv.bank.fees_owed_cp);
+    inv.bank.fees_owed_cp -= settled;
+    inv.bank.balance_cp = inv.bank.balance_cp.saturating_add(cp - settled);
+    inv.save_after_pickup();
+    Ok(cp - settled)
+}
+
+/// Take coin out of the account and into the purse.
+pub fn withdraw(inv: &mut Inventory, cp: u32) -> Result<u32, BankError> {
+    if cp == 0 {
+        return Err(BankError::ZeroAmount);
+    }
+    if inv.bank.balance_cp < cp {
+        return Err(BankError::InsufficientBalance);
+    }
+    inv.bank.balance_cp -= cp;
+    inv.add_copper(cp);
+    inv.save_after_pickup();
+    Ok(cp)
+}
+
+/// Lock the carried item at `idx` in the vault.
+pub fn store_item(inv: &mut Inventory, idx: usize) -> Result<String, BankError> {
+    if idx >= inv.items.len() {
+        return Err(BankError::NoSuchItem);
+    }
+    if inv.bank.vault.len() >= VAULT_CAPACITY {
+        return Err(BankError::VaultFull);
+    }
+    let stolen = inv.is_stolen_at(idx);
+    let true_name = inv.true_name_at(idx).map(str::to_string);
+    let name = inv.remove_item_at(idx);
+    inv.bank.vault.push(VaultItem {
+        name: name.clone(),
+        stolen,
+        true_name,
+    });
+    inv.save_after_pickup();
+    Ok(name)
+}
+
+/// Take the vault item at `idx` back out; refused while vault fees are overdue.
+pub fn retrieve_item(inv: &mut Inventory, idx: usize) -> Result<String, BankError> {
+    if idx >= inv.bank.vault.len() {
+        return Err(BankError::NoSuchItem);
+    }
+    if inv.bank.fees_owed_cp > 0 {
+        return Err(BankError::FeesOwed(inv.bank.fees_owed_cp));
+    }
+    let item = inv.bank.vault.remove(idx);
+    inv.push_disguised(item.name.clone(), item.true_name, item.stolen);
+    inv.save_after_pickup();
+    Ok(item.name)
+}
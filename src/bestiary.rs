@@ -0,0 +1,392 @@
+use crate::clock::TimeOfDay;
+use crate::rng::with_rng;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+/// When a creature is out hunting.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Activity {
+    Any,
+    Diurnal,   // dawn through dusk
+    Nocturnal, // dusk through the night
+}
+
+impl Activity {
+    pub fn active_at(&self, tod: TimeOfDay) -> bool {
+        match self {
+            Activity::Any => true,
+            Activity::Diurnal => tod != TimeOfDay::Night,
+            Activity::Nocturnal => matches!(tod, TimeOfDay::Dusk | TimeOfDay::Night),
+        }
+    }
+}
+
+/// Signature trick a monster brings to a fight (resolved in `combat`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ability {
+    None,
+    Drain,      // heals itself by the damage it deals
+    Paralyze,   // a hit may freeze the player for a round
+    Thief,      // a hit may snatch an item, then it runs
+    FireBreath, // every third turn breathes fire instead of biting
+    Stoneskin,  // light weapons only do half damage
+}
+
+impl Ability {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Ability::None => "",
+            Ability::Drain => "drains life",
+            Ability::Paralyze => "paralyzing touch",
+            Ability::Thief => "steals and runs",
+            Ability::FireBreath => "breathes fire",
+            Ability::Stoneskin => "resists light weapons",
+        }
+    }
+}
+
+/// How a monster reacts once badly wounded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Temper {
+    Steady,   // fights on regardless
+    Cowardly, // runs for it
+    Berserk,  // flies into a rage and hits harder
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Monster {
+    pub name: &'static str,
+    pub strength: u8, // influences hp & damage
+    pub activity: Activity,
+    pub ability: Ability,
+    pub temper: Temper,
+}
+
+impl Monster {
+    pub fn max_hp(&self) -> u32 {
+        5 + (self.strength as u32 * 5)
+    }
+    pub fn damage_range(&self) -> std::ops::RangeInclusive<u32> {
+        let base = self.strength as u32;
+        base..=base + 4
+    }
+    pub fn armor_class(&self) -> u32 {
+        10 + self.strength as u32 / 2
+    }
+    pub fn attack_bonus(&self) -> u32 {
+        1 + self.strength as u32 / 2
+    }
+}
+
+const fn m(
+    name: &'static str,
+    strength: u8,
+    activity: Activity,
+    ability: Ability,
+    temper: Temper,
+) -> Monster {
+    Monster {
+        name,
+        strength,
+        activity,
+        ability,
+        temper,
+    }
+}
+
+pub const MONSTERS: &[Monster] = &[
+    m(
+        "Goblin Sneak",
+        1,
+        Activity::Any,
+        Ability::None,
+        Temper::Cowardly,
+    ),
+    m(
+        "Cave Rat",
+        1,
+        Activity::Any,
+        Ability::Thief,
+        Temper::Cowardly,
+    ),
+    m(
+        "Skeleton Guard",
+        2,
+        Activity::Nocturnal,
+        Ability::None,
+        Temper::Steady,
+    ),
+    m(
+        "Orc Marauder",
+        3,
+        Activity::Diurnal,
+        Ability::None,
+        Temper::Berserk,
+    ),
+    m(
+        "Ghoul",
+        4,
+        Activity::Nocturnal,
+        Ability::Paralyze,
+        Temper::Steady,
+    ),
+    m(
+        "Ogre Brute",
+        5,
+        Activity::Diurnal,
+        Ability::None,
+        Temper::Berserk,
+    ),
+    m(
+        "Wyvern",
+        6,
+        Activity::Diurnal,
+        Ability::None,
+        Temper::Cowardly,
+    ),
+    m(
+        "Vampire Stalker",
+        7,
+        Activity::Nocturnal,
+        Ability::Drain,
+        Temper::Steady,
+    ),
+    m(
+        "Stone Golem",
+        8,
+        Activity::Any,
+        Ability::Stoneskin,
+        Temper::Steady,
+    ),
+    m(
+        "Ancient Lich",
+        9,
+        Activity::Nocturnal,
+        Ability::None,
+        Temper::Steady,
+    ),
+    m(
+        "Dragon Wyrm",
+        10,
+        Activity::Diurnal,
+        Ability::FireBreath,
+        Temper::Berserk,
+    ),
+];
+
+pub fn find(name: &str) -> Option<Monster> {
+    MONSTERS
+        .iter()
+        .find(|m| m.name.eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// Monsters that may be met at this time of day.
+pub fn encounter_table(tod: TimeOfDay) -> Vec<Monster> {
+    MONSTERS
+        .iter()
+        .filter(|m| m.activity.active_at(tod))
+        .copied()
+        .collect()
+}
+
+pub fn random_monster(tod: TimeOfDay) -> Monster {
+    let table = encounter_table(tod);
+    with_rng(|r| *table.choose(r).unwrap())
+}
+
+// Chance a random encounter is a group rather than a lone monster
+pub const GROUP_CHANCE: f64 = 0.30;
+
+/// A band of monsters met together; the first member leads.
+#[derive(Clone, Copy, Debug)]
+pub struct MonsterGroup {
+    pub name: &'static str,
+    pub members: &'static [&'static str],
+}
+
+pub const GROUPS: &[MonsterGroup] = &[
+    MonsterGroup {
+        name: "Goblin pack",
+        members: &["Goblin Sneak", "Goblin Sneak", "Goblin Sneak"],
+    },
+    MonsterGroup {
+        name: "Rat swarm",
+        members: &["Cave Rat", "Cave Rat", "Cave Rat", "Cave Rat"],
+    },
+    MonsterGroup {
+        name: "Orc war band",
+        members: &["Orc Marauder", "Orc Marauder", "Goblin Sneak"],
+    },
+    MonsterGroup {
+        name: "Ghoul feast",
+        members: &["Ghoul", "Ghoul"],
+    },
+    MonsterGroup {
+        name: "Lich and honour guard",
+        members: &["Ancient Lich", "Skeleton Guard", "Skeleton Guard"],
+    },
+];
+
+impl MonsterGroup {
+    pub fn monsters(&self) -> Vec<Monster> {
+        self.members.iter().filter_map(|n| find(n)).collect()
+    }
+    fn active_at(&self, tod: TimeOfDay) -> bool {
+        self.monsters().iter().all(|m| m.activity.active_at(tod))
+    }
+}
+
+/// Roll a random encounter: usually a lone monster, sometimes a group.
+pub fn random_encounter(tod: TimeOfDay) -> Vec<Monster> {
+    weighted_encounter(tod, |_| 1.0)
+}
+
+/// Like `random_encounter`, but each monster is picked in proportion to `weight`
+/// (zero rules it out). A group's weight is the product of its members'.
+/// Falls back to the weakest active monster if everything is ruled out.
+pub fn weighted_encounter(tod: TimeOfDay, weight: impl Fn(&Monster) -> f64) -> Vec<Monster> {
+    let groups: Vec<(&MonsterGroup, f64)> = GROUPS
+        .iter()
+        .filter(|g| g.active_at(tod))
+        .map(|g| (g, g.monsters().iter().map(&weight).product::<f64>()))
+        .filter(|(_, w)| *w > 0.0)
+        .collect();
+    let group = with_rng(|r| {
+        if !groups.is_empty() && r.gen_bool(GROUP_CHANCE) {
+            groups.choose_weighted(r, |(_, w)| *w).ok().map(|(g, _)| *g)
+        } else {
+            None
+        }
+    });
+    if let Some(g) = group {
+        return g.monsters();
+    }
+    vec![weighted_monster(tod, weight)]
+}
+
+/// A lone monster picked in proportion to `weight`; the weakest active one if all weigh zero.
+pub fn weighted_monster(tod: TimeOfDay, weight: impl Fn(&Monster) -> f64) -> Monster {
+    let table = encounter_table(tod);
+    let picked = with_rng(|r| {
+        table
+            .choose_weighted(r, |m| weight(m).max(0.0))
+            .ok()
+            .copied()
+    });
+    picked.unwrap_or_else(|| *table.iter().min_by_key(|m| m.strength).unwrap())
+}
+
+/// One line of a monster's drop table; each is rolled independently.
+#[derive(Clone, Copy, Debug)]
+pub enum Drop {
+    Item(&'static str, f64),            // name, chance
+    Coins(u32, u32, &'static str, f64), // min, max, denomination, chance
+}
+
+pub const DROP_TABLES: &[(&str, &[Drop])] = &[
+    (
+        "Goblin Sneak",
+        &[
+            Drop::Coins(2, 12, "cp", 0.6),
+            Drop::Item("Crude Dagger", 0.2),
+            Drop::Item("Stolen Trinket", 0.1),
+        ],
+    ),
+    (
+        "Cave Rat",
+        &[Drop::Item("Rat Tail", 0.5), Drop::Coins(1, 6, "cp", 0.3)],
+    ),
+    (
+        "Skeleton Guard",
+        &[
+            Drop::Item("Rusty Sword", 0.35),
+            Drop::Item("Bone Shard", 0.5),
+            Drop::Coins(1, 8, "sp", 0.3),
+        ],
+    ),
+    (
+        "Orc Marauder",
+        &[
+            Drop::Coins(5, 20, "sp", 0.5),
+            Drop::Item("Orcish Cleaver", 0.2),
+            Drop::Item("Orc Tusk", 0.4),
+        ],
+    ),
+    (
+        "Ghoul",
+        &[
+            Drop::Item("Grave Dust", 0.5),
+            Drop::Item("Tarnished Locket", 0.15),
+        ],
+    ),
+    (
+        "Ogre Brute",
+        &[
+            Drop::Coins(10, 40, "sp", 0.5),
+            Drop::Item("Ogre Club", 0.25),
+            Drop::Item("Wooden Shield", 0.1),
+        ],
+    ),
+    (
+        "Wyvern",
+        &[
+            Drop::Item("Wyvern Scale", 0.7),
+            Drop::Item("Wyvern Stinger", 0.25),
+        ],
+    ),
+    (
+        "Vampire Stalker",
+        &[
+            Drop::Item("Vampire Fang", 0.4),
+            Drop::Coins(2, 10, "gp", 0.4),
+            Drop::Item("Silver Ring", 0.2),
+        ],
+    ),
+    (
+        "Stone Golem",
+        &[
+            Drop::Item("Golem Core", 0.3),
+            Drop::Item("Chunk of Granite", 0.6),
+        ],
+    ),
+    (
+        "Ancient Lich",
+        &[
+            Drop::Item("Phylactery Shard", 0.3),
+            Drop::Item("Spellbook", 0.2),
+            Drop::Coins(10, 30, "gp", 0.5),
+        ],
+    ),
+    (
+        "Dragon Wyrm",
+        &[
+            Drop::Item("Dragon Scale", 0.8),
+            Drop::Item("Dragon Tooth", 0.4),
+            Drop::Coins(20, 60, "gp", 0.6),
+        ],
+    ),
+];
+
+pub fn drop_table(name: &str) -> &'static [Drop] {
+    DROP_TABLES
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map_or(&[], |(_, d)| *d)
+}
+
+/// Roll a monster's drop table: item names and coin strings like "12 sp",
+/// ready for `Inventory::add_item`.
+pub fn roll_drops(m: &Monster) -> Vec<String> {
+    with_rng(|r| {
+        drop_table(m.name)
+            .iter()
+            .filter_map(|d| match *d {
+                Drop::Item(name, chance) => r.gen_bool(chance).then(|| name.to_string()),
+                Drop::Coins(min, max, denom, chance) => r
+                    .gen_bool(chance)
+                    .then(|| format!("{} {}", r.gen_range(min..=max), denom)),
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,243 @@
+use crate::difficulty::Difficulty;
+use crate::inventory::Inventory;
+use serde::{Deserialize, Serialize};
+
+pub const BASE_MAX_HP: u32 = 20;
+pub const NAME_MAX_LEN: usize = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CharacterClass {
+    Rogue,   // light fingers
+    Fighter, // tough and hard-hitting
+    Bard,    // silver tongue and a way with crowds
+}
+
+impl CharacterClass {
+    pub const ALL: [CharacterClass; 3] = [
+        CharacterClass::Rogue,
+        CharacterClass::Fighter,
+        CharacterClass::Bard,
+    ];
+
+    pub fn parse(s: &str) -> Option<CharacterClass> {
+        match s.trim().to_lowercase().as_str() {
+            "rogue" | "r" => Some(CharacterClass::Rogue),
+            "fighter" | "f" => Some(CharacterClass::Fighter),
+            "bard" | "b" => Some(CharacterClass::Bard),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CharacterClass::Rogue => "Rogue",
+            CharacterClass::Fighter => "Fighter",
+            CharacterClass::Bard => "Bard",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            CharacterClass::Rogue => "Quick hands: better odds picking pockets.",
+            CharacterClass::Fighter => "Drilled in arms: more hit points, surer and harder blows.",
+            CharacterClass::Bard => "Silver tongue: better haggling and tavern luck.",
+        }
+    }
+
+    pub fn max_hp(&self) -> u32 {
+        match self {
+            CharacterClass::Fighter => BASE_MAX_HP + 10,
+            _ => BASE_MAX_HP,
+        }
+    }
+
+    /// Added to the pickpocket success chance.
+    pub fn pickpocket_bonus(&self) -> f64 {
+        match self {
+            CharacterClass::Rogue => 0.15,
+            CharacterClass::Fighter => -0.05,
+            CharacterClass::Bard => 0.0,
+        }
+    }
+
+    /// Added to the player's to-hit roll.
+    pub fn attack_bonus(&self) -> u32 {
+        match self {
+            CharacterClass::Fighter => 2,
+            _ => 0,
+        }
+    }
+
+    /// Added to each successful hit.
+    pub fn damage_bonus(&self) -> u32 {
+        match self {
+            CharacterClass::Fighter => 2,
+            _ => 0,
+        }
+    }
+
+    /// Added to the pounds a character can carry before being overloaded.
+    pub fn carry_bonus_lb(&self) -> f64 {
+        match self {
+            CharacterClass::Fighter => 30.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Fed into `HaggleContext::charisma`.
+    pub fn charisma(&self) -> i32 {
+        match self {
+            CharacterClass::Bard => 2,
+            CharacterClass::Rogue => 1,
+            CharacterClass::Fighter => 0,
+        }
+    }
+
+    /// Multiplier on tavern tip-luck and flirt chances.
+    pub fn tavern_factor(&self) -> f64 {
+        match self {
+            CharacterClass::Bard => 1.5,
+            _ => 1.0,
+        }
+    }
+
+    pub fn starting_kit(&self) -> &'static [&'static str] {
+        match self {
+            CharacterClass::Rogue => &["Dagger", "Thieves' Tools", "Dark Cloak"],
+            CharacterClass::Fighter => &["Longsword", "Wooden Shield", "Iron Helm"],
+            CharacterClass::Bard => &["Lute", "Rapier", "Fine Clothes"],
+        }
+    }
+}
+
+/// Where the character came from: flavour plus a few extra belongings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Background {
+    Urchin,
+    Soldier,
+    Noble,
+    Wanderer,
+}
+
+impl Background {
+    pub const ALL: [Background; 4] = [
+        Background::Urchin,
+        Background::Soldier,
+        Background::Noble,
+        Background::Wanderer,
+    ];
+
+    pub fn parse(s: &str) -> Option<Background> {
+        match s.trim().to_lowercase().as_str() {
+            "urchin" => Some(Background::Urchin),
+            "soldier" => Some(Background::Soldier),
+            "noble" => Some(Background::Noble),
+            "wanderer" => Some(Background::Wanderer),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Background::Urchin => "Urchin",
+            Background::Soldier => "Soldier",
+            Background::Noble => "Noble",
+            Background::Wanderer => "Wanderer",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Background::Urchin => "Grew up in the gutters with a few coppers and a lucky charm.",
+            Background::Soldier => "Mustered out with a bedroll, rations and a little pay.",
+            Background::Noble => "Left the family estate with a signet ring and a fat purse.",
+            Background::Wanderer => "Walked every road there is; travels light.",
+        }
+    }
+
+    /// Extra starting belongings; coin strings go through `Inventory::add_item`.
+    pub fn kit(&self) -> &'static [&'static str] {
+        match self {
+            Background::Urchin => &["25 cp", "Lucky Charm"],
+            Background::Soldier => &["5 gp", "Bedroll", "Rations"],
+            Background::Noble => &["25 gp", "Signet Ring"],
+            Background::Wanderer => &["2 gp", "Walking Staff", "Map"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Character {
+    pub name: String,
+    pub class: CharacterClass,
+    pub background: Background,
+}
+
+impl Character {
+    pub fn title(&self) -> String {
+        format!(
+            "{} the {} {}",
+            self.name,
+            self.background.label(),
+            self.class.label()
+        )
+    }
+}
+
+/// Tidy a player-entered name; empty input is refused.
+pub fn clean_name(name: &str) -> Result<String, &'static str> {
+    let name: String = name
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(NAME_MAX_LEN)
+        .collect();
+    if name.is_empty() {
+        Err("Every adventurer needs a name")
+    } else {
+        Ok(name)
+    }
+}
+
+/// Build a new character: class hit points, class kit and background kit.
+pub fn create(
+    name: &str,
+    class: CharacterClass,
+    background: Background,
+    difficulty: Difficulty,
+    hardcore: bool,
+) -> Result<Inventory, &'static str> {
+    let mut inv = Inventory::new();
+    inv.difficulty = difficulty;
+    inv.hardcore = hardcore;
+    take_over(&mut inv, name, class, background)?;
+    Ok(inv)
+}
+
+/// Put a new character into `inv`: HP for their class, the starting kit and their name.
+/// Everything else stays, so the fresh inventory `graveyard::bury` leaves behind keeps its
+/// stats and, at a hot-seat table, the shared world.
+pub fn take_over(
+    inv: &mut Inventory,
+    name: &str,
+    class: CharacterClass,
+    background: Background,
+) -> Result<(), &'static str> {
+    let name = clean_name(name)?;
+    inv.max_hp = class.max_hp();
+    inv.current_hp = inv.max_hp;
+    for item in class.starting_kit().iter().chain(background.kit()) {
+        inv.add_item(item);
+    }
+    inv.character = Some(Character {
+        name,
+        class,
+        background,
+    });
+    Ok(())
+}
+
+/// The player's class, if the save has one (older saves are classless).
+pub fn class_of(inv: &Inventory) -> Option<CharacterClass> {
+    inv.character.as_ref().map(|c| c.class)
+}
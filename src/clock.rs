@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+pub const MINUTES_PER_HOUR: u64 = 60;
+pub const MINUTES_PER_DAY: u64 = 24 * MINUTES_PER_HOUR;
+pub const START_HOUR: u64 = 8; // a fresh character wakes on day 1 at 08:00
+
+// How long actions take (minutes)
+pub const PICKPOCKET_MINUTES: u64 = 60;
+pub const FIGHT_ROUND_MINUTES: u64 = 5;
+pub const SHOP_VISIT_MINUTES: u64 = 30;
+pub const TAVERN_DRINK_MINUTES: u64 = 20;
+pub const TAVERN_FOOD_MINUTES: u64 = 30;
+pub const TAVERN_TIP_MINUTES: u64 = 5;
+pub const TAVERN_FLIRT_MINUTES: u64 = 15;
+pub const TAVERN_GAMBLE_MINUTES: u64 = 15;
+pub const TAVERN_HIRE_MINUTES: u64 = 20; // haggling over a hireling's terms
+pub const WAKE_HOUR: u64 = 8; // a night's stay ends at this hour
+pub const EXPLORE_MOVE_MINUTES: u64 = 10; // walking to the next dungeon room
+pub const DOOR_ATTEMPT_MINUTES: u64 = 5; // fiddling with a stuck lock
+pub const BANK_VISIT_MINUTES: u64 = 15;
+pub const CRAFT_MINUTES: u64 = 20; // unless a recipe says otherwise
+pub const APPRAISAL_MINUTES: u64 = 15; // a close look at a mystery item
+
+// Tavern opening hours: shut from CLOSE until OPEN
+pub const TAVERN_CLOSE_HOUR: u32 = 3;
+pub const TAVERN_OPEN_HOUR: u32 = 6;
+
+// Bank opening hours: open from OPEN until CLOSE
+pub const BANK_OPEN_HOUR: u32 = 8;
+pub const BANK_CLOSE_HOUR: u32 = 18;
+
+// Stored luck fades if unused for a day
+pub const LUCK_DURATION_MINUTES: u64 = MINUTES_PER_DAY;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOfDay {
+    Dawn,  // 05-08
+    Day,   // 08-17
+    Dusk,  // 17-20
+    Night, // 20-05
+}
+
+impl TimeOfDay {
+    pub fn from_hour(hour: u32) -> TimeOfDay {
+        match hour {
+            5..=7 => TimeOfDay::Dawn,
+            8..=16 => TimeOfDay::Day,
+            17..=19 => TimeOfDay::Dusk,
+            _ => TimeOfDay::Night,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeOfDay::Dawn => "Dawn",
+            TimeOfDay::Day => "Day",
+            TimeOfDay::Dusk => "Dusk",
+            TimeOfDay::Night => "Night",
+        }
+    }
+
+    /// Added to the base pickpocket success chance: darkness helps, daylight hurts.
+    pub fn pickpocket_modifier(&self) -> f64 {
+        match self {
+            TimeOfDay::Dawn => 0.0,
+            TimeOfDay::Day => -0.05,
+            TimeOfDay::Dusk => 0.05,
+            TimeOfDay::Night => 0.10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TavernCrowd {
+    Closed,
+    Quiet,
+    Lively,
+    Packed,
+}
+
+impl TavernCrowd {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TavernCrowd::Closed => "Closed",
+            TavernCrowd::Quiet => "Quiet",
+            TavernCrowd::Lively => "Lively",
+            TavernCrowd::Packed => "Packed",
+        }
+    }
+
+    /// Multiplier on tavern luck chances: a busy bartender has less time for you,
+    /// a packed room gives the barmaid cover.
+    pub fn tip_luck_factor(&self) -> f64 {
+        match self {
+            TavernCrowd::Quiet => 1.25,
+            TavernCrowd::Lively => 1.0,
+            TavernCrowd::Packed => 0.75,
+            TavernCrowd::Closed => 0.0,
+        }
+    }
+    pub fn flirt_factor(&self) -> f64 {
+        match self {
+            TavernCrowd::Quiet => 0.75,
+            TavernCrowd::Lively => 1.0,
+            TavernCrowd::Packed => 1.5,
+            TavernCrowd::Closed => 0.0,
+        }
+    }
+}
+
+/// In-game calendar, stored in minutes since midnight of day 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorldClock {
+    pub minutes: u64,
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self {
+            minutes: START_HOUR * MINUTES_PER_HOUR,
+        }
+    }
+}
+
+impl WorldClock {
+    pub fn day(&self) -> u64 {
+        self.minutes / MINUTES_PER_DAY + 1
+    }
+    pub fn hour(&self) -> u32 {
+        ((self.minutes % MINUTES_PER_DAY) / MINUTES_PER_HOUR) as u32
+    }
+    pub fn minute(&self) -> u32 {
+        (self.minutes % MINUTES_PER_HOUR) as u32
+    }
+    pub fn time_of_day(&self) -> TimeOfDay {
+        TimeOfDay::from_hour(self.hour())
+    }
+
+    pub fn tavern_crowd(&self) -> TavernCrowd {
+        let h = self.hour();
+        if (TAVERN_CLOSE_HOUR..TAVERN_OPEN_HOUR).contains(&h) {
+            return TavernCrowd::Closed;
+        }
+        match h {
+            6..=16 => TavernCrowd::Quiet,
+            17..=22 => TavernCrowd::Packed,
+            _ => TavernCrowd::Lively,
+        }
+    }
+
+    pub fn tavern_open(&self) -> bool {
+        self.tavern_crowd() != TavernCrowd::Closed
+    }
+
+    pub fn bank_open(&self) -> bool {
+        (BANK_OPEN_HOUR..BANK_CLOSE_HOUR).contains(&self.hour())
+    }
+
+    /// Advance the clock; returns the number of whole hours that ticked over.
+    pub fn advance(&mut self, minutes: u64) -> u32 {
+        let before = self.minutes / MINUTES_PER_HOUR;
+        self.minutes = self.minutes.saturating_add(minutes);
+        (self.minutes / MINUTES_PER_HOUR - before) as u32
+    }
+
+    /// Minutes until the clock next reads `hour`:00 (a full day if it already does).
+    pub fn minutes_until_hour(&self, hour: u64) -> u64 {
+        let now = self.minutes % MINUTES_PER_DAY;
+        let target = hour * MINUTES_PER_HOUR;
+        if target > now {
+            target - now
+        } else {
+            MINUTES_PER_DAY - now + target
+        }
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "Day {}, {:02}:{:02} ({})",
+            self.day(),
+            self.hour(),
+            self.minute(),
+            self.time_of_day().label()
+        )
+    }
+}
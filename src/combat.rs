@@ -0,0 +1,602 @@
+use crate::bestiary::{self, Ability, Monster, Temper};
+use crate::character;
+use crate::companion::{MORALE_DEFEAT, MORALE_DOWNED, MORALE_FLED, MORALE_VICTORY};
+use crate::difficulty::{self, XP_PER_STRENGTH};
+use crate::graveyard::{self, Grave};
+use crate::inventory::Inventory;
+use crate::quest::QuestEvent;
+use crate::rng::with_rng;
+use crate::stats::StatEvent;
+use rand::Rng;
+
+pub const PLAYER_DAMAGE_MIN: u32 = 2;
+pub const PLAYER_DAMAGE_MAX: u32 = 6;
+pub const DEFEAT_GOLD_LOSS: f64 = 0.10; // share of gold lost when knocked out
+pub const DEFEAT_ITEMS_LOST: usize = 3;
+pub const FLEE_GOLD_LOSS: f64 = 0.05;
+pub const FLEE_ITEMS_LOST: usize = 1;
+
+// To-hit model: d20 + attack bonus must reach the defender's armor class
+pub const PLAYER_BASE_AC: u32 = 10;
+pub const PLAYER_ATTACK_BONUS: u32 = 4;
+pub const DEFEND_AC_BONUS: u32 = 5;
+pub const CRIT_ROLL: u32 = 20;
+pub const FUMBLE_ROLL: u32 = 1;
+pub const CRIT_MULTIPLIER: u32 = 2;
+// Worn armor adds to the player's AC (each kind counts once)
+pub const ARMOR_PIECES: &[(&str, u32)] = &[
+    ("Wooden Shield", 1),
+    ("Iron Helm", 1),
+    ("Chain Shirt", 3),
+    ("Dwarven Plate", 5),
+];
+
+// Monster abilities and low-HP behaviour
+pub const LOW_HP_SHARE: f64 = 0.25; // at or below this share of max HP a monster flees or rages
+pub const ENRAGE_DAMAGE_BONUS: f64 = 0.5; // extra damage while enraged
+pub const PARALYZE_CHANCE: f64 = 0.25;
+pub const STEAL_CHANCE: f64 = 0.35;
+pub const FIRE_BREATH_EVERY: u32 = 3; // breathes on every Nth turn
+pub const FIRE_BREATH_MULTIPLIER: u32 = 2;
+// Carrying one of these gets full damage through a Stoneskin hide
+pub const HEAVY_WEAPONS: &[&str] = &["Longsword", "Battleaxe", "Warhammer", "Greatsword", "Maul"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct Combatant {
+    pub monster: Monster,
+    pub hp: u32,
+    pub turns: u32, // turns taken so far (drives fire breath)
+    pub enraged: bool,
+    pub fled: bool,
+}
+
+impl Combatant {
+    /// Still on the field and fighting.
+    pub fn alive(&self) -> bool {
+        self.hp > 0 && !self.fled
+    }
+    pub fn slain(&self) -> bool {
+        self.hp == 0
+    }
+    fn badly_hurt(&self) -> bool {
+        (self.hp as f64) <= self.monster.max_hp() as f64 * LOW_HP_SHARE
+    }
+}
+
+pub fn player_armor_class(inv: &Inventory) -> u32 {
+    PLAYER_BASE_AC
+        + ARMOR_PIECES
+            .iter()
+            .filter(|(name, _)| inv.items.iter().any(|it| it.eq_ignore_ascii_case(name)))
+            .map(|(_, ac)| ac)
+            .sum::<u32>()
+}
+
+/// Whether the player carries a weapon heavy enough to crack stone.
+pub fn has_heavy_weapon(inv: &Inventory) -> bool {
+    inv.items.iter().any(|it| {
+        HEAVY_WEAPONS
+            .iter()
+            .any(|w| it.to_lowercase().contains(&w.to_lowercase()))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FightStatus {
+    Ongoing,
+    Victory, // no foe left standing and at least one slain
+    Escaped, // every foe ran off before any fell: no spoils
+    Defeat,
+}
+
+/// One fight in progress: the player against one or more monsters.
+#[derive(Debug, Clone)]
+pub struct Encounter {
+    pub foes: Vec<Combatant>,
+    pub in_dungeon: bool,                // guarding the current dungeon room
+    pub player_paralyzed: u32,           // rounds the player must sit out
+    pub felled_by: Option<&'static str>, // the foe that dropped the player
+}
+
+impl Encounter {
+    pub fn new(monsters: Vec<Monster>) -> Encounter {
+        Encounter {
+            foes: monsters
+                .into_iter()
+                .map(|m| Combatant {
+                    monster: m,
+                    hp: m.max_hp(),
+                    turns: 0,
+                    enraged: false,
+                    fled: false,
+                })
+                .collect(),
+            in_dungeon: false,
+            player_paralyzed: 0,
+            felled_by: None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        let first = self.foes[0].monster.name;
+        if self.foes.len() == 1 {
+            format!("a {}", first)
+        } else if self.foes.iter().all(|f| f.monster.name == first) {
+            format!("{} {}s", self.foes.len(), first)
+        } else {
+            let names: Vec<&str> = self.foes.iter().map(|f| f.monster.name).collect();
+            names.join(", ")
+        }
+    }
+
+    /// Indices of foes still standing.
+    pub fn living(&self) -> Vec<usize> {
+        (0..self.foes.len())
+            .filter(|&i| self.foes[i].alive())
+            .collect()
+    }
+
+    /// No foe left standing (slain or run off).
+    pub fn is_over(&self) -> bool {
+        self.foes.iter().all(|f| !f.alive())
+    }
+
+    // Once no foe is standing: won only if something actually fell
+    fn ended(&self) -> FightStatus {
+        if self.foes.iter().any(|f| f.slain()) {
+            FightStatus::Victory
+        } else {
+            FightStatus::Escaped
+        }
+    }
+
+    /// One round: the player strikes `target` (first living foe if none or invalid),
+    /// then every living foe takes its turn.
+    pub fn round(
+        &mut self,
+        inv: &mut Inventory,
+        target: Option<usize>,
+    ) -> (Vec<String>, FightStatus) {
+        self.resolve(inv, Some(target))
+    }
+
+    /// One round spent on guard: no attack, but foes need to beat a higher armor class
+    /// and dragon fire only does half damage.
+    pub fn defend(&mut self, inv: &mut Inventory) -> (Vec<String>, FightStatus) {
+        self.resolve(inv, None)
+    }
+
+    // `strike` is None when the player defends
+    fn resolve(
+        &mut self,
+        inv: &mut Inventory,
+        strike: Option<Option<usize>>,
+    ) -> (Vec<String>, FightStatus) {
+        let mut lines = Vec::new();
+        let living = self.living();
+        let Some(&first) = living.first() else {
+            return (lines, self.ended());
+        };
+        let mut defending = false;
+        let mut focus = first;
+        if self.player_paralyzed > 0 {
+            self.player_paralyzed -= 1;
+            lines.push("You are paralyzed and cannot act!".to_string());
+        } else if let Some(target) = strike {
+            focus = target.filter(|t| living.contains(t)).unwrap_or(first);
+            self.player_attack(focus, inv, &mut lines);
+        } else {
+            defending = true;
+            lines.push(format!(
+                "You raise your guard (AC {}).",
+                player_armor_class(inv) + DEFEND_AC_BONUS
+            ));
+        }
+        if self.is_over() {
+            return (lines, self.ended());
+        }
+        self.companions_act(focus, inv, &mut lines);
+        if self.is_over() {
+            return (lines, self.ended());
+        }
+        for i in self.living() {
+            if self.monster_turn(i, inv, defending, &mut lines) {
+                return (lines, FightStatus::Defeat);
+            }
+        }
+        if self.is_over() {
+            return (lines, self.ended());
+        }
+        (lines, FightStatus::Ongoing)
+    }
+
+    fn player_attack(&mut self, t: usize, inv: &Inventory, lines: &mut Vec<String>) {
+        let heavy = has_heavy_weapon(inv);
+        let class = character::class_of(inv);
+        let bonus = PLAYER_ATTACK_BONUS + class.map_or(0, |c| c.attack_bonus());
+        let foe = &mut self.foes[t];
+        let name = foe.monster.name;
+        let ac = foe.monster.armor_class();
+        let roll = roll_d20();
+        let total = roll + bonus;
+        let summary = format!("You roll {} + {} = {} vs AC {}", roll, bonus, total, ac);
+        let outcome = attack_outcome(roll, total, ac);
+        if outcome == Swing::Miss {
+            let what = if roll == FUMBLE_ROLL {
+                "fumble! Your swing goes wide"
+            } else {
+                "miss"
+            };
+            lines.push(format!("{} — {} the {}.", summary, what, name));
+            return;
+        }
+        let mut dmg = with_rng(|r| r.gen_range(PLAYER_DAMAGE_MIN..=PLAYER_DAMAGE_MAX))
+            + class.map_or(0, |c| c.damage_bonus());
+        if outcome == Swing::Critical {
+            dmg *= CRIT_MULTIPLIER;
+            lines.push(format!("{} — critical hit!", summary));
+        } else {
+            lines.push(format!("{} — hit.", summary));
+        }
+        if foe.monster.ability == Ability::Stoneskin && !heavy {
+            dmg = dmg.div_ceil(2);
+            lines.push(format!("Your blow glances off the {}'s stone hide.", name));
+        }
+        foe.hp = foe.hp.saturating_sub(dmg);
+        lines.push(format!("You strike the {} for {} damage!", name, dmg));
+        if foe.slain() {
+            lines.push(format!("You slew the {}!", name));
+        }
+    }
+
+    // Each companion on their feet strikes the player's target (or the next foe standing);
+    // a healer tends the player instead when they're badly hurt.
+    fn companions_act(&mut self, focus: usize, inv: &mut Inventory, lines: &mut Vec<String>) {
+        for ci in 0..inv.party.companions.len() {
+            let c = &inv.party.companions[ci];
+            if !c.standing() {
+                continue;
+            }
+            let (kind, who) = (c.kind, c.title());
+            if let Some(heal) = kind.heal_range()
+                && (inv.current_hp as f64) <= inv.max_hp as f64 * LOW_HP_SHARE * 2.0
+            {
+                let amount = with_rng(|r| r.gen_range(heal)).min(inv.max_hp - inv.current_hp);
+                inv.current_hp += amount;
+                lines.push(format!("{} tends your wounds (+{} HP).", who, amount));
+                continue;
+            }
+            let living = self.living();
+            let Some(&t) = living.iter().find(|&&t| t == focus).or(living.first()) else {
+                return;
+            };
+            let foe = &mut self.foes[t];
+            let name = foe.monster.name;
+            let ac = foe.monster.armor_class();
+            let roll = roll_d20();
+            let outcome = attack_outcome(roll, roll + kind.attack_bonus(), ac);
+            if outcome == Swing::Miss {
+                lines.push(format!("{} swings at the {} and misses.", who, name));
+                continue;
+            }
+            let mut dmg = with_rng(|r| r.gen_range(kind.damage_range()));
+            if outcome == Swing::Critical {
+                dmg *= CRIT_MULTIPLIER;
+            }
+            if foe.monster.ability == Ability::Stoneskin {
+                dmg = dmg.div_ceil(2);
+            }
+            foe.hp = foe.hp.saturating_sub(dmg);
+            lines.push(format!("{} strikes the {} for {} damage!", who, name, dmg));
+            if foe.slain() {
+                lines.push(format!("{} slew the {}!", who, name));
+            }
+        }
+    }
+
+    // One monster's turn: low-HP behaviour, then its attack. Returns true if the player drops.
+    fn monster_turn(
+        &mut self,
+        i: usize,
+        inv: &mut Inventory,
+        defending: bool,
+        lines: &mut Vec<String>,
+    ) -> bool {
+        let foe = &mut self.foes[i];
+        let m = foe.monster;
+        if foe.badly_hurt() {
+            match m.temper {
+                Temper::Cowardly => {
+                    foe.fled = true;
+                    lines.push(format!("The wounded {} turns tail and flees!", m.name));
+                    return false;
+                }
+                Temper::Berserk if !foe.enraged => {
+                    foe.enraged = true;
+                    lines.push(format!("The {} flies into a bloody rage!", m.name));
+                }
+                _ => {}
+            }
+        }
+        foe.turns += 1;
+        let breath =
+            m.ability == Ability::FireBreath && foe.turns.is_multiple_of(FIRE_BREATH_EVERY);
+        let mut dmg = with_rng(|r| r.gen_range(m.damage_range()));
+        if let Some(ci) = companion_target(inv) {
+            let c = &mut inv.party.companions[ci];
+            let who = c.title();
+            if !breath {
+                let roll = roll_d20();
+                match attack_outcome(roll, roll + m.attack_bonus(), c.kind.armor_class()) {
+                    Swing::Miss => {
+                        lines.push(format!("The {} lunges at {} and misses.", m.name, who));
+                        return false;
+                    }
+                    Swing::Critical => dmg *= CRIT_MULTIPLIER,
+                    Swing::Hit => {}
+                }
+            } else {
+                dmg *= FIRE_BREATH_MULTIPLIER;
+            }
+            if foe.enraged {
+                dmg += (dmg as f64 * ENRAGE_DAMAGE_BONUS).round() as u32;
+            }
+            c.hp = c.hp.saturating_sub(dmg);
+            lines.push(format!("The {} hits {} for {} damage!", m.name, who, dmg));
+            if !c.standing() {
+                c.morale = c.morale.saturating_add_signed(MORALE_DOWNED);
+                lines.push(format!("{} goes down!", who));
+            }
+            return false;
+        }
+        if breath {
+            // Fire needs no attack roll; a raised guard only halves it
+            dmg *= FIRE_BREATH_MULTIPLIER;
+            if defending {
+                dmg = dmg.div_ceil(2);
+            }
+        } else {
+            let ac = player_armor_class(inv) + if defending { DEFEND_AC_BONUS } else { 0 };
+            let roll = roll_d20();
+            let total = roll + m.attack_bonus();
+            let summary = format!(
+                "The {} rolls {} + {} = {} vs your AC {}",
+                m.name,
+                roll,
+                m.attack_bonus(),
+                total,
+                ac
+            );
+            match attack_outcome(roll, total, ac) {
+                Swing::Miss => {
+                    let what = if roll == FUMBLE_ROLL {
+                        "fumble! It stumbles past you"
+                    } else {
+                        "miss"
+                    };
+                    lines.push(format!("{} — {}.", summary, what));
+                    return false;
+                }
+                Swing::Critical => {
+                    dmg *= CRIT_MULTIPLIER;
+                    lines.push(format!("{} — critical hit!", summary));
+                }
+                Swing::Hit => lines.push(format!("{} — hit.", summary)),
+            }
+        }
+        if foe.enraged {
+            dmg += (dmg as f64 * ENRAGE_DAMAGE_BONUS).round() as u32;
+        }
+        inv.current_hp = inv.current_hp.saturating_sub(dmg);
+        if breath {
+            lines.push(format!(
+                "🔥 The {} breathes fire over you for {} damage!",
+                m.name, dmg
+            ));
+        } else {
+            lines.push(format!("The {} hits you for {} damage!", m.name, dmg));
+        }
+        if inv.current_hp == 0 {
+            lines.push(format!(
+                "You fall unconscious! The {} defeats you...",
+                m.name
+            ));
+            self.felled_by = Some(m.name);
+            return true;
+        }
+        match m.ability {
+            Ability::Drain => {
+                let healed = dmg.min(m.max_hp() - foe.hp);
+                if healed > 0 {
+                    foe.hp += healed;
+                    lines.push(format!("The {} drains {} HP from you!", m.name, healed));
+                }
+            }
+            Ability::Paralyze
+                if self.player_paralyzed == 0 && with_rng(|r| r.gen_bool(PARALYZE_CHANCE)) =>
+            {
+                self.player_paralyzed = 1;
+                lines.push(format!("The {}'s touch leaves you paralyzed!", m.name));
+            }
+            Ability::Thief if !inv.items.is_empty() && with_rng(|r| r.gen_bool(STEAL_CHANCE)) => {
+                let idx = with_rng(|r| r.gen_range(0..inv.items.len()));
+                let item = inv.remove_item_at(idx);
+                foe.fled = true;
+                lines.push(format!(
+                    "The {} snatches your {} and scurries away!",
+                    m.name, item
+                ));
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Swing {
+    Miss,
+    Hit,
+    Critical,
+}
+
+// Foes pick on the player twice as often as on any one companion still standing.
+// Without companions no roll is made, so solo fights play out exactly as before.
+fn companion_target(inv: &Inventory) -> Option<usize> {
+    let standing: Vec<usize> = (0..inv.party.companions.len())
+        .filter(|&i| inv.party.companions[i].standing())
+        .collect();
+    if standing.is_empty() {
+        return None;
+    }
+    let pick = with_rng(|r| r.gen_range(0..standing.len() + 2));
+    standing.get(pick.checked_sub(2)?).copied()
+}
+
+fn roll_d20() -> u32 {
+    with_rng(|r| r.gen_range(1..=20))
+}
+
+// A natural 20 always lands (twice as hard), a natural 1 always misses
+fn attack_outcome(roll: u32, total: u32, ac: u32) -> Swing {
+    if roll == CRIT_ROLL {
+        Swing::Critical
+    } else if roll == FUMBLE_ROLL || total < ac {
+        Swing::Miss
+    } else {
+        Swing::Hit
+    }
+}
+
+/// Make sure hit points are initialised before a fight (older saves start at 0).
+pub fn prepare_player(inv: &mut Inventory) {
+    if inv.max_hp == 0 {
+        inv.max_hp = 20;
+    }
+    if inv.current_hp == 0 || inv.current_hp > inv.max_hp {
+        inv.current_hp = inv.max_hp;
+    }
+}
+
+pub fn reward_range_gp(m: &Monster) -> std::ops::RangeInclusive<u32> {
+    let min_gp = (10 * (m.strength as u32).max(1)).max(5);
+    let max_gp = (40 * m.strength as u32).min(400).max(min_gp + 5);
+    min_gp..=max_gp
+}
+
+/// What a won fight paid out.
+#[derive(Debug, Clone, Default)]
+pub struct Spoils {
+    pub gold: u32,          // bounty, scaled by difficulty
+    pub drops: Vec<String>, // items and coins from the monsters' drop tables
+    pub notes: Vec<String>, // quest progress, level ups
+}
+
+impl Spoils {
+    /// Drops that aren't coins (for "items gained" summaries).
+    pub fn items(&self) -> Vec<String> {
+        let cre = crate::loot::currency_regex();
+        self.drops
+            .iter()
+            .filter(|d| !cre.is_match(d))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Pay out gold (scaled by difficulty), drops and experience for every slain foe and
+/// tell the quest log. Foes that ran off pay nothing.
+pub fn settle_victory(inv: &mut Inventory, enc: &Encounter) -> Spoils {
+    let mut spoils = Spoils::default();
+    let mut xp = 0;
+    let factor = inv.difficulty.reward_factor();
+    let purse_before = inv.total_cp();
+    for f in enc.foes.iter().filter(|f| f.slain()) {
+        let base = with_rng(|r| r.gen_range(reward_range_gp(&f.monster)));
+        spoils.gold += ((base as f64) * factor).round() as u32;
+        spoils.drops.extend(bestiary::roll_drops(&f.monster));
+        xp += f.monster.strength as u32 * XP_PER_STRENGTH;
+        spoils
+            .notes
+            .extend(inv.quests.record(QuestEvent::MonsterSlain(f.monster.name)));
+        inv.stats.record(StatEvent::MonsterSlain(f.monster.name));
+    }
+    inv.gold_pieces = inv.gold_pieces.saturating_add(spoils.gold);
+    for d in &spoils.drops {
+        inv.add_item(d);
+    }
+    inv.stats.record(StatEvent::CoinEarned(
+        inv.total_cp().saturating_sub(purse_before),
+    ));
+    let level = inv.level();
+    inv.experience = inv.experience.saturating_add(xp);
+    if inv.level() > level {
+        spoils
+            .notes
+            .push(format!("⭐ You reached level {}!", inv.level()));
+    }
+    spoils.notes.extend(inv.stats.take_announcements());
+    inv.party.shift_morale(MORALE_VICTORY);
+    inv.save_after_pickup();
+    spoils
+}
+
+// Lose a share of gold and some random items (both scaled by difficulty)
+fn lose_valuables(inv: &mut Inventory, share: f64, items: usize) -> (u32, Vec<String>) {
+    let (share, items) = difficulty::scaled_penalty(inv, share, items);
+    let loss = ((inv.gold_pieces as f64) * share).round() as u32;
+    let loss = loss.min(inv.gold_pieces);
+    inv.gold_pieces -= loss;
+    let mut removed = Vec::new();
+    for _ in 0..items {
+        if inv.items.is_empty() {
+            break;
+        }
+        let idx = with_rng(|r| r.gen_range(0..inv.items.len()));
+        removed.push(inv.remove_item_at(idx));
+    }
+    (loss, removed)
+}
+
+/// Knocked out: lose gold and items; you and your companions wake with full hit points.
+/// Returns (gold lost, items lost).
+pub fn settle_defeat(inv: &mut Inventory) -> (u32, Vec<String>) {
+    let lost = lose_valuables(inv, DEFEAT_GOLD_LOSS, DEFEAT_ITEMS_LOST);
+    inv.stats.record(StatEvent::KnockedOut);
+    inv.stats
+        .record(StatEvent::CoinLost(lost.0.saturating_mul(100)));
+    inv.current_hp = inv.max_hp;
+    inv.party.rest();
+    inv.party.shift_morale(MORALE_DEFEAT);
+    inv.save_after_pickup();
+    lost
+}
+
+/// How a defeat was settled.
+#[derive(Debug, Clone)]
+pub enum Fall {
+    KnockedOut { loss: u32, removed: Vec<String> },
+    Perished(Grave), // hardcore: the run is over and a fresh character takes its place
+}
+
+/// Settle a lost fight: knocked out normally, buried in hardcore mode.
+pub fn settle_fall(inv: &mut Inventory, enc: &Encounter) -> Fall {
+    if inv.hardcore {
+        let killer = enc.felled_by.unwrap_or("a monster");
+        Fall::Perished(graveyard::bury(inv, killer))
+    } else {
+        let (loss, removed) = settle_defeat(inv);
+        Fall::KnockedOut { loss, removed }
+    }
+}
+
+/// Running away drops a little gold and an item. Returns (gold lost, items lost).
+pub fn settle_flee(inv: &mut Inventory) -> (u32, Vec<String>) {
+    let lost = lose_valuables(inv, FLEE_GOLD_LOSS, FLEE_ITEMS_LOST);
+    inv.stats.record(StatEvent::Fled);
+    inv.stats
+        .record(StatEvent::CoinLost(lost.0.saturating_mul(100)));
+    inv.party.shift_morale(MORALE_FLED);
+    inv.save_after_pickup();
+    lost
+}
@@ -0,0 +1,340 @@
+use crate::inventory::{Inventory, format_cp};
+use crate::money::{Money, MoneyError, PayMode};
+use crate::rng::with_rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+pub const PARTY_MAX: usize = 2;
+
+// Morale runs 0-100; at or below the floor a companion walks out
+pub const MORALE_START: u32 = 60;
+pub const MORALE_MAX: u32 = 100;
+pub const DESERT_MORALE: u32 = 20;
+pub const MAX_UNPAID_DAYS: u32 = 2; // leaves rather than go this long without pay
+pub const MORALE_PAID: i32 = 5;
+pub const MORALE_UNPAID: i32 = -25;
+pub const MORALE_VICTORY: i32 = 10;
+pub const MORALE_DEFEAT: i32 = -20;
+pub const MORALE_FLED: i32 = -10;
+pub const MORALE_DOWNED: i32 = -15; // knocked out in a fight
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompanionKind {
+    Sellsword,       // hits hard, soaks blows
+    ApprenticeThief, // keeps watch while you work a crowd
+    Healer,          // patches you up mid-fight
+}
+
+impl CompanionKind {
+    pub const ALL: [CompanionKind; 3] = [
+        CompanionKind::Sellsword,
+        CompanionKind::ApprenticeThief,
+        CompanionKind::Healer,
+    ];
+
+    pub fn parse(s: &str) -> Option<CompanionKind> {
+        match s
+            .trim()
+            .to_lowercase()
+            .replace([' ', '_', '-'], "")
+            .as_str()
+        {
+            "sellsword" | "s" => Some(CompanionKind::Sellsword),
+            "apprenticethief" | "thief" | "t" => Some(CompanionKind::ApprenticeThief),
+            "healer" | "h" => Some(CompanionKind::Healer),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompanionKind::Sellsword => "Sellsword",
+            CompanionKind::ApprenticeThief => "Apprentice Thief",
+            CompanionKind::Healer => "Healer",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            CompanionKind::Sellsword => "A blade for hire: fights beside you and draws blows.",
+            CompanionKind::ApprenticeThief => {
+                "Keeps lookout while you pick pockets; handy with a knife."
+            }
+            CompanionKind::Healer => "Tends your wounds between blows in a fight.",
+        }
+    }
+
+    /// Wage in copper, paid each day from carried coin (the first day up front).
+    pub fn daily_wage_cp(&self) -> u32 {
+        match self {
+            CompanionKind::Sellsword => 200,
+            CompanionKind::ApprenticeThief => 120,
+            CompanionKind::Healer => 150,
+        }
+    }
+
+    pub fn max_hp(&self) -> u32 {
+        match self {
+            CompanionKind::Sellsword => 16,
+            CompanionKind::ApprenticeThief => 10,
+            CompanionKind::Healer => 8,
+        }
+    }
+
+    pub fn armor_class(&self) -> u32 {
+        match self {
+            CompanionKind::Sellsword => 14,
+            CompanionKind::ApprenticeThief => 12,
+            CompanionKind::Healer => 11,
+        }
+    }
+
+    pub fn attack_bonus(&self) -> u32 {
+        match self {
+            CompanionKind::Sellsword => 4,
+            CompanionKind::ApprenticeThief => 3,
+            CompanionKind::Healer => 1,
+        }
+    }
+
+    pub fn damage_range(&self) -> RangeInclusive<u32> {
+        match self {
+            CompanionKind::Sellsword => 2..=7,
+            CompanionKind::ApprenticeThief => 1..=4,
+            CompanionKind::Healer => 1..=3,
+        }
+    }
+
+    /// Added to the pickpocket success chance while this companion keeps watch.
+    pub fn lookout_bonus(&self) -> f64 {
+        match self {
+            CompanionKind::ApprenticeThief => 0.10,
+            CompanionKind::Sellsword | CompanionKind::Healer => 0.03,
+        }
+    }
+
+    /// Hit points restored in place of an attack when the player is badly hurt.
+    pub fn heal_range(&self) -> Option<RangeInclusive<u32>> {
+        match self {
+            CompanionKind::Healer => Some(2..=5),
+            _ => None,
+        }
+    }
+
+    fn names(&self) -> &'static [&'static str] {
+        match self {
+            CompanionKind::Sellsword => &["Brakka", "Osric", "Helga", "Torvald"],
+            CompanionKind::ApprenticeThief => &["Pip", "Wren", "Nim", "Sly Tam"],
+            CompanionKind::Healer => &["Sister Maud", "Elowen", "Brother Cole", "Ysolde"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Companion {
+    pub kind: CompanionKind,
+    pub name: String,
+    pub hp: u32,
+    pub morale: u32,
+    #[serde(default)]
+    pub unpaid_days: u32,
+    #[serde(default)]
+    pub owed_cp: u32, // back wages
+}
+
+impl Companion {
+    pub fn new(kind: CompanionKind, name: &str) -> Companion {
+        Companion {
+            kind,
+            name: name.to_string(),
+            hp: kind.max_hp(),
+            morale: MORALE_START,
+            unpaid_days: 0,
+            owed_cp: 0,
+        }
+    }
+
+    /// "Brakka the Sellsword"
+    pub fn title(&self) -> String {
+        format!("{} the {}", self.name, self.kind.label())
+    }
+
+    /// Conscious and able to fight or keep watch.
+    pub fn standing(&self) -> bool {
+        self.hp > 0
+    }
+
+    pub fn status_label(&self) -> String {
+        let mut s = format!(
+            "HP {}/{}, morale {}, wage {}/day",
+            self.hp,
+            self.kind.max_hp(),
+            self.morale,
+            format_cp(self.kind.daily_wage_cp())
+        );
+        if self.owed_cp > 0 {
+            s.push_str(&format!(", owed {}", format_cp(self.owed_cp)));
+        }
+        s
+    }
+
+    fn shift_morale(&mut self, delta: i32) {
+        self.morale = self.morale.saturating_add_signed(delta).min(MORALE_MAX);
+    }
+
+    fn deserts(&self) -> bool {
+        self.morale <= DESERT_MORALE || self.unpaid_days >= MAX_UNPAID_DAYS
+    }
+}
+
+/// Saved hirelings travelling with the player.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Party {
+    pub companions: Vec<Companion>,
+    hours_pending: u32, // hours since the last payday
+}
+
+impl Party {
+    pub fn is_empty(&self) -> bool {
+        self.companions.is_empty()
+    }
+
+    /// Raise or lower everyone's morale (fights won, lost or run from).
+    pub fn shift_morale(&mut self, delta: i32) {
+        for c in &mut self.companions {
+            c.shift_morale(delta);
+        }
+    }
+
+    /// A night's rest puts everyone back on their feet.
+    pub fn rest(&mut self) {
+        for c in &mut self.companions {
+            c.hp = c.kind.max_hp();
+        }
+    }
+
+    pub fn daily_wages_cp(&self) -> u32 {
+        self.companions.iter().map(|c| c.kind.daily_wage_cp()).sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HireError {
+    PartyFull,
+    InsufficientFunds,
+    NoExactChange,
+    NoSuchCompanion,
+}
+
+impl HireError {
+    pub fn message(&self) -> String {
+        match self {
+            HireError::PartyFull => format!("You can lead at most {} companions", PARTY_MAX),
+            HireError::InsufficientFunds => "You can't cover the first day's wage".into(),
+            HireError::NoExactChange => {
+                "You don't have the exact coins for the first day's wage".into()
+            }
+            HireError::NoSuchCompanion => "There's no such companion".into(),
+        }
+    }
+}
+
+/// Hire a companion, paying the first day's wage up front.
+pub fn hire(inv: &mut Inventory, kind: CompanionKind) -> Result<String, HireError> {
+    if inv.party.companions.len() >= PARTY_MAX {
+        return Err(HireError::PartyFull);
+    }
+    let wage = Money::from(kind.daily_wage_cp());
+    inv.pay(wage, inv.pay_mode).map_err(|e| match e {
+        MoneyError::NoExactChange => HireError::NoExactChange,
+        MoneyError::InsufficientFunds => HireError::InsufficientFunds,
+    })?;
+    let taken: Vec<&str> = inv
+        .party
+        .companions
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+    let free: Vec<&str> = kind
+        .names()
+        .iter()
+        .copied()
+        .filter(|n| !taken.contains(n))
+        .collect();
+    let name = with_rng(|r| free.choose(r).copied()).unwrap_or(kind.names()[0]);
+    let companion = Companion::new(kind, name);
+    let title = companion.title();
+    inv.party.companions.push(companion);
+    inv.save_after_pickup();
+    Ok(title)
+}
+
+/// Let a companion go. Back wages are settled if the purse allows.
+pub fn dismiss(inv: &mut Inventory, idx: usize) -> Result<Companion, HireError> {
+    if idx >= inv.party.companions.len() {
+        return Err(HireError::NoSuchCompanion);
+    }
+    let c = inv.party.companions.remove(idx);
+    let _ = inv.pay(Money::from(c.owed_cp), PayMode::MakeChange);
+    inv.save_after_pickup();
+    Ok(c)
+}
+
+/// Best lookout among the companions still on their feet.
+pub fn lookout_bonus(inv: &Inventory) -> f64 {
+    inv.party
+        .companions
+        .iter()
+        .filter(|c| c.standing())
+        .map(|c| c.kind.lookout_bonus())
+        .fold(0.0, f64::max)
+}
+
+/// Let time pass: each full day every companion wants paying from carried coin.
+/// Anyone unpaid too long or too unhappy walks out. Returns notes worth telling the player.
+pub fn pass_time(inv: &mut Inventory, hours: u32) -> Vec<String> {
+    let mut notes = Vec::new();
+    if inv.party.is_empty() {
+        return notes;
+    }
+    inv.party.hours_pending = inv.party.hours_pending.saturating_add(hours);
+    let days = inv.party.hours_pending / 24;
+    inv.party.hours_pending %= 24;
+    for _ in 0..days {
+        for i in 0..inv.party.companions.len() {
+            let c = &mut inv.party.companions[i];
+            c.owed_cp = c.owed_cp.saturating_add(c.kind.daily_wage_cp());
+            let owed = Money::from(c.owed_cp);
+            let paid = inv.pay(owed, PayMode::MakeChange).is_ok();
+            let c = &mut inv.party.companions[i];
+            if paid {
+                c.owed_cp = 0;
+                c.unpaid_days = 0;
+                c.shift_morale(MORALE_PAID);
+            } else {
+                c.unpaid_days += 1;
+                c.shift_morale(MORALE_UNPAID);
+                notes.push(format!(
+                    "💸 You couldn't pay {}; they're owed {}.",
+                    c.title(),
+                    format_cp(c.owed_cp)
+                ));
+            }
+        }
+    }
+    inv.party.companions.retain(|c| {
+        if !c.deserts() {
+            return true;
+        }
+        let why = if c.unpaid_days >= MAX_UNPAID_DAYS {
+            "tired of working for nothing"
+        } else {
+            "having lost faith in you"
+        };
+        notes.push(format!("🚶 {} has deserted, {}.", c.title(), why));
+        false
+    });
+    notes
+}
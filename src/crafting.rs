@@ -0,0 +1,249 @@
+use crate::clock::CRAFT_MINUTES;
+use crate::currency;
+use crate::inventory::Inventory;
+use crate::stats::StatEvent;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+// Embedded like the shop catalogs so the wasm build needs no filesystem access.
+const RECIPES: &str = include_str!("../data/recipes.json");
+
+static BOOK: OnceLock<Vec<Recipe>> = OnceLock::new();
+
+/// One slot in a recipe: a single item name, or any one of several.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Ingredient {
+    One(String),
+    AnyOf(Vec<String>),
+}
+
+impl Ingredient {
+    fn names(&self) -> &[String] {
+        match self {
+            Ingredient::One(name) => std::slice::from_ref(name),
+            Ingredient::AnyOf(names) => names,
+        }
+    }
+
+    /// Whether a carried item fits this slot (a trailing price like "(2 cp)" is ignored).
+    pub fn matches(&self, item: &str) -> bool {
+        let base = base_name(item);
+        self.names().iter().any(|n| n.eq_ignore_ascii_case(base))
+    }
+
+    /// "Herb Bundle or Mushrooms"
+    pub fn label(&self) -> String {
+        self.names().join(" or ")
+    }
+}
+
+fn default_minutes() -> u64 {
+    CRAFT_MINUTES
+}
+
+/// A way to make something, loaded from `data/recipes.json`. Ingredients are used up;
+/// tools only need to be carried.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Recipe {
+    pub id: String,
+    pub makes: String,
+    pub ingredients: Vec<Ingredient>,
+    #[serde(default)]
+    pub tools: Vec<Ingredient>,
+    #[serde(default = "default_minutes")]
+    pub minutes: u64,
+    pub hint: String, // shown for recipes not yet discovered
+}
+
+impl Recipe {
+    /// "Herb Bundle or Mushrooms + Empty vial (with Whetstone)"
+    pub fn formula(&self) -> String {
+        let parts: Vec<String> = self.ingredients.iter().map(|i| i.label()).collect();
+        let mut s = parts.join(" + ");
+        if !self.tools.is_empty() {
+            let tools: Vec<String> = self.tools.iter().map(|t| t.label()).collect();
+            s.push_str(&format!(" (with {})", tools.join(", ")));
+        }
+        s
+    }
+}
+
+#[derive(Deserialize)]
+struct RecipeFile {
+    recipes: Vec<Recipe>,
+}
+
+/// Every recipe in the game, in file order.
+pub fn recipes() -> &'static [Recipe] {
+    BOOK.get_or_init(|| {
+        serde_json::from_str::<RecipeFile>(RECIPES)
+            .expect("embedded recipe file is valid")
+            .recipes
+    })
+}
+
+pub fn recipe(id: &str) -> Option<&'static Recipe> {
+    recipes().iter().find(|r| r.id == id)
+}
+
+// "Whetstone (2 cp)" -> "Whetstone"; other parentheses ("Mushrooms (poisonous)") stay
+fn base_name(item: &str) -> &str {
+    let Some(open) = item.rfind(" (") else {
+        return item;
+    };
+    let inner = item[open + 2..].strip_suffix(')').unwrap_or("");
+    let priced = inner.split_once(' ').is_some_and(|(n, code)| {
+        n.chars().all(|c| c.is_ascii_digit()) && currency::active().get(code).is_some()
+    });
+    if priced { &item[..open] } else { item }
+}
+
+/// Recipes the player has worked out, saved with the inventory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecipeBook {
+    pub discovered: Vec<String>, // recipe ids, in discovery order
+}
+
+impl RecipeBook {
+    pub fn knows(&self, id: &str) -> bool {
+        self.discovered.iter().any(|d| d == id)
+    }
+
+    /// Note a recipe as discovered; true the first time.
+    pub fn learn(&mut self, id: &str) -> bool {
+        if self.knows(id) {
+            return false;
+        }
+        self.discovered.push(id.to_string());
+        true
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CraftError {
+    UnknownRecipe,              // not in the book yet (or no such id)
+    MissingIngredients(String), // what's lacking, for the player
+    NoSuchItem,                 // a selected index past the end of the inventory
+    NothingHappens,             // the selected items don't combine into anything
+}
+
+impl CraftError {
+    pub fn message(&self) -> String {
+        match self {
+            CraftError::UnknownRecipe => "You don't know how to make that yet".into(),
+            CraftError::MissingIngredients(what) => format!("You still need {}", what),
+            CraftError::NoSuchItem => "There's no such item".into(),
+            CraftError::NothingHappens => {
+                "You fiddle with them for a while. Nothing comes of it".into()
+            }
+        }
+    }
+}
+
+/// What came out of the workshop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crafted {
+    pub recipe: String, // id
+    pub made: String,
+    pub used: Vec<String>,
+    pub discovered: bool, // first time making it
+    pub minutes: u64,
+}
+
+impl Crafted {
+    pub fn summary(&self) -> String {
+        let mut s = format!("You combine {} into {}", self.used.join(", "), self.made);
+        if self.discovered {
+            s.push_str(" — a new recipe for your book!");
+        }
+        s
+    }
+}
+
+// Pick an item index for every ingredient (each used once) and check the tools are carried
+// outside them. `pool` limits which items may be used up.
+fn assign(inv: &Inventory, recipe: &Recipe, pool: &[usize]) -> Result<Vec<usize>, String> {
+    let mut used: Vec<usize> = Vec::new();
+    let mut missing: Vec<String> = Vec::new();
+    for ing in &recipe.ingredients {
+        match pool
+            .iter()
+            .copied()
+            .find(|i| !used.contains(i) && ing.matches(&inv.items[*i]))
+        {
+            Some(i) => used.push(i),
+            None => missing.push(ing.label()),
+        }
+    }
+    for tool in &recipe.tools {
+        let carried =
+            (0..inv.items.len()).any(|i| !used.contains(&i) && tool.matches(&inv.items[i]));
+        if !carried {
+            missing.push(tool.label());
+        }
+    }
+    if missing.is_empty() {
+        Ok(used)
+    } else {
+        Err(missing.join(", "))
+    }
+}
+
+/// Known recipes whose ingredients and tools are all carried right now.
+pub fn craftable(inv: &Inventory) -> Vec<&'static Recipe> {
+    let all: Vec<usize> = (0..inv.items.len()).collect();
+    recipes()
+        .iter()
+        .filter(|r| inv.recipes.knows(&r.id) && assign(inv, r, &all).is_ok())
+        .collect()
+}
+
+// Use up the assigned items and hand over the result; stolen goods make stolen goods
+fn make(inv: &mut Inventory, recipe: &Recipe, mut used: Vec<usize>) -> Crafted {
+    used.sort_unstable_by(|a, b| b.cmp(a));
+    let stolen = used.iter().any(|&i| inv.is_stolen_at(i));
+    let mut names: Vec<String> = used.into_iter().map(|i| inv.remove_item_at(i)).collect();
+    names.reverse();
+    inv.push_item(recipe.makes.clone(), stolen);
+    let discovered = inv.recipes.learn(&recipe.id);
+    inv.stats.record(StatEvent::ItemCrafted);
+    inv.save_after_pickup();
+    Crafted {
+        recipe: recipe.id.clone(),
+        made: recipe.makes.clone(),
+        used: names,
+        discovered,
+        minutes: recipe.minutes,
+    }
+}
+
+/// Make a recipe already in the book from whatever matching items are carried.
+pub fn craft(inv: &mut Inventory, id: &str) -> Result<Crafted, CraftError> {
+    let recipe = recipe(id)
+        .filter(|_| inv.recipes.knows(id))
+        .ok_or(CraftError::UnknownRecipe)?;
+    let all: Vec<usize> = (0..inv.items.len()).collect();
+    let used = assign(inv, recipe, &all).map_err(CraftError::MissingIngredients)?;
+    Ok(make(inv, recipe, used))
+}
+
+/// Experiment: try the selected items together. If they are exactly the ingredients of
+/// some recipe (tools may be anywhere in the pack) it is made and learned.
+pub fn combine(inv: &mut Inventory, selected: &[usize]) -> Result<Crafted, CraftError> {
+    if selected.iter().any(|&i| i >= inv.items.len()) {
+        return Err(CraftError::NoSuchItem);
+    }
+    let mut pool = selected.to_vec();
+    pool.sort_unstable();
+    pool.dedup();
+    let found = recipes().iter().find_map(|r| {
+        if r.ingredients.len() != pool.len() {
+            return None;
+        }
+        assign(inv, r, &pool).ok().map(|used| (r, used))
+    });
+    let (recipe, used) = found.ok_or(CraftError::NothingHappens)?;
+    Ok(make(inv, recipe, used))
+}
@@ -0,0 +1,282 @@
+use crate::inventory::Inventory;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+pub const CURRENCY_FILE: &str = "currency.json"; // optional custom coinage for the CLI
+
+// The money changer keeps a cut of everything that crosses the counter
+pub const EXCHANGE_FEE: f64 = 0.05;
+pub const EXCHANGE_MIN_FEE_CP: u32 = 1;
+
+// Prices, purses and `Inventory`'s coin fields are kept in these three units
+const BASE_COINS: [(&str, u32); 3] = [("cp", 1), ("sp", 10), ("gp", 100)];
+
+static ACTIVE: OnceLock<CurrencyTable> = OnceLock::new();
+static RE_COIN_PHRASE: OnceLock<Regex> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurrencyError {
+    Invalid(String),     // a denomination table that can't be used
+    AlreadyInstalled,    // the active table is fixed once coins have been handled
+    UnknownCoin(String), // a code the active table doesn't define
+    SameCoin,            // exchanging a coin for itself
+    ZeroAmount,
+    InsufficientCoins, // not carrying that many of the coin
+    TooSmall,          // not even one coin back after the fee
+}
+
+impl CurrencyError {
+    pub fn message(&self) -> String {
+        match self {
+            CurrencyError::Invalid(why) => format!("Unusable coinage: {}", why),
+            CurrencyError::AlreadyInstalled => "The coinage is already set".into(),
+            CurrencyError::UnknownCoin(code) => format!("No one here takes '{}'", code),
+            CurrencyError::SameCoin => {
+                "The changer looks at you oddly: those are the same coin".into()
+            }
+            CurrencyError::ZeroAmount => "Name an amount first".into(),
+            CurrencyError::InsufficientCoins => "You aren't carrying that many".into(),
+            CurrencyError::TooSmall => {
+                "That isn't enough to buy a single coin after the fee".into()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Denomination {
+    pub code: String, // written after amounts in loot and saves: "5 pp"
+    pub singular: String,
+    pub plural: String,
+    pub value_cp: u32,
+}
+
+impl Denomination {
+    fn new(code: &str, singular: &str, plural: &str, value_cp: u32) -> Self {
+        Self {
+            code: code.into(),
+            singular: singular.into(),
+            plural: plural.into(),
+            value_cp,
+        }
+    }
+
+    /// "1 gold piece" / "3 gold pieces"
+    pub fn describe(&self, amount: u32) -> String {
+        let name = if amount == 1 {
+            &self.singular
+        } else {
+            &self.plural
+        };
+        format!("{} {}", amount, name)
+    }
+
+    // First word of the name: "platinum" for "platinum piece"
+    fn metal(&self) -> &str {
+        self.singular.split_whitespace().next().unwrap_or("")
+    }
+}
+
+/// The coins a setting uses, most valuable first. Custom tables must keep cp, sp and gp
+/// at 1, 10 and 100 (the units prices are kept in) and may add any others.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurrencyTable {
+    pub name: String,
+    pub denominations: Vec<Denomination>,
+}
+
+impl CurrencyTable {
+    /// Platinum, gold, electrum, silver and copper.
+    pub fn standard() -> CurrencyTable {
+        CurrencyTable {
+            name: "Standard".into(),
+            denominations: vec![
+                Denomination::new("pp", "platinum piece", "platinum pieces", 1_000),
+                Denomination::new("gp", "gold piece", "gold pieces", 100),
+                Denomination::new("ep", "electrum piece", "electrum pieces", 50),
+                Denomination::new("sp", "silver piece", "silver pieces", 10),
+                Denomination::new("cp", "copper piece", "copper pieces", 1),
+            ],
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<CurrencyTable, CurrencyError> {
+        let mut table: CurrencyTable =
+            serde_json::from_str(json).map_err(|e| CurrencyError::Invalid(e.to_string()))?;
+        table.validate()?;
+        table
+            .denominations
+            .sort_by_key(|d| std::cmp::Reverse(d.value_cp));
+        Ok(table)
+    }
+
+    pub fn validate(&self) -> Result<(), CurrencyError> {
+        for d in &self.denominations {
+            if d.code.is_empty() || !d.code.chars().all(|c| c.is_ascii_lowercase()) {
+                return Err(CurrencyError::Invalid(format!(
+                    "code '{}' must be lowercase letters",
+                    d.code
+                )));
+            }
+            if d.value_cp == 0 {
+                return Err(CurrencyError::Invalid(format!(
+                    "{} is worth nothing",
+                    d.code
+                )));
+            }
+            if self
+                .denominations
+                .iter()
+                .filter(|o| o.code == d.code)
+                .count()
+                > 1
+            {
+                return Err(CurrencyError::Invalid(format!(
+                    "{} is listed twice",
+                    d.code
+                )));
+            }
+        }
+        for (code, value) in BASE_COINS {
+            if self.get(code).map(|d| d.value_cp) != Some(value) {
+                return Err(CurrencyError::Invalid(format!(
+                    "{} must be present and worth {} cp",
+                    code, value
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, code: &str) -> Option<&Denomination> {
+        self.denominations.iter().find(|d| d.code == code)
+    }
+
+    /// Denominations carried outside the cp/sp/gp purse fields.
+    pub fn extra(&self) -> impl Iterator<Item = &Denomination> {
+        self.denominations
+            .iter()
+            .filter(|d| !BASE_COINS.iter().any(|(c, _)| *c == d.code))
+    }
+
+    // "pp|gp|ep|sp|cp", for the loot regexes
+    pub(crate) fn code_pattern(&self) -> String {
+        let codes: Vec<&str> = self.denominations.iter().map(|d| d.code.as_str()).collect();
+        codes.join("|")
+    }
+}
+
+/// The coinage in use; the standard table unless `install` ran first.
+pub fn active() -> &'static CurrencyTable {
+    ACTIVE.get_or_init(CurrencyTable::standard)
+}
+
+/// Swap in a custom table. Must happen before any coin is parsed or formatted.
+pub fn install(table: CurrencyTable) -> Result<(), CurrencyError> {
+    table.validate()?;
+    ACTIVE
+        .set(table)
+        .map_err(|_| CurrencyError::AlreadyInstalled)
+}
+
+/// Read coin words such as "a platinum coin" or "3 electrum pieces" as "1 pp" / "3 ep".
+pub fn parse_coin_phrase(s: &str) -> Option<String> {
+    let re = RE_COIN_PHRASE.get_or_init(|| {
+        Regex::new(r"(?i)^(?:(\d+|a|an|one)\s+)?([a-z]+)\s+(?:coin|coins|piece|pieces)$").unwrap()
+    });
+    let caps = re.captures(s.trim())?;
+    let amount = match caps.get(1).map(|m| m.as_str().to_lowercase()) {
+        None => 1,
+        Some(n) if matches!(n.as_str(), "a" | "an" | "one") => 1,
+        Some(n) => n.parse().ok()?,
+    };
+    let metal = caps[2].to_lowercase();
+    let d = active()
+        .denominations
+        .iter()
+        .find(|d| d.metal().eq_ignore_ascii_case(&metal))?;
+    Some(format!("{} {}", amount, d.code))
+}
+
+/// What a trip to the money changer came to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exchange {
+    pub from: String,
+    pub amount: u32,
+    pub to: String,
+    pub received: u32,
+    pub fee_cp: u32,
+    pub change_cp: u32, // leftover value paid back in cp/sp/gp
+}
+
+impl Exchange {
+    pub fn summary(&self) -> String {
+        let table = active();
+        let describe = |code: &str, n: u32| {
+            table
+                .get(code)
+                .map_or_else(|| format!("{} {}", n, code), |d| d.describe(n))
+        };
+        let mut msg = format!(
+            "The changer takes {} and counts out {} (fee {})",
+            describe(&self.from, self.amount),
+            describe(&self.to, self.received),
+            crate::inventory::format_cp(self.fee_cp)
+        );
+        if self.change_cp > 0 {
+            msg.push_str(&format!(
+                ", plus {} in change",
+                crate::inventory::format_cp(self.change_cp)
+            ));
+        }
+        msg
+    }
+}
+
+/// Trade `amount` carried coins of one denomination for another, less the changer's fee.
+pub fn exchange(
+    inv: &mut Inventory,
+    from: &str,
+    amount: u32,
+    to: &str,
+) -> Result<Exchange, CurrencyError> {
+    let table = active();
+    let from_d = table
+        .get(from)
+        .ok_or_else(|| CurrencyError::UnknownCoin(from.to_string()))?;
+    let to_d = table
+        .get(to)
+        .ok_or_else(|| CurrencyError::UnknownCoin(to.to_string()))?;
+    if from == to {
+        return Err(CurrencyError::SameCoin);
+    }
+    if amount == 0 {
+        return Err(CurrencyError::ZeroAmount);
+    }
+    if inv.coin_count(from) < amount {
+        return Err(CurrencyError::InsufficientCoins);
+    }
+    let value = amount.saturating_mul(from_d.value_cp);
+    let fee_cp = (((value as f64) * EXCHANGE_FEE).round() as u32)
+        .max(EXCHANGE_MIN_FEE_CP)
+        .min(value);
+    let net = value - fee_cp;
+    let received = net / to_d.value_cp;
+    if received == 0 {
+        return Err(CurrencyError::TooSmall);
+    }
+    let change_cp = net % to_d.value_cp;
+    inv.take_coins(from, amount);
+    inv.add_coins(to, received);
+    inv.add_copper(change_cp);
+    inv.save_after_pickup();
+    Ok(Exchange {
+        from: from.to_string(),
+        amount,
+        to: to.to_string(),
+        received,
+        fee_cp,
+        change_cp,
+    })
+}
@@ -0,0 +1,129 @@
+use crate::bestiary::{self, Monster};
+use crate::clock::TimeOfDay;
+use crate::combat;
+use crate::inventory::Inventory;
+use serde::{Deserialize, Serialize};
+
+// Experience and levels
+pub const XP_PER_STRENGTH: u32 = 10; // awarded per slain foe, times its strength
+pub const XP_PER_LEVEL: u32 = 100;
+pub const MAX_LEVEL: u32 = 10;
+
+// Encounter scaling
+pub const OVERREACH: u32 = 2; // monsters this far above the threat cap still turn up, rarely
+pub const OVERREACH_WEIGHT: f64 = 0.25;
+
+/// Chosen when a character is created; fixed for the life of the save.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn parse(s: &str) -> Option<Difficulty> {
+        match s.trim().to_lowercase().as_str() {
+            "easy" | "e" => Some(Difficulty::Easy),
+            "normal" | "n" => Some(Difficulty::Normal),
+            "hard" | "h" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// Added to the player's power when deciding how strong a foe may be.
+    pub fn threat_margin(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 0,
+            Difficulty::Normal => 1,
+            Difficulty::Hard => 3,
+        }
+    }
+
+    /// Multiplier on fight gold.
+    pub fn reward_factor(&self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+
+    /// Multiplier on gold and items lost when defeated or fleeing.
+    pub fn penalty_factor(&self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+}
+
+pub fn level_for(experience: u32) -> u32 {
+    (1 + experience / XP_PER_LEVEL).min(MAX_LEVEL)
+}
+
+/// Gear bonus to the threat cap: a heavy weapon and every two points of armor.
+fn gear_bonus(inv: &Inventory) -> u32 {
+    let armor = combat::player_armor_class(inv) - combat::PLAYER_BASE_AC;
+    u32::from(combat::has_heavy_weapon(inv)) + armor / 2
+}
+
+/// Strongest monster strength the player should normally meet: level and gear,
+/// scaled down when wounded, plus the difficulty margin.
+pub fn threat_cap(inv: &Inventory) -> u32 {
+    let power = (2 * inv.level() + gear_bonus(inv)) as f64;
+    let health = if inv.max_hp == 0 {
+        1.0
+    } else {
+        (inv.current_hp as f64 / inv.max_hp as f64).clamp(0.5, 1.0)
+    };
+    ((power * health).round() as u32).max(1) + inv.difficulty.threat_margin()
+}
+
+/// Relative chance of meeting `m` under `cap`: full weight up to the cap,
+/// a small chance slightly above it, none beyond.
+pub fn encounter_weight(m: &Monster, cap: u32) -> f64 {
+    let s = m.strength as u32;
+    if s <= cap {
+        1.0
+    } else if s <= cap + OVERREACH {
+        OVERREACH_WEIGHT
+    } else {
+        0.0
+    }
+}
+
+/// A random encounter sized for this player at the current time of day.
+pub fn scaled_encounter(inv: &Inventory, tod: TimeOfDay) -> Vec<Monster> {
+    let cap = threat_cap(inv);
+    bestiary::weighted_encounter(tod, |m| encounter_weight(m, cap))
+}
+
+/// A lone monster sized for this player.
+pub fn scaled_monster(inv: &Inventory, tod: TimeOfDay) -> Monster {
+    let cap = threat_cap(inv);
+    bestiary::weighted_monster(tod, |m| encounter_weight(m, cap))
+}
+
+/// Gold and items lost, scaled by difficulty (at least one item if any were at stake).
+pub fn scaled_penalty(inv: &Inventory, share: f64, items: usize) -> (f64, usize) {
+    let f = inv.difficulty.penalty_factor();
+    let items = if items == 0 {
+        0
+    } else {
+        ((items as f64 * f).round() as usize).max(1)
+    };
+    ((share * f).min(1.0), items)
+}
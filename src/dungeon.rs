@@ -0,0 +1,475 @@
+use crate::appraisal;
+use crate::bestiary::{self, Monster};
+use crate::clock::{DOOR_ATTEMPT_MINUTES, EXPLORE_MOVE_MINUTES};
+use crate::inventory::Inventory;
+use crate::loot::{currency_regex, parse_and_format_loot_cached};
+use crate::rng::with_rng;
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+// Level layout
+pub const DUNGEON_WIDTH: i32 = 56;
+pub const DUNGEON_HEIGHT: i32 = 22;
+pub const DUNGEON_MIN_ROOMS: usize = 6;
+pub const DUNGEON_MAX_ROOMS: usize = 9;
+
+// Hazards
+pub const DOOR_LOCK_CHANCE: f64 = 0.25;
+pub const DOOR_PICK_CHANCE: f64 = 0.55;
+pub const TRAP_SPOT_CHANCE: f64 = 0.35;
+
+// Skeleton keys open any locked door once; chests sometimes hold one
+pub const DUNGEON_KEY: &str = "Skeleton Key";
+pub const CHEST_KEY_CHANCE: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub w: i32,
+    pub h: i32,
+}
+
+impl Rect {
+    pub fn center(&self) -> (i32, i32) {
+        (self.x + self.w / 2, self.y + self.h / 2)
+    }
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.x + self.w && y >= self.y && y < self.y + self.h
+    }
+    // Overlap test with a one tile margin so walls never touch
+    fn overlaps(&self, o: &Rect) -> bool {
+        self.x - 1 < o.x + o.w
+            && o.x - 1 < self.x + self.w
+            && self.y - 1 < o.y + o.h
+            && o.y - 1 < self.y + self.h
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoomContent {
+    Entrance,
+    Empty,
+    Monster { name: String },
+    Chest,
+    Trap { damage: u32 },
+    Stairs,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Room {
+    pub id: usize,
+    pub rect: Rect,
+    pub content: RoomContent,
+    pub visited: bool,
+    pub cleared: bool,
+}
+
+impl Room {
+    pub fn label(&self) -> String {
+        if !self.visited {
+            return "unexplored".into();
+        }
+        match (&self.content, self.cleared) {
+            (RoomContent::Entrance, _) => "entrance".into(),
+            (RoomContent::Stairs, _) => "stairs down".into(),
+            (RoomContent::Monster { name }, false) => format!("lair of a {}", name),
+            (RoomContent::Chest, false) => "unopened chest".into(),
+            (RoomContent::Chest, true) => "looted chest".into(),
+            (RoomContent::Trap { .. }, _) => "sprung trap".into(),
+            _ => "empty chamber".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Door {
+    None,
+    Open,
+    Locked,
+}
+
+/// Passage between two rooms; `path` lists the floor tiles outside either room.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Corridor {
+    pub a: usize,
+    pub b: usize,
+    pub path: Vec<(i32, i32)>,
+    pub door: Door,
+    pub door_at: Option<(i32, i32)>,
+}
+
+impl Corridor {
+    pub fn other(&self, room: usize) -> Option<usize> {
+        if self.a == room {
+            Some(self.b)
+        } else if self.b == room {
+            Some(self.a)
+        } else {
+            None
+        }
+    }
+}
+
+/// One dungeon level plus the explorer's position in it (saved with the inventory).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dungeon {
+    pub seed: u64,
+    pub depth: u32,
+    pub width: i32,
+    pub height: i32,
+    pub rooms: Vec<Room>,
+    pub corridors: Vec<Corridor>,
+    pub current: usize,
+    pub previous: usize,
+}
+
+/// A way out of the current room.
+#[derive(Debug, Clone)]
+pub struct Exit {
+    pub room: usize,
+    pub direction: &'static str,
+    pub door: Door,
+    pub label: String,
+}
+
+/// What happened on entering a room; `encounter` asks the front end to run a fight.
+#[derive(Debug, Clone, Default)]
+pub struct ExploreReport {
+    pub lines: Vec<String>,
+    pub encounter: Option<Monster>,
+}
+
+fn level_rng(seed: u64, depth: u32) -> SmallRng {
+    SmallRng::seed_from_u64(seed ^ (depth as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+fn roll_content(r: &mut SmallRng, depth: u32) -> RoomContent {
+    let max_strength = (2 + depth * 2).min(10) as u8;
+    match r.gen_range(0..100) {
+        0..=39 => {
+            let pool: Vec<&Monster> = bestiary::MONSTERS
+                .iter()
+                .filter(|m| m.strength <= max_strength)
+                .collect();
+            RoomContent::Monster {
+                name: pool.choose(r).unwrap().name.to_string(),
+            }
+        }
+        40..=59 => RoomContent::Chest,
+        60..=74 => RoomContent::Trap {
+            damage: r.gen_range(2..=4) + depth * 2,
+        },
+        _ => RoomContent::Empty,
+    }
+}
+
+// L-shaped path between two points, horizontal or vertical leg first
+fn carve(from: (i32, i32), to: (i32, i32), horizontal_first: bool) -> Vec<(i32, i32)> {
+    let mut tiles = Vec::new();
+    let (mut x, mut y) = from;
+    let step = |a: i32, b: i32| (b - a).signum();
+    if horizontal_first {
+        while x != to.0 {
+            x += step(x, to.0);
+            tiles.push((x, y));
+        }
+    }
+    while y != to.1 {
+        y += step(y, to.1);
+        tiles.push((x, y));
+    }
+    while x != to.0 {
+        x += step(x, to.0);
+        tiles.push((x, y));
+    }
+    tiles
+}
+
+impl Dungeon {
+    /// Build level `depth` from `seed`; the same pair always yields the same layout.
+    pub fn generate(seed: u64, depth: u32) -> Dungeon {
+        let mut r = level_rng(seed, depth);
+        let target = r.gen_range(DUNGEON_MIN_ROOMS..=DUNGEON_MAX_ROOMS);
+        let mut rects: Vec<Rect> = Vec::new();
+        let mut attempts = 0;
+        while rects.len() < target && attempts < 500 {
+            attempts += 1;
+            let w = r.gen_range(5..=10);
+            let h = r.gen_range(3..=5);
+            let rect = Rect {
+                x: r.gen_range(1..DUNGEON_WIDTH - w - 1),
+                y: r.gen_range(1..DUNGEON_HEIGHT - h - 1),
+                w,
+                h,
+            };
+            if !rects.iter().any(|o| o.overlaps(&rect)) {
+                rects.push(rect);
+            }
+        }
+        // West to east so the stairs end up far from the entrance
+        rects.sort_by_key(|rc| rc.center().0);
+        let last = rects.len() - 1;
+        let rooms: Vec<Room> = rects
+            .iter()
+            .enumerate()
+            .map(|(id, &rect)| Room {
+                id,
+                rect,
+                content: match id {
+                    0 => RoomContent::Entrance,
+                    i if i == last => RoomContent::Stairs,
+                    _ => roll_content(&mut r, depth),
+                },
+                visited: id == 0,
+                cleared: id == 0 || id == last,
+            })
+            .collect();
+        // Chain neighbours, then add a couple of loops
+        let mut links: Vec<(usize, usize)> = (1..rooms.len()).map(|i| (i - 1, i)).collect();
+        for _ in 0..2 {
+            let a = r.gen_range(0..rooms.len());
+            let b = r.gen_range(0..rooms.len());
+            let (a, b) = (a.min(b), a.max(b));
+            if b > a + 1 && !links.contains(&(a, b)) {
+                links.push((a, b));
+            }
+        }
+        let corridors = links
+            .into_iter()
+            .map(|(a, b)| {
+                let (ra, rb) = (rooms[a].rect, rooms[b].rect);
+                let path: Vec<(i32, i32)> = carve(ra.center(), rb.center(), r.gen_bool(0.5))
+                    .into_iter()
+                    .filter(|&(x, y)| !rects.iter().any(|rc| rc.contains(x, y)))
+                    .collect();
+                let locked = r.gen_bool(DOOR_LOCK_CHANCE);
+                let door = match (path.is_empty(), locked) {
+                    (true, _) => Door::None,
+                    (false, true) => Door::Locked,
+                    (false, false) => Door::Open,
+                };
+                Corridor {
+                    a,
+                    b,
+                    door_at: path.last().copied().filter(|_| door != Door::None),
+                    path,
+                    door,
+                }
+            })
+            .collect();
+        Dungeon {
+            seed,
+            depth,
+            width: DUNGEON_WIDTH,
+            height: DUNGEON_HEIGHT,
+            rooms,
+            corridors,
+            current: 0,
+            previous: 0,
+        }
+    }
+
+    pub fn room(&self) -> &Room {
+        &self.rooms[self.current]
+    }
+
+    pub fn exits(&self) -> Vec<Exit> {
+        let (cx, cy) = self.room().rect.center();
+        self.corridors
+            .iter()
+            .filter_map(|c| {
+                let to = c.other(self.current)?;
+                let (tx, ty) = self.rooms[to].rect.center();
+                let (dx, dy) = (tx - cx, ty - cy);
+                let direction = if dx.abs() >= dy.abs() {
+                    if dx >= 0 { "East" } else { "West" }
+                } else if dy >= 0 {
+                    "South"
+                } else {
+                    "North"
+                };
+                Some(Exit {
+                    room: to,
+                    direction,
+                    door: c.door,
+                    label: self.rooms[to].label(),
+                })
+            })
+            .collect()
+    }
+
+    fn corridor_between(&mut self, a: usize, b: usize) -> Option<&mut Corridor> {
+        self.corridors
+            .iter_mut()
+            .find(|c| (c.a == a && c.b == b) || (c.a == b && c.b == a))
+    }
+
+    pub fn explored_count(&self) -> usize {
+        self.rooms.iter().filter(|r| r.visited).count()
+    }
+}
+
+/// Enter the dungeon, generating the first level on the first visit.
+pub fn enter(inv: &mut Inventory) -> &Dungeon {
+    inv.dungeon.get_or_insert_with(|| {
+        let seed = with_rng(|r| r.r#gen::<u64>());
+        Dungeon::generate(seed, 1)
+    })
+}
+
+/// Walk to a neighbouring room and resolve whatever waits inside.
+pub fn move_to(
+    inv: &mut Inventory,
+    room: usize,
+    loot_items: &[String],
+) -> Result<ExploreReport, &'static str> {
+    let key = inv
+        .items
+        .iter()
+        .position(|i| i.eq_ignore_ascii_case(DUNGEON_KEY));
+    let dungeon = inv.dungeon.as_mut().ok_or("You are not in a dungeon")?;
+    let from = dungeon.current;
+    let corridor = dungeon
+        .corridor_between(from, room)
+        .ok_or("No passage leads there")?;
+    let mut report = ExploreReport::default();
+    let mut used_key = None;
+    if corridor.door == Door::Locked {
+        if key.is_some() {
+            report
+                .lines
+                .push("🗝️  Your skeleton key turns in the lock and snaps off.".into());
+            used_key = key;
+        } else if with_rng(|r| r.gen_bool(DOOR_PICK_CHANCE)) {
+            report.lines.push("🔓 You pick the lock.".into());
+        } else {
+            report
+                .lines
+                .push("🔒 The door is locked and your picks slip.".into());
+            report.lines.extend(inv.advance_time(DOOR_ATTEMPT_MINUTES));
+            return Ok(report);
+        }
+        corridor.door = Door::Open;
+    }
+    dungeon.previous = from;
+    dungeon.current = room;
+    let depth = dungeon.depth;
+    let target = &mut dungeon.rooms[room];
+    let first_visit = !target.visited;
+    target.visited = true;
+    let content = target.content.clone();
+    let cleared = target.cleared;
+    if let Some(idx) = used_key {
+        inv.remove_item_at(idx);
+    }
+    report.lines.extend(inv.advance_time(EXPLORE_MOVE_MINUTES));
+    if cleared {
+        report.lines.push(if first_visit {
+            "You step into a quiet chamber.".into()
+        } else {
+            "You retrace your steps.".into()
+        });
+        if content == RoomContent::Stairs {
+            report.lines.push("🪜 Stairs lead further down.".into());
+        }
+        return Ok(report);
+    }
+    match content {
+        RoomContent::Monster { name } => {
+            let monster = bestiary::find(&name).unwrap_or(bestiary::MONSTERS[0]);
+            report
+                .lines
+                .push(format!("⚔️  A {} guards this room!", monster.name));
+            report.encounter = Some(monster);
+        }
+        RoomContent::Chest => {
+            report
+                .lines
+                .push("🧰 You find a chest and pry it open.".into());
+            let reward_gp = with_rng(|r| r.gen_range(5..=20)) * depth;
+            inv.add_item(&format!("{} gp", reward_gp));
+            let mut found = vec![format!("{} gp", reward_gp)];
+            if let Some(desc) = with_rng(|r| loot_items.choose(r).cloned()) {
+                let (items, _) = parse_and_format_loot_cached(&desc);
+                let cre = currency_regex();
+                for (it, true_name) in items.iter().map(|it| appraisal::disguise(it)) {
+                    if cre.is_match(&it) {
+                        inv.add_item(&it);
+                    } else {
+                        inv.push_disguised(it.clone(), true_name, false);
+                        found.push(it);
+                    }
+                }
+            }
+            if with_rng(|r| r.gen_bool(CHEST_KEY_CHANCE)) {
+                inv.add_item(DUNGEON_KEY);
+                found.push(DUNGEON_KEY.into());
+            }
+            report.lines.push(format!("Inside: {}", found.join(", ")));
+            clear_current(inv);
+        }
+        RoomContent::Trap { damage } => {
+            if with_rng(|r| r.gen_bool(TRAP_SPOT_CHANCE)) {
+                report
+                    .lines
+                    .push("⚠️  You spot a pressure plate and step around it.".into());
+            } else {
+                // Traps hurt but never kill outright
+                let dealt = damage.min(inv.current_hp.saturating_sub(1));
+                inv.current_hp -= dealt;
+                report.lines.push(format!(
+                    "💥 A trap springs! You take {} damage ({}/{} HP).",
+                    dealt, inv.current_hp, inv.max_hp
+                ));
+            }
+            clear_current(inv);
+        }
+        _ => {
+            report.lines.push("The room is empty.".into());
+            clear_current(inv);
+        }
+    }
+    inv.save_after_pickup();
+    Ok(report)
+}
+
+/// Mark the current room as dealt with (monster slain, chest looted, trap sprung).
+pub fn clear_current(inv: &mut Inventory) {
+    if let Some(d) = inv.dungeon.as_mut() {
+        let idx = d.current;
+        d.rooms[idx].cleared = true;
+        inv.save_after_pickup();
+    }
+}
+
+/// Fall back to the room you came from (after fleeing a fight).
+pub fn retreat(inv: &mut Inventory) {
+    if let Some(d) = inv.dungeon.as_mut() {
+        d.current = d.previous;
+    }
+}
+
+/// Back to the level entrance (after being knocked out).
+pub fn return_to_entrance(inv: &mut Inventory) {
+    if let Some(d) = inv.dungeon.as_mut() {
+        d.current = 0;
+        d.previous = 0;
+        inv.save_after_pickup();
+    }
+}
+
+/// Take the stairs down to a fresh level generated from the same seed.
+pub fn descend(inv: &mut Inventory) -> Result<u32, &'static str> {
+    let d = inv.dungeon.as_mut().ok_or("You are not in a dungeon")?;
+    if d.room().content != RoomContent::Stairs {
+        return Err("There are no stairs here");
+    }
+    *d = Dungeon::generate(d.seed, d.depth + 1);
+    let depth = d.depth;
+    inv.advance_time(EXPLORE_MOVE_MINUTES);
+    inv.save_after_pickup();
+    Ok(depth)
+}
@@ -0,0 +1,127 @@
+use crate::character;
+use crate::currency;
+use crate::inventory::Inventory;
+use crate::shop;
+
+// Everyone can carry this much; class, level and containers add to it
+pub const BASE_CAPACITY_LB: f64 = 50.0;
+pub const LEVEL_CAPACITY_LB: f64 = 5.0; // per level above the first
+pub const COIN_WEIGHT_LB: f64 = 0.02; // fifty coins to the pound, whatever the metal
+pub const DEFAULT_ITEM_WEIGHT_LB: f64 = 1.0;
+pub const OVERLOAD_PICKPOCKET_PENALTY: f64 = 0.25;
+
+// Containers that raise capacity; only one of each kind is worn
+pub const CONTAINERS: [(&str, f64); 2] = [("Backpack", 30.0), ("Bag of Holding", 150.0)];
+
+// Rough weights for loot the shops don't list, matched on whole words
+const KEYWORD_WEIGHTS: [(&str, f64); 16] = [
+    ("anvil", 50.0),
+    ("armor", 40.0),
+    ("plate", 45.0),
+    ("chain", 20.0),
+    ("shield", 6.0),
+    ("shovel", 5.0),
+    ("weights", 5.0),
+    ("hammer", 3.0),
+    ("sword", 3.0),
+    ("axe", 4.0),
+    ("skull", 2.0),
+    ("fossil", 2.0),
+    ("journal", 1.5),
+    ("ring", 0.1),
+    ("coin", 0.02),
+    ("button", 0.02),
+];
+
+/// Weight of one item in pounds: the shop listing, else a guess from its name.
+pub fn item_weight(name: &str) -> f64 {
+    if let Some(w) = shop::lookup_weight(name) {
+        return w;
+    }
+    let lower = name.to_lowercase();
+    lower
+        .split(|c: char| !c.is_alphanumeric())
+        .find_map(|word| {
+            KEYWORD_WEIGHTS
+                .iter()
+                .find(|(k, _)| *k == word)
+                .map(|(_, w)| *w)
+        })
+        .unwrap_or(DEFAULT_ITEM_WEIGHT_LB)
+}
+
+/// Weight of every coin carried, of every denomination.
+pub fn coin_weight(inv: &Inventory) -> f64 {
+    let coins: u64 = currency::active()
+        .denominations
+        .iter()
+        .map(|d| inv.coin_count(&d.code) as u64)
+        .sum();
+    coins as f64 * COIN_WEIGHT_LB
+}
+
+/// Items plus coin, in pounds. Banked coin and vault items weigh nothing.
+pub fn carried_weight(inv: &Inventory) -> f64 {
+    inv.items.iter().map(|i| item_weight(i)).sum::<f64>() + coin_weight(inv)
+}
+
+/// Pounds the character can carry before being overloaded.
+pub fn capacity(inv: &Inventory) -> f64 {
+    let class = character::class_of(inv).map_or(0.0, |c| c.carry_bonus_lb());
+    let level = inv.level().saturating_sub(1) as f64 * LEVEL_CAPACITY_LB;
+    let containers: f64 = CONTAINERS
+        .iter()
+        .filter(|(name, _)| inv.items.iter().any(|i| i.eq_ignore_ascii_case(name)))
+        .map(|(_, bonus)| bonus)
+        .sum();
+    BASE_CAPACITY_LB + class + level + containers
+}
+
+/// What the character is hauling against what they can manage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Load {
+    pub carried_lb: f64,
+    pub capacity_lb: f64,
+}
+
+impl Load {
+    pub fn overloaded(&self) -> bool {
+        self.carried_lb > self.capacity_lb
+    }
+
+    /// "34.5 / 80 lb", flagged when over
+    pub fn label(&self) -> String {
+        format!(
+            "{:.1} / {:.0} lb{}",
+            self.carried_lb,
+            self.capacity_lb,
+            if self.overloaded() {
+                " (overloaded)"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+pub fn load(inv: &Inventory) -> Load {
+    Load {
+        carried_lb: carried_weight(inv),
+        capacity_lb: capacity(inv),
+    }
+}
+
+pub fn is_overloaded(inv: &Inventory) -> bool {
+    load(inv).overloaded()
+}
+
+/// Why the character can't run from a fight, if they can't.
+pub fn flee_blocked(inv: &Inventory) -> Option<String> {
+    let load = load(inv);
+    load.overloaded().then(|| {
+        format!(
+            "You're carrying too much to run ({}). Sell or bank something, or fight on.",
+            load.label()
+        )
+    })
+}
@@ -0,0 +1,170 @@
+use crate::inventory::Inventory;
+use crate::money::{Money, MoneyError};
+use crate::rng::with_rng;
+use crate::stats::StatEvent;
+use rand::Rng;
+
+// Rune Dice table limits (the house plays with a pair of dice engraved with runes)
+pub const GAMBLE_MIN_BET_CP: u32 = 10;
+pub const GAMBLE_MAX_BET_CP: u32 = 500;
+pub const GAMBLE_SEVEN_PAYOUT: u32 = 4; // 4:1 on a 1-in-6 shot keeps the house ahead
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceWager {
+    Under, // 2-6 pays 1:1
+    Seven, // exactly 7 pays 4:1
+    Over,  // 8-12 pays 1:1
+}
+
+impl DiceWager {
+    pub const ALL: [DiceWager; 3] = [DiceWager::Under, DiceWager::Seven, DiceWager::Over];
+
+    pub fn parse(s: &str) -> Option<DiceWager> {
+        match s.trim().to_lowercase().as_str() {
+            "under" | "low" => Some(DiceWager::Under),
+            "seven" | "7" => Some(DiceWager::Seven),
+            "over" | "high" => Some(DiceWager::Over),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiceWager::Under => "Under Seven (1:1)",
+            DiceWager::Seven => "Lucky Seven (4:1)",
+            DiceWager::Over => "Over Seven (1:1)",
+        }
+    }
+
+    pub fn payout_multiplier(&self) -> u32 {
+        match self {
+            DiceWager::Seven => GAMBLE_SEVEN_PAYOUT,
+            _ => 1,
+        }
+    }
+
+    pub fn wins(&self, total: u8) -> bool {
+        match self {
+            DiceWager::Under => total < 7,
+            DiceWager::Seven => total == 7,
+            DiceWager::Over => total > 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GambleError {
+    BetTooLow,
+    BetTooHigh,
+    InsufficientFunds,
+    NoExactChange,
+}
+
+impl GambleError {
+    pub fn message(&self) -> String {
+        match self {
+            GambleError::BetTooLow => format!("Minimum bet is {} cp", GAMBLE_MIN_BET_CP),
+            GambleError::BetTooHigh => format!("The house caps bets at {} cp", GAMBLE_MAX_BET_CP),
+            GambleError::InsufficientFunds => "You can't cover that bet".into(),
+            GambleError::NoExactChange => "You don't have the exact coins for that bet".into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GambleOutcome {
+    pub wager: DiceWager,
+    pub bet_cp: u32,
+    pub dice: (u8, u8),
+    pub rerolled_from: Option<(u8, u8)>,
+    pub won: bool,
+    pub net_cp: i64,
+    pub luck_spent: bool,
+}
+
+impl GambleOutcome {
+    pub fn total(&self) -> u8 {
+        self.dice.0 + self.dice.1
+    }
+
+    pub fn summary(&self) -> String {
+        let reroll = match self.rerolled_from {
+            Some((a, b)) => format!(" (luck rerolled {}+{})", a, b),
+            None => String::new(),
+        };
+        if self.won {
+            format!(
+                "Rune dice show {}+{}={}{} — {} wins {} cp",
+                self.dice.0,
+                self.dice.1,
+                self.total(),
+                reroll,
+                self.wager.label(),
+                self.net_cp
+            )
+        } else {
+            format!(
+                "Rune dice show {}+{}={}{} — the house takes your {} cp",
+                self.dice.0,
+                self.dice.1,
+                self.total(),
+                reroll,
+                self.bet_cp
+            )
+        }
+    }
+}
+
+fn roll_pair() -> (u8, u8) {
+    with_rng(|r| (r.gen_range(1..=6), r.gen_range(1..=6)))
+}
+
+/// Play one round of Rune Dice. Stored luck (if spent) rerolls a losing throw once.
+pub fn play_rune_dice(
+    inv: &mut Inventory,
+    bet_cp: u32,
+    wager: DiceWager,
+    spend_luck: bool,
+) -> Result<GambleOutcome, GambleError> {
+    if bet_cp < GAMBLE_MIN_BET_CP {
+        return Err(GambleError::BetTooLow);
+    }
+    if bet_cp > GAMBLE_MAX_BET_CP {
+        return Err(GambleError::BetTooHigh);
+    }
+    // The stake goes on the table before the dice roll; a win hands it back with the payout
+    inv.pay(Money::from(bet_cp), inv.pay_mode)
+        .map_err(|e| match e {
+            MoneyError::NoExactChange => GambleError::NoExactChange,
+            MoneyError::InsufficientFunds => GambleError::InsufficientFunds,
+        })?;
+    let mut dice = roll_pair();
+    let mut rerolled_from = None;
+    let mut luck_spent = false;
+    if !wager.wins(dice.0 + dice.1) && spend_luck && inv.luck_boost {
+        inv.luck_boost = false;
+        luck_spent = true;
+        rerolled_from = Some(dice);
+        dice = roll_pair();
+    }
+    let won = wager.wins(dice.0 + dice.1);
+    let net_cp = if won {
+        let winnings = bet_cp.saturating_mul(wager.payout_multiplier());
+        inv.add_copper(bet_cp.saturating_add(winnings));
+        inv.stats.record(StatEvent::CoinEarned(winnings));
+        winnings as i64
+    } else {
+        inv.stats.record(StatEvent::CoinLost(bet_cp));
+        -(bet_cp as i64)
+    };
+    inv.save_after_pickup();
+    Ok(GambleOutcome {
+        wager,
+        bet_cp,
+        dice,
+        rerolled_from,
+        won,
+        net_cp,
+        luck_spent,
+    })
+}
@@ -0,0 +1,121 @@
+use crate::difficulty::Difficulty;
+use crate::inventory::Inventory;
+use crate::stats::StatEvent;
+use serde::{Deserialize, Serialize};
+#[cfg(any(feature = "cli", test))]
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const GRAVEYARD_FILE: &str = "graveyard.json";
+
+static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Where headstones are kept: `GRAVEYARD_FILE` unless `set_path` chose somewhere else.
+pub fn path() -> &'static Path {
+    PATH.get_or_init(|| PathBuf::from(GRAVEYARD_FILE))
+}
+
+/// Keep the graveyard somewhere else (tests use a temp dir). Must happen before the first
+/// burial; false if the path was already settled.
+pub fn set_path(path: impl Into<PathBuf>) -> bool {
+    PATH.set(path.into()).is_ok()
+}
+
+/// Headstone for a hardcore character whose run has ended.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Grave {
+    #[serde(default)]
+    pub character: Option<String>, // "Name the Background Class", if one was created
+    pub killed_by: String,
+    pub died_on: String, // clock label at the time of death
+    pub level: u32,
+    pub experience: u32,
+    pub difficulty: Difficulty,
+    pub gold: u32,
+    pub silver: u32,
+    pub copper: u32,
+    pub items: usize,
+    pub deepest_level: u32, // dungeon depth reached (0 if never entered)
+    pub quests_completed: usize,
+}
+
+impl Grave {
+    pub fn of(inv: &Inventory, killed_by: &str) -> Grave {
+        Grave {
+            character: inv.character.as_ref().map(|c| c.title()),
+            killed_by: killed_by.to_string(),
+            died_on: inv.clock.label(),
+            level: inv.level(),
+            experience: inv.experience,
+            difficulty: inv.difficulty,
+            gold: inv.gold_pieces,
+            silver: inv.silver_pieces,
+            copper: inv.copper_pieces,
+            items: inv.items.len(),
+            deepest_level: inv.dungeon.as_ref().map_or(0, |d| d.depth),
+            quests_completed: inv.quests.completed_count(),
+        }
+    }
+
+    pub fn epitaph(&self) -> String {
+        format!(
+            "🪦 {} slain by {} on {} — level {} ({} XP, {}), {} gp {} sp {} cp and {} item(s), deepest dungeon level {}, {} quest(s) completed",
+            self.character.as_deref().unwrap_or("A nameless adventurer"),
+            self.killed_by,
+            self.died_on,
+            self.level,
+            self.experience,
+            self.difficulty.label(),
+            self.gold,
+            self.silver,
+            self.copper,
+            self.items,
+            self.deepest_level,
+            self.quests_completed
+        )
+    }
+}
+
+/// End a hardcore run: append a headstone to the graveyard and replace the character
+/// with a fresh one that keeps the same difficulty, hardcore setting and lifetime stats.
+pub fn bury(inv: &mut Inventory, killed_by: &str) -> Grave {
+    inv.stats.record(StatEvent::Died);
+    let grave = Grave::of(inv, killed_by);
+    #[cfg(any(feature = "cli", test))]
+    {
+        let mut graves = load().unwrap_or_default();
+        graves.push(grave.clone());
+        if let Err(e) = save(&graves) {
+            println!("⚠️  Failed to update the graveyard: {}", e);
+        }
+    }
+    let mut fresh = Inventory::new();
+    fresh.difficulty = inv.difficulty;
+    fresh.hardcore = true;
+    fresh.stats = std::mem::take(&mut inv.stats);
+    fresh.pay_mode = inv.pay_mode;
+    // At a hot-seat table the world carries on for everyone else
+    if inv.hot_seat {
+        fresh.hot_seat = true;
+        fresh.clock = inv.clock;
+        fresh.market = std::mem::take(&mut inv.market);
+        fresh.notoriety = inv.notoriety;
+        fresh.quests.board = std::mem::take(&mut inv.quests.board);
+        fresh.quests.next_id = inv.quests.next_id;
+    }
+    *inv = fresh;
+    inv.save_after_pickup();
+    grave
+}
+
+#[cfg(any(feature = "cli", test))]
+pub fn load() -> Result<Vec<Grave>, Box<dyn std::error::Error>> {
+    Ok(serde_json::from_str(&fs::read_to_string(path())?)?)
+}
+
+#[cfg(any(feature = "cli", test))]
+fn save(graves: &[Grave]) -> Result<(), Box<dyn std::error::Error>> {
+    fs::write(path(), serde_json::to_string_pretty(graves)?)?;
+    Ok(())
+}
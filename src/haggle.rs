@@ -0,0 +1,209 @@
+use crate::character;
+use crate::inventory::Inventory;
+use crate::rng::with_rng;
+use rand::Rng;
+
+// Negotiation tuning
+pub const HAGGLE_BASE_DISCOUNT: f64 = 0.25; // how far below asking a neutral merchant will go
+pub const HAGGLE_MAX_DISCOUNT: f64 = 0.40;
+pub const HAGGLE_BASE_PATIENCE: u32 = 3;
+pub const HAGGLE_MAX_PATIENCE: u32 = 6;
+pub const HAGGLE_BIG_SALE_CP: u32 = 5_000; // merchants tolerate more rounds on large sales
+pub const HAGGLE_RESPECTED_LEVEL: u32 = 5; // seasoned adventurers get a warmer welcome
+
+/// Player-side factors that shape a negotiation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HaggleContext {
+    pub notoriety: u32,
+    pub charisma: i32,
+    pub level: u32,
+    pub luck: bool,
+}
+
+impl HaggleContext {
+    pub fn from_inventory(inv: &Inventory, spend_luck: bool) -> Self {
+        Self {
+            notoriety: inv.notoriety,
+            charisma: character::class_of(inv).map_or(0, |c| c.charisma()),
+            level: inv.level(),
+            luck: spend_luck && inv.luck_boost,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mood {
+    Friendly,
+    Neutral,
+    Irritated,
+    Furious,
+}
+
+impl Mood {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Mood::Friendly => "Friendly",
+            Mood::Neutral => "Neutral",
+            Mood::Irritated => "Irritated",
+            Mood::Furious => "Furious",
+        }
+    }
+    fn worsen(self, steps: u32) -> Mood {
+        let mut m = self;
+        for _ in 0..steps {
+            m = match m {
+                Mood::Friendly => Mood::Neutral,
+                Mood::Neutral => Mood::Irritated,
+                Mood::Irritated | Mood::Furious => Mood::Furious,
+            };
+        }
+        m
+    }
+    // Share of the gap between ask and offer the merchant gives up per counter
+    fn concession(&self) -> f64 {
+        match self {
+            Mood::Friendly => 0.50,
+            Mood::Neutral => 0.35,
+            Mood::Irritated => 0.20,
+            Mood::Furious => 0.0,
+        }
+    }
+    fn accept_bias(&self) -> f64 {
+        match self {
+            Mood::Friendly => 0.35,
+            Mood::Neutral => 0.20,
+            Mood::Irritated => 0.05,
+            Mood::Furious => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HaggleStep {
+    Accepted { price_cp: u32 },
+    Countered { ask_cp: u32 },
+    WalkedAway,
+}
+
+/// A multi-round negotiation over a fixed basket price.
+#[derive(Debug, Clone)]
+pub struct Negotiation {
+    pub asking_cp: u32,
+    pub current_ask_cp: u32,
+    pub patience: u32,
+    pub mood: Mood,
+    pub round: u32,
+    pub luck_used: bool,
+    pub outcome: Option<HaggleStep>,
+    floor_cp: u32,
+}
+
+impl Negotiation {
+    pub fn new(asking_cp: u32, ctx: HaggleContext) -> Self {
+        // Each level past the first earns a little respect (capped at ten)
+        let seasoned = ctx.level.saturating_sub(1).min(10);
+        let mut discount =
+            HAGGLE_BASE_DISCOUNT + ctx.charisma as f64 * 0.03 + seasoned as f64 * 0.01
+                - ctx.notoriety.min(10) as f64 * 0.02;
+        if ctx.luck {
+            discount += 0.10;
+        }
+        let discount = discount.clamp(0.05, HAGGLE_MAX_DISCOUNT);
+        let floor_cp = ((asking_cp as f64) * (1.0 - discount)).round() as u32;
+        let mut patience =
+            HAGGLE_BASE_PATIENCE as i64 + ctx.charisma.max(0) as i64 + (seasoned / 4) as i64
+                - (ctx.notoriety / 3) as i64;
+        if ctx.luck {
+            patience += 1;
+        }
+        if asking_cp >= HAGGLE_BIG_SALE_CP {
+            patience += 1;
+        }
+        let patience = patience.clamp(1, HAGGLE_MAX_PATIENCE as i64) as u32;
+        let mood = if ctx.notoriety >= 6 {
+            Mood::Irritated
+        } else if ctx.charisma > 0 || ctx.luck || ctx.level >= HAGGLE_RESPECTED_LEVEL {
+            Mood::Friendly
+        } else {
+            Mood::Neutral
+        };
+        Self {
+            asking_cp,
+            current_ask_cp: asking_cp,
+            patience,
+            mood,
+            round: 0,
+            luck_used: ctx.luck,
+            outcome: None,
+            floor_cp: floor_cp.min(asking_cp),
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// Accept the merchant's current asking price.
+    pub fn accept(&mut self) -> HaggleStep {
+        if let Some(o) = self.outcome {
+            return o;
+        }
+        let step = HaggleStep::Accepted {
+            price_cp: self.current_ask_cp,
+        };
+        self.outcome = Some(step);
+        step
+    }
+
+    /// Player breaks off the negotiation.
+    pub fn walk_away(&mut self) -> HaggleStep {
+        if let Some(o) = self.outcome {
+            return o;
+        }
+        self.outcome = Some(HaggleStep::WalkedAway);
+        HaggleStep::WalkedAway
+    }
+
+    /// Make a counter-offer; the merchant accepts, counters or walks away.
+    pub fn offer(&mut self, offer_cp: u32) -> HaggleStep {
+        if let Some(o) = self.outcome {
+            return o;
+        }
+        self.round += 1;
+        if offer_cp >= self.current_ask_cp {
+            return self.accept();
+        }
+        if offer_cp >= self.floor_cp {
+            let span = (self.current_ask_cp - self.floor_cp).max(1) as f64;
+            let closeness = (offer_cp - self.floor_cp) as f64 / span;
+            let chance = (0.20 + closeness * 0.50 + self.mood.accept_bias()).clamp(0.0, 1.0);
+            if with_rng(|r| r.gen_bool(chance)) {
+                let step = HaggleStep::Accepted { price_cp: offer_cp };
+                self.outcome = Some(step);
+                return step;
+            }
+        } else {
+            // Lowballing sours the mood; a real insult sours it twice over
+            let insult = (offer_cp as f64) < self.floor_cp as f64 * 0.6;
+            self.mood = self.mood.worsen(if insult { 2 } else { 1 });
+            if insult {
+                self.patience = self.patience.saturating_sub(1);
+            }
+        }
+        self.patience = self.patience.saturating_sub(1);
+        if self.patience == 0 || self.mood == Mood::Furious {
+            self.outcome = Some(HaggleStep::WalkedAway);
+            return HaggleStep::WalkedAway;
+        }
+        let target = offer_cp.max(self.floor_cp);
+        let gap = self.current_ask_cp.saturating_sub(target) as f64;
+        let drop = (gap * self.mood.concession()).round() as u32;
+        self.current_ask_cp = self
+            .current_ask_cp
+            .saturating_sub(drop.max(1))
+            .max(self.floor_cp);
+        HaggleStep::Countered {
+            ask_cp: self.current_ask_cp,
+        }
+    }
+}
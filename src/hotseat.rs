@@ -0,0 +1,275 @@
+use crate::clock::{MINUTES_PER_HOUR, WorldClock};
+use crate::inventory::{Inventory, format_cp};
+use crate::loot::format_items_for_display;
+use crate::market::Market;
+use crate::money::{Money, PayMode};
+use crate::quest::Quest;
+use serde::{Deserialize, Serialize};
+#[cfg(any(feature = "cli", test))]
+use std::fs;
+
+pub const HOTSEAT_FILE: &str = "hotseat.json";
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 4;
+// World time each player may use before the keyboard passes on
+pub const TURN_MINUTES: u64 = 4 * MINUTES_PER_HOUR;
+
+/// What every player at the table shares: the time of day, the shops and their prices,
+/// how wary the town is of pickpockets, and the rumors pinned up at the tavern.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct World {
+    pub clock: WorldClock,
+    pub market: Market,
+    pub notoriety: u32,
+    pub rumors: Vec<Quest>,
+    pub next_quest_id: u32,
+}
+
+impl World {
+    // The player on turn plays in the shared world...
+    fn lend(&self, inv: &mut Inventory) {
+        inv.clock = self.clock;
+        inv.market = self.market.clone();
+        inv.notoriety = self.notoriety;
+        inv.quests.board = self.rumors.clone();
+        inv.quests.next_id = self.next_quest_id;
+    }
+
+    // ...and whatever they changed in it is kept for everyone
+    fn reclaim(&mut self, inv: &Inventory) {
+        self.clock = inv.clock;
+        self.market = inv.market.clone();
+        self.notoriety = inv.notoriety;
+        self.rumors = inv.quests.board.clone();
+        self.next_quest_id = inv.quests.next_id;
+    }
+}
+
+/// A hot-seat game: several characters taking turns at one keyboard in a shared world.
+/// Each player keeps their own inventory, companions, quests and stats.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotSeat {
+    pub world: World,
+    pub players: Vec<Inventory>,
+    pub turn: usize, // index of the player on turn
+    pub round: u32,  // completed rotations, from 1
+    #[serde(default)]
+    pub turn_began: u64, // world clock minutes when the current turn started
+}
+
+impl HotSeat {
+    /// Seat the players; the world starts from the first player's clock.
+    pub fn new(mut players: Vec<Inventory>) -> HotSeat {
+        let mut world = World::default();
+        if let Some(first) = players.first() {
+            world.reclaim(first);
+        }
+        for p in players.iter_mut() {
+            p.hot_seat = true;
+            world.lend(p);
+        }
+        HotSeat {
+            turn_began: world.clock.minutes,
+            world,
+            players,
+            turn: 0,
+            round: 1,
+        }
+    }
+
+    pub fn current(&self) -> &Inventory {
+        &self.players[self.turn]
+    }
+
+    pub fn current_mut(&mut self) -> &mut Inventory {
+        &mut self.players[self.turn]
+    }
+
+    /// "Aldric the Rogue", or "Player 2" for characters without a name.
+    pub fn name_of(&self, idx: usize) -> String {
+        self.players[idx]
+            .character
+            .as_ref()
+            .map_or_else(|| format!("Player {}", idx + 1), |c| c.name.clone())
+    }
+
+    /// Hand the world to the player on turn. Their own affairs (wages, quest deadlines,
+    /// bank fees, fading luck) catch up on the time the others spent; returns the notes.
+    pub fn begin_turn(&mut self) -> Vec<String> {
+        let now = self.world.clock.minutes;
+        let inv = &mut self.players[self.turn];
+        let behind = now.saturating_sub(inv.clock.minutes);
+        let notes = inv.advance_time(behind);
+        self.world.lend(inv);
+        self.turn_began = now;
+        notes
+    }
+
+    /// World time the player on turn has left.
+    pub fn minutes_left(&self) -> u64 {
+        let used = self.current().clock.minutes.saturating_sub(self.turn_began);
+        TURN_MINUTES.saturating_sub(used)
+    }
+
+    pub fn turn_over(&self) -> bool {
+        self.minutes_left() == 0
+    }
+
+    /// Keep what the player on turn did to the world, e.g. before saving mid-turn.
+    pub fn sync_world(&mut self) {
+        self.world.reclaim(&self.players[self.turn]);
+    }
+
+    /// Take the world back and pass the keyboard to the next player.
+    pub fn end_turn(&mut self) {
+        self.sync_world();
+        self.turn = (self.turn + 1) % self.players.len();
+        if self.turn == 0 {
+            self.round += 1;
+        }
+    }
+
+    /// Check both sides can hand over their part of a trade.
+    pub fn check_trade(&self, trade: &Trade) -> Result<(), TradeError> {
+        let n = self.players.len();
+        if trade.from >= n || trade.to >= n {
+            return Err(TradeError::NoSuchPlayer);
+        }
+        if trade.from == trade.to {
+            return Err(TradeError::SamePlayer);
+        }
+        if trade.give.is_empty() && trade.take.is_empty() {
+            return Err(TradeError::NothingOffered);
+        }
+        for (idx, bundle) in [(trade.from, &trade.give), (trade.to, &trade.take)] {
+            let inv = &self.players[idx];
+            if inv.money() < Money::from(bundle.coin_cp) {
+                return Err(TradeError::CantAfford(self.name_of(idx)));
+            }
+            for (n, &item) in bundle.items.iter().enumerate() {
+                if item >= inv.items.len() || bundle.items[..n].contains(&item) {
+                    return Err(TradeError::MissingItem(self.name_of(idx)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Swap the bundles once the other player has agreed. Stolen goods stay stolen.
+    pub fn execute_trade(&mut self, trade: &Trade) -> Result<(), TradeError> {
+        self.check_trade(trade)?;
+        self.hand_over(trade.from, trade.to, &trade.give);
+        self.hand_over(trade.to, trade.from, &trade.take);
+        for idx in [trade.from, trade.to] {
+            self.players[idx].save_after_pickup();
+        }
+        Ok(())
+    }
+
+    // Move one side's coin and items across (already checked)
+    fn hand_over(&mut self, from: usize, to: usize, bundle: &Bundle) {
+        if bundle.coin_cp > 0 {
+            let paid = self.players[from].pay(Money::from(bundle.coin_cp), PayMode::MakeChange);
+            if paid.is_ok() {
+                self.players[to].receive(Money::from(bundle.coin_cp));
+            }
+        }
+        // Remove from the back so earlier indices stay valid
+        let mut picks = bundle.items.clone();
+        picks.sort_unstable_by(|a, b| b.cmp(a));
+        let mut moved = Vec::with_capacity(picks.len());
+        for idx in picks {
+            let giver = &mut self.players[from];
+            let stolen = giver.is_stolen_at(idx);
+            let true_name = giver.true_name_at(idx).map(str::to_string);
+            moved.push((giver.remove_item_at(idx), true_name, stolen));
+        }
+        for (name, true_name, stolen) in moved.into_iter().rev() {
+            self.players[to].push_disguised(name, true_name, stolen);
+        }
+    }
+
+    #[cfg(any(feature = "cli", test))]
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(HOTSEAT_FILE, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    #[cfg(any(feature = "cli", test))]
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let mut table: Self = serde_json::from_str(&fs::read_to_string(HOTSEAT_FILE)?)?;
+        if table.players.len() < MIN_PLAYERS || table.turn >= table.players.len() {
+            return Err("hot-seat save has no valid table".into());
+        }
+        for p in table.players.iter_mut() {
+            p.hot_seat = true;
+        }
+        Ok(table)
+    }
+}
+
+/// One side of a trade: coin (in copper) and items by their index in the owner's pack.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bundle {
+    pub coin_cp: u32,
+    pub items: Vec<usize>,
+}
+
+impl Bundle {
+    pub fn is_empty(&self) -> bool {
+        self.coin_cp == 0 && self.items.is_empty()
+    }
+
+    /// "2 gp 5 sp and a Torch", or "nothing"; item names come from `owner`'s pack.
+    pub fn label(&self, owner: &Inventory) -> String {
+        let mut parts = Vec::new();
+        if self.coin_cp > 0 {
+            parts.push(format_cp(self.coin_cp));
+        }
+        let names: Vec<String> = self
+            .items
+            .iter()
+            .filter_map(|&i| owner.items.get(i).cloned())
+            .collect();
+        if !names.is_empty() {
+            parts.push(format_items_for_display(&names));
+        }
+        if parts.is_empty() {
+            "nothing".into()
+        } else {
+            parts.join(" and ")
+        }
+    }
+}
+
+/// `from` offers `give` to `to` and asks for `take` in return.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trade {
+    pub from: usize,
+    pub to: usize,
+    pub give: Bundle,
+    pub take: Bundle,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TradeError {
+    NoSuchPlayer,
+    SamePlayer,
+    NothingOffered,
+    CantAfford(String),  // who is short of coin
+    MissingItem(String), // who doesn't hold an item they'd hand over
+    Declined,
+}
+
+impl TradeError {
+    pub fn message(&self) -> String {
+        match self {
+            TradeError::NoSuchPlayer => "There's no such player at the table".into(),
+            TradeError::SamePlayer => "You can't trade with yourself".into(),
+            TradeError::NothingOffered => "Nothing is changing hands".into(),
+            TradeError::CantAfford(who) => format!("{} can't cover the coin", who),
+            TradeError::MissingItem(who) => format!("{} doesn't carry that", who),
+            TradeError::Declined => "The offer is turned down".into(),
+        }
+    }
+}
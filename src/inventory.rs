@@ -1,4 +1,19 @@
+use crate::bank::Bank;
+use crate::character::Character;
+use crate::clock::{LUCK_DURATION_MINUTES, WorldClock};
+use crate::companion::{self, Party};
+use crate::crafting::RecipeBook;
+use crate::currency;
+use crate::difficulty::{self, Difficulty};
+use crate::dungeon::Dungeon;
+#[cfg(feature = "cli")]
+use crate::encumbrance;
+use crate::market::Market;
+use crate::money::{Money, MoneyError, PayMode, Payment};
+use crate::quest::QuestLog;
+use crate::stats::Stats;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 #[cfg(any(feature = "cli", test))]
 use std::fs;
 
@@ -10,6 +25,9 @@ pub struct Inventory {
     pub copper_pieces: u32,
     pub silver_pieces: u32,
     pub gold_pieces: u32,
+    // Coins beyond cp/sp/gp (platinum, electrum or a custom table's), by code.
+    #[serde(default)]
+    pub coins: BTreeMap<String, u32>,
     #[serde(default)]
     pub luck_boost: bool,
     // Player hit points (persistent across fights). Added later; older saves default to 0 and are normalized on load/new.
@@ -17,6 +35,59 @@ pub struct Inventory {
     pub max_hp: u32,
     #[serde(default)]
     pub current_hp: u32,
+    // Times caught pickpocketing; merchants remember faces.
+    #[serde(default)]
+    pub notoriety: u32,
+    // Index-aligned with `items`: true for things lifted from someone's pocket. Entries
+    // past the end count as clean (see `is_stolen_at`).
+    #[serde(default)]
+    pub stolen: Vec<bool>,
+    // Index-aligned with `items`: what a disguised item really is, settled when it was
+    // looted. None (or past the end) for anything that never had a known identity.
+    #[serde(default)]
+    pub true_names: Vec<Option<String>>,
+    #[serde(default)]
+    pub market: Market,
+    #[serde(default)]
+    pub quests: QuestLog,
+    #[serde(default)]
+    pub clock: WorldClock,
+    // Clock reading when stored luck was first noticed; it fades a day later.
+    #[serde(default)]
+    pub luck_since: Option<u64>,
+    // Current dungeon level, position and cleared rooms (None until first visit).
+    #[serde(default)]
+    pub dungeon: Option<Dungeon>,
+    // Picked when the character is created; scales encounters, rewards and penalties.
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    // Earned by slaying monsters; sets the character level.
+    #[serde(default)]
+    pub experience: u32,
+    // Hardcore: dropping to 0 HP ends the run (see `graveyard`).
+    #[serde(default)]
+    pub hardcore: bool,
+    // Name, class and background (None for saves from before character creation).
+    #[serde(default)]
+    pub character: Option<Character>,
+    // Lifetime counters and achievements; carried over to new characters.
+    #[serde(default)]
+    pub stats: Stats,
+    // Banked coin and vault items: out of reach of thieves and monsters.
+    #[serde(default)]
+    pub bank: Bank,
+    // Hired companions: they fight beside you and want paying daily.
+    #[serde(default)]
+    pub party: Party,
+    // Crafting recipes worked out so far.
+    #[serde(default)]
+    pub recipes: RecipeBook,
+    // Whether shops, the tavern and companions get exact coins only or may make change.
+    #[serde(default)]
+    pub pay_mode: PayMode,
+    // Seated at a hot-seat table: the table saves everyone to its own file.
+    #[serde(skip)]
+    pub hot_seat: bool,
 }
 
 impl Inventory {
@@ -26,48 +97,246 @@ impl Inventory {
             copper_pieces: 0,
             silver_pieces: 0,
             gold_pieces: 0,
+            coins: BTreeMap::new(),
             luck_boost: false,
             max_hp: 20,
             current_hp: 20,
+            notoriety: 0,
+            stolen: Vec::new(),
+            true_names: Vec::new(),
+            market: Market::default(),
+            quests: QuestLog::default(),
+            clock: WorldClock::default(),
+            luck_since: None,
+            dungeon: None,
+            difficulty: Difficulty::Normal,
+            experience: 0,
+            hardcore: false,
+            character: None,
+            stats: Stats::default(),
+            bank: Bank::default(),
+            party: Party::default(),
+            recipes: RecipeBook::default(),
+            pay_mode: PayMode::default(),
+            hot_seat: false,
         }
     }
 
+    pub fn level(&self) -> u32 {
+        difficulty::level_for(self.experience)
+    }
+
     pub fn add_item(&mut self, item: &str) {
-        if let Some((amount, cur)) = self.parse_currency(item) {
-            match cur.as_str() {
-                "cp" => self.copper_pieces += amount,
-                "sp" => self.silver_pieces += amount,
-                "gp" => self.gold_pieces += amount,
-                _ => self.items.push(item.to_string()),
+        match self.parse_currency(item) {
+            Some((amount, cur)) => self.add_coins(&cur, amount),
+            None => self.push_item(item.to_string(), false),
+        }
+    }
+
+    /// Carry an item (never coin), noting whether it was stolen.
+    pub fn push_item(&mut self, item: String, stolen: bool) {
+        self.push_disguised(item, None, stolen);
+    }
+
+    /// Carry an item that may be disguised (see `appraisal::disguise`), remembering what it
+    /// really is until it's appraised.
+    pub fn push_disguised(&mut self, item: String, true_name: Option<String>, stolen: bool) {
+        self.stolen.resize(self.items.len(), false);
+        self.true_names.resize(self.items.len(), None);
+        self.items.push(item);
+        self.stolen.push(stolen);
+        self.true_names.push(true_name);
+    }
+
+    /// Put an item at `idx` (e.g. an appraised item back in its old place).
+    pub fn insert_item(&mut self, idx: usize, item: String, stolen: bool) {
+        self.stolen.resize(self.items.len(), false);
+        self.true_names.resize(self.items.len(), None);
+        self.items.insert(idx, item);
+        self.stolen.insert(idx, stolen);
+        self.true_names.insert(idx, None);
+    }
+
+    /// Add coins by code; codes the active currency table doesn't know are kept as items.
+    pub fn add_coins(&mut self, code: &str, amount: u32) {
+        match code {
+            "cp" => self.copper_pieces = self.copper_pieces.saturating_add(amount),
+            "sp" => self.silver_pieces = self.silver_pieces.saturating_add(amount),
+            "gp" => self.gold_pieces = self.gold_pieces.saturating_add(amount),
+            _ if currency::active().get(code).is_some() => {
+                let n = self.coins.entry(code.to_string()).or_default();
+                *n = n.saturating_add(amount);
             }
-        } else {
-            self.items.push(item.to_string());
+            _ => self.push_item(format!("{} {}", amount, code), false),
+        }
+    }
+
+    pub fn coin_count(&self, code: &str) -> u32 {
+        match code {
+            "cp" => self.copper_pieces,
+            "sp" => self.silver_pieces,
+            "gp" => self.gold_pieces,
+            _ => self.coins.get(code).copied().unwrap_or(0),
+        }
+    }
+
+    /// Remove up to `amount` coins of one kind; returns how many were taken.
+    pub fn take_coins(&mut self, code: &str, amount: u32) -> u32 {
+        let slot = match code {
+            "cp" => &mut self.copper_pieces,
+            "sp" => &mut self.silver_pieces,
+            "gp" => &mut self.gold_pieces,
+            _ => match self.coins.get_mut(code) {
+                Some(n) => n,
+                None => return 0,
+            },
+        };
+        let taken = amount.min(*slot);
+        *slot -= taken;
+        self.coins.retain(|_, n| *n > 0);
+        taken
+    }
+
+    pub fn is_stolen_at(&self, idx: usize) -> bool {
+        idx < self.items.len() && self.stolen.get(idx).copied().unwrap_or(false)
+    }
+
+    pub fn set_stolen_at(&mut self, idx: usize, stolen: bool) {
+        if idx < self.items.len() {
+            self.stolen.resize(self.items.len(), false);
+            self.stolen[idx] = stolen;
         }
     }
 
-    pub fn add_copper(&mut self, mut copper: u32) {
-        self.gold_pieces += copper / 100;
-        copper %= 100;
-        self.silver_pieces += copper / 10;
-        copper %= 10;
-        self.copper_pieces += copper;
+    /// What the disguised item at `idx` really is, if that was ever known.
+    pub fn true_name_at(&self, idx: usize) -> Option<&str> {
+        if idx >= self.items.len() {
+            return None;
+        }
+        self.true_names.get(idx).and_then(|n| n.as_deref())
+    }
+
+    /// Remove the item at `idx` along with its stolen flag and true name.
+    pub fn remove_item_at(&mut self, idx: usize) -> String {
+        if idx < self.stolen.len() {
+            self.stolen.remove(idx);
+        }
+        if idx < self.true_names.len() {
+            self.true_names.remove(idx);
+        }
+        self.items.remove(idx)
     }
 
+    /// Advance the world clock by `minutes`; returns any notices worth showing.
+    /// Merchants and quests move on by the whole hours that tick over.
+    pub fn advance_time(&mut self, minutes: u64) -> Vec<String> {
+        if self.luck_boost && self.luck_since.is_none() {
+            self.luck_since = Some(self.clock.minutes);
+        }
+        let hours = self.clock.advance(minutes);
+        self.market.pass_time(hours);
+        let mut notes = self.quests.pass_time(hours);
+        notes.extend(self.bank.pass_time(hours));
+        notes.extend(companion::pass_time(self, hours));
+        if !self.luck_boost {
+            self.luck_since = None;
+        } else if let Some(since) = self.luck_since
+            && self.clock.minutes - since >= LUCK_DURATION_MINUTES
+        {
+            self.luck_boost = false;
+            self.luck_since = None;
+            notes.push("🍂 Your stored luck has faded.".into());
+        }
+        notes
+    }
+
+    pub fn add_copper(&mut self, copper: u32) {
+        self.receive(Money::from(copper));
+    }
+
+    /// Take coin in as gold, silver and copper (change, rewards, sales).
+    pub fn receive(&mut self, amount: Money) {
+        let cp = amount.as_cp();
+        let clamp = |n: u64| n.min(u32::MAX as u64) as u32;
+        self.gold_pieces = self.gold_pieces.saturating_add(clamp(cp / 100));
+        self.silver_pieces = self.silver_pieces.saturating_add(clamp(cp / 10 % 10));
+        self.copper_pieces = self.copper_pieces.saturating_add(clamp(cp % 10));
+    }
+
+    /// Everything carried, across every denomination.
+    pub fn money(&self) -> Money {
+        let table = currency::active();
+        let coin = |code: &str, n: u32| {
+            table.get(code).map_or(Money::ZERO, |d| {
+                Money::cp(d.value_cp as u64).saturating_mul(n as u64)
+            })
+        };
+        [
+            coin("gp", self.gold_pieces),
+            coin("sp", self.silver_pieces),
+            coin("cp", self.copper_pieces),
+        ]
+        .into_iter()
+        .chain(self.coins.iter().map(|(code, n)| coin(code, *n)))
+        .sum()
+    }
+
+    /// `money()` in copper, clamped to `u32` for older call sites.
     pub fn total_cp(&self) -> u32 {
-        self.gold_pieces * 100 + self.silver_pieces * 10 + self.copper_pieces
+        self.money().as_cp_u32()
     }
 
-    pub fn try_spend_cp(&mut self, cost_cp: u32) -> bool {
-        let total = self.total_cp();
-        if total < cost_cp {
-            return false;
+    /// Pay from the coins carried, keeping the rest of the mix as it is. Coins adding up to
+    /// the price are handed over first (largest first); failing that, `MakeChange` breaks the
+    /// smallest coin that covers what's left and takes the change in gp/sp/cp.
+    pub fn pay(&mut self, cost: Money, mode: PayMode) -> Result<Payment, MoneyError> {
+        if self.money() < cost {
+            return Err(MoneyError::InsufficientFunds);
+        }
+        let table = currency::active();
+        let mut plan: Vec<(&str, u32)> = Vec::new();
+        let mut left = cost.as_cp();
+        for d in &table.denominations {
+            let n = (left / d.value_cp as u64).min(self.coin_count(&d.code) as u64) as u32;
+            if n > 0 {
+                plan.push((&d.code, n));
+                left -= n as u64 * d.value_cp as u64;
+            }
+        }
+        let mut change = Money::ZERO;
+        if left > 0 {
+            if mode == PayMode::ExactChange {
+                return Err(MoneyError::NoExactChange);
+            }
+            // Some denomination must have coins to spare, each worth more than `left`
+            let planned = |code: &str| plan.iter().find(|(c, _)| *c == code).map_or(0, |p| p.1);
+            let Some(d) = table
+                .denominations
+                .iter()
+                .rev()
+                .find(|d| self.coin_count(&d.code) > planned(&d.code))
+            else {
+                return Err(MoneyError::InsufficientFunds);
+            };
+            match plan.iter_mut().find(|(c, _)| *c == d.code) {
+                Some(p) => p.1 += 1,
+                None => plan.push((&d.code, 1)),
+            }
+            change = Money::cp(d.value_cp as u64 - left);
+        }
+        for (code, n) in &plan {
+            self.take_coins(code, *n);
         }
-        let remaining = total - cost_cp;
-        self.gold_pieces = remaining / 100;
-        let rem = remaining % 100;
-        self.silver_pieces = rem / 10;
-        self.copper_pieces = rem % 10;
-        true
+        self.receive(change);
+        Ok(Payment {
+            handed_over: cost.saturating_add(change),
+            change,
+        })
+    }
+
+    /// Pay `cost_cp`, making change if needed; false (and nothing spent) if it can't be afforded.
+    pub fn try_spend_cp(&mut self, cost_cp: u32) -> bool {
+        self.pay(Money::from(cost_cp), PayMode::MakeChange).is_ok()
     }
 
     pub fn parse_currency(&self, item: &str) -> Option<(u32, String)> {
@@ -84,6 +353,9 @@ impl Inventory {
     pub fn save_after_pickup(&mut self) {
         #[cfg(any(feature = "cli", test))]
         {
+            if self.hot_seat {
+                return;
+            }
             if let Err(e) = self.save() {
                 println!("⚠️  Failed to save inventory: {}", e);
             }
@@ -92,8 +364,25 @@ impl Inventory {
     }
     #[cfg(feature = "cli")]
     pub fn show(&self) {
+        println!("🕰️  {}", self.clock.label());
+        if let Some(c) = &self.character {
+            println!("🧙 {}", c.title());
+        }
+        println!(
+            "⭐ Level {} ({} XP) — {} difficulty{} — HP {}/{}",
+            self.level(),
+            self.experience,
+            self.difficulty.label(),
+            if self.hardcore { ", hardcore" } else { "" },
+            self.current_hp,
+            self.max_hp
+        );
+        println!("🏋️  Load: {}", encumbrance::load(self).label());
         let has_items = !self.items.is_empty();
-        let has_currency = self.copper_pieces > 0 || self.silver_pieces > 0 || self.gold_pieces > 0;
+        let has_currency = self.copper_pieces > 0
+            || self.silver_pieces > 0
+            || self.gold_pieces > 0
+            || !self.coins.is_empty();
         if !has_items && !has_currency {
             println!("Your inventory is empty.");
             return;
@@ -101,6 +390,12 @@ impl Inventory {
         println!("Your inventory contains:");
         if has_currency {
             println!("💰 Currency:");
+            for d in currency::active().extra() {
+                let n = self.coin_count(&d.code);
+                if n > 0 {
+                    println!("  • {}", d.describe(n));
+                }
+            }
             if self.gold_pieces > 0 {
                 println!("  • {} gold pieces", self.gold_pieces);
             }
@@ -119,6 +414,18 @@ impl Inventory {
                 println!("  • {}", item);
             }
         }
+        if !self.party.is_empty() {
+            println!("🤝 Companions:");
+            for c in &self.party.companions {
+                println!("  • {} — {}", c.title(), c.status_label());
+            }
+        }
+        if !self.quests.active.is_empty() {
+            println!("📜 Quests:");
+            for q in &self.quests.active {
+                println!("  • {} — {}", q.title(), q.progress_label());
+            }
+        }
     }
 
     #[cfg(any(feature = "cli", test))]
@@ -128,7 +435,7 @@ impl Inventory {
     }
     #[cfg(any(feature = "cli", test))]
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let mut inv: Self = serde_json::from_str(&std::fs::read_to_string(SAVE_FILE)?)?;
+        let mut inv: Self = serde_json::from_str(&fs::read_to_string(SAVE_FILE)?)?;
         if inv.max_hp == 0 {
             inv.max_hp = 20;
         }
@@ -154,22 +461,5 @@ impl Default for Inventory {
 }
 
 pub fn format_cp(cp: u32) -> String {
-    if cp == 0 {
-        return "0 cp".to_string();
-    }
-    let gp = cp / 100;
-    let rem = cp % 100;
-    let sp = rem / 10;
-    let cp_left = rem % 10;
-    let mut parts = Vec::new();
-    if gp > 0 {
-        parts.push(format!("{} gp", gp));
-    }
-    if sp > 0 {
-        parts.push(format!("{} sp", sp));
-    }
-    if cp_left > 0 {
-        parts.push(format!("{} cp", cp_left));
-    }
-    parts.join(" ")
+    Money::from(cp).to_string()
 }
@@ -1,7 +1,31 @@
 pub mod actions;
+pub mod appraisal;
+pub mod bank;
+pub mod bestiary;
+pub mod character;
+pub mod clock;
+pub mod combat;
+pub mod companion;
+pub mod crafting;
+pub mod currency;
+pub mod difficulty;
+pub mod dungeon;
+pub mod encumbrance;
+pub mod gamble;
+pub mod graveyard;
+pub mod haggle;
+pub mod hotseat;
 pub mod inventory;
 pub mod loot;
+pub mod map;
+pub mod market;
+pub mod money;
+pub mod quest;
 pub mod rng;
+pub mod shop;
+pub mod stats;
+#[cfg(feature = "tui")]
+pub mod tui;
 pub mod ui;
 #[cfg(feature = "wasm")]
 pub mod wasm_api;
@@ -1,3 +1,4 @@
+use crate::currency;
 use regex::Regex;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
@@ -8,11 +9,20 @@ static RE_CURRENCY: OnceLock<Regex> = OnceLock::new();
 pub type LootCacheEntry = (Arc<[String]>, Arc<String>);
 static LOOT_CACHE: OnceLock<Mutex<HashMap<String, LootCacheEntry>>> = OnceLock::new();
 
+// Both money regexes follow the active currency table (pp|gp|ep|sp|cp by default)
 pub fn standalone_money_regex() -> &'static Regex {
-    RE_STANDALONE_MONEY.get_or_init(|| Regex::new(r"^\d+\s*(gp|sp|cp)$").unwrap())
+    RE_STANDALONE_MONEY.get_or_init(|| {
+        Regex::new(&format!(r"^\d+\s*({})$", currency::active().code_pattern())).unwrap()
+    })
 }
 pub fn currency_regex() -> &'static Regex {
-    RE_CURRENCY.get_or_init(|| Regex::new(r"^(\d+)\s*(cp|sp|gp)$").unwrap())
+    RE_CURRENCY.get_or_init(|| {
+        Regex::new(&format!(
+            r"^(\d+)\s*({})$",
+            currency::active().code_pattern()
+        ))
+        .unwrap()
+    })
 }
 fn loot_cache() -> &'static Mutex<HashMap<String, LootCacheEntry>> {
     LOOT_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
@@ -23,23 +33,28 @@ pub fn parse_loot_into_items(desc: &str) -> Vec<String> {
     let unified = desc.replace(" and ", ",");
     let money_re = standalone_money_regex();
     let mut out = Vec::new();
-    for raw in unified.split(',') {
-        let mut s = raw.trim();
+    // "A platinum coin wrapped in a crude map" is the coin plus the map
+    let pieces = unified
+        .split(',')
+        .flat_map(|raw| match raw.split_once(" wrapped in ") {
+            Some((coin, wrapper)) if currency::parse_coin_phrase(strip_article(coin)).is_some() => {
+                vec![coin, wrapper]
+            }
+            _ => vec![raw],
+        });
+    for raw in pieces {
+        let s = strip_article(raw);
         if s.is_empty() {
             continue;
         }
-        for prefix in [
-            "A ", "a ", "An ", "an ", "some ", "Some ", "several ", "Several ",
-        ] {
-            if let Some(rest) = s.strip_prefix(prefix) {
-                s = rest;
-            }
-        }
-        s = s.trim();
-        if s.is_empty() {
+        if let Some(coins) = currency::parse_coin_phrase(s) {
+            out.push(coins);
             continue;
         }
-        let ends_with_currency = s.ends_with(" gp") || s.ends_with(" sp") || s.ends_with(" cp");
+        let ends_with_currency = currency::active()
+            .denominations
+            .iter()
+            .any(|d| s.ends_with(&format!(" {}", d.code)));
         if money_re.is_match(s) || s.contains('(') || !ends_with_currency {
             out.push(capitalize_first_letter(s));
         }
@@ -50,6 +65,19 @@ pub fn parse_loot_into_items(desc: &str) -> Vec<String> {
     out
 }
 
+// Trim a loot piece and drop its leading article ("a", "some", ...)
+fn strip_article(raw: &str) -> &str {
+    let mut s = raw.trim();
+    for prefix in [
+        "A ", "a ", "An ", "an ", "some ", "Some ", "several ", "Several ",
+    ] {
+        if let Some(rest) = s.strip_prefix(prefix) {
+            s = rest;
+        }
+    }
+    s.trim()
+}
+
 pub fn capitalize_first_letter(s: &str) -> String {
     let mut it = s.chars();
     match it.next() {
@@ -77,32 +105,13 @@ pub fn format_items_for_display(items: &[String]) -> String {
             if let Some(caps) = cre.captures(item)
                 && let (Some(a), Some(cur)) = (caps.get(1), caps.get(2))
             {
-                let amount = a.as_str();
-                let expanded = match cur.as_str() {
-                    "cp" => {
-                        if amount == "1" {
-                            "copper piece"
-                        } else {
-                            "copper pieces"
-                        }
-                    }
-                    "sp" => {
-                        if amount == "1" {
-                            "silver piece"
-                        } else {
-                            "silver pieces"
-                        }
-                    }
-                    "gp" => {
-                        if amount == "1" {
-                            "gold piece"
-                        } else {
-                            "gold pieces"
-                        }
-                    }
-                    _ => return add_article(item),
+                let Ok(amount) = a.as_str().parse::<u32>() else {
+                    return add_article(item);
+                };
+                return match currency::active().get(cur.as_str()) {
+                    Some(d) => d.describe(amount),
+                    None => add_article(item),
                 };
-                return format!("{} {}", amount, expanded);
             }
             add_article(item)
         })
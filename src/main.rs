@@ -1,8 +1,19 @@
 // Clean minimal entrypoint (legacy code moved into library modules)
 use dungeon_core::{
-    actions::{fight_monster, pick_pocket, visit_shop, visit_tavern},
+    actions::{
+        craft_items, create_character, explore_dungeon, fight_monster, pick_pocket, setup_hot_seat,
+        show_inventory, trade_goods, visit_bank, visit_shop, visit_tavern,
+    },
+    currency::{self, CURRENCY_FILE, CurrencyTable},
+    hotseat::HOTSEAT_FILE,
     inventory::{Inventory, SAVE_FILE},
-    ui::{MainAction, prompt_main_action},
+    map::render_map,
+    money::PayMode,
+    print_simple_header,
+    ui::{
+        MainAction, print_stats, prompt_difficulty, prompt_hardcore, prompt_main_action,
+        prompt_turn_action,
+    },
 };
 use std::env;
 use std::fs;
@@ -24,6 +35,21 @@ fn load_loot_items() -> Vec<String> {
         })
 }
 
+// Optional custom coinage; must be installed before any coin is parsed
+fn load_currency_table() {
+    let Ok(json) = fs::read_to_string(CURRENCY_FILE) else {
+        return;
+    };
+    match CurrencyTable::from_json(&json).and_then(currency::install) {
+        Ok(()) => println!(
+            "Using {} coinage from {}",
+            currency::active().name,
+            CURRENCY_FILE
+        ),
+        Err(e) => println!("⚠️  Ignoring {}: {}", CURRENCY_FILE, e.message()),
+    }
+}
+
 fn print_version_and_exit() {
     // Version comes from Cargo.toml via env! macro at compile time
     println!("dungeon v{}", env!("CARGO_PKG_VERSION"));
@@ -39,14 +65,88 @@ fn handle_reset_flag() {
 
 fn print_help_and_exit() {
     println!(
-        "Usage: dungeon [OPTIONS]\n\nOptions:\n  -v, --version    Show version and exit\n  -r, --reset      Reset inventory (delete {save})\n  -h, --help       Show this help and exit\n\nShort flags can be clustered, e.g. -rv.",
-        save = SAVE_FILE
+        "Usage: dungeon [OPTIONS] [COMMAND]\n\nCommands:\n  stats            Show lifetime stats and achievements, then exit\n  hotseat          Local multiplayer: take turns in one shared world (saved to {table})\n\nOptions:\n  -v, --version    Show version and exit\n  -r, --reset      Reset inventory (delete {save})\n  -h, --help       Show this help and exit\n      --exact-change  Only pay with exact coins from now on (saved)\n      --make-change   Let merchants make change again (the default)\n      --tui        Full-screen terminal UI (needs the tui feature)\n\nShort flags can be clustered, e.g. -rv.",
+        save = SAVE_FILE,
+        table = HOTSEAT_FILE
     );
 }
 
+// Everything on the main menu that only touches the player's own inventory
+fn take_action(inventory: &mut Inventory, action: MainAction, loot_items: &[String]) {
+    match action {
+        MainAction::PickPocket => pick_pocket(inventory, loot_items),
+        MainAction::Inventory => show_inventory(inventory),
+        MainAction::Shop => visit_shop(inventory),
+        MainAction::Fight => fight_monster(inventory),
+        MainAction::Dungeon => explore_dungeon(inventory, loot_items),
+        MainAction::Map => match &inventory.dungeon {
+            Some(d) => println!("{}", render_map(d)),
+            None => println!("You haven't set foot in the dungeon yet."),
+        },
+        MainAction::Tavern => visit_tavern(inventory),
+        MainAction::Bank => visit_bank(inventory),
+        MainAction::Craft => craft_items(inventory),
+        MainAction::Stats => print_stats(&inventory.stats),
+        MainAction::Trade | MainAction::EndTurn | MainAction::Exit => {}
+    }
+    for note in inventory.stats.take_announcements() {
+        println!("{}", note);
+    }
+}
+
+// Several characters taking turns at one keyboard; the table is saved after every turn
+fn play_hot_seat(loot_items: &[String]) {
+    let mut table = setup_hot_seat();
+    loop {
+        let name = table.name_of(table.turn);
+        print_simple_header(&format!("Round {} — {}'s turn", table.round, name));
+        for note in table.begin_turn() {
+            println!("{}", note);
+        }
+        loop {
+            let left = table.minutes_left();
+            println!(
+                "🕰️  {} — {}h {:02}m left this turn",
+                table.current().clock.label(),
+                left / 60,
+                left % 60
+            );
+            match prompt_turn_action() {
+                MainAction::EndTurn => break,
+                MainAction::Trade => trade_goods(&mut table),
+                MainAction::Exit => {
+                    table.sync_world();
+                    match table.save() {
+                        Ok(()) => println!("Table saved to {}!", HOTSEAT_FILE),
+                        Err(e) => println!("Failed to save the table: {}", e),
+                    }
+                    println!("Exiting");
+                    return;
+                }
+                action => take_action(table.current_mut(), action, loot_items),
+            }
+            if table.turn_over() {
+                println!("⌛ {}'s time is up.", name);
+                break;
+            }
+        }
+        table.end_turn();
+        if let Err(e) = table.save() {
+            println!("Failed to save the table: {}", e);
+        }
+    }
+}
+
 fn main() {
     // Lightweight manual flag parsing (keep dependencies minimal)
     let mut args: Vec<String> = env::args().skip(1).collect();
+    #[cfg(feature = "tui")]
+    let full_screen = {
+        let before = args.len();
+        args.retain(|a| a != "--tui");
+        args.len() != before
+    };
+    let mut pay_mode = None;
     if !args.is_empty() {
         // Support combined short flags like -vr (order independent)
         let mut did_action = false;
@@ -65,6 +165,8 @@ fn main() {
                     print_help_and_exit();
                     return;
                 }
+                "--exact-change" => pay_mode = Some(PayMode::ExactChange),
+                "--make-change" => pay_mode = Some(PayMode::MakeChange),
                 _ if a.starts_with('-') && !a.starts_with("--") && a.len() > 2 => {
                     // Split clustered short flags like -vr
                     for ch in a.chars().skip(1) {
@@ -92,23 +194,60 @@ fn main() {
         }
         args = remaining;
         // If we only performed a reset action and nothing else, exit early
-        if did_action && args.is_empty() {
+        if did_action && args.is_empty() && pay_mode.is_none() {
+            return;
+        }
+        if args.first().map(String::as_str) == Some("stats") {
+            match Inventory::load() {
+                Ok(inv) => print_stats(&inv.stats),
+                Err(_) => println!("No saved game yet ({} not found).", SAVE_FILE),
+            }
             return;
         }
     }
+    load_currency_table();
     let loot_items = load_loot_items();
     println!("Loaded {} loot items from {}", loot_items.len(), LOOT_FILE);
+    if args.first().map(String::as_str) == Some("hotseat") {
+        play_hot_seat(&loot_items);
+        return;
+    }
     let mut inventory = Inventory::load().unwrap_or_else(|_| {
         println!("No existing inventory found, starting fresh!");
-        Inventory::new()
+        let difficulty = prompt_difficulty();
+        let hardcore = prompt_hardcore();
+        println!(
+            "Difficulty set to {}{}.",
+            difficulty.label(),
+            if hardcore { " (hardcore)" } else { "" }
+        );
+        create_character(difficulty, hardcore)
     });
+    if let Some(mode) = pay_mode {
+        inventory.pay_mode = mode;
+        println!(
+            "{}",
+            match mode {
+                PayMode::ExactChange => "💰 You'll pay with exact coins only.",
+                PayMode::MakeChange => "💰 Merchants may make change for you.",
+            }
+        );
+    }
+    #[cfg(feature = "tui")]
+    if full_screen {
+        let (inventory, result) = dungeon_core::tui::run(inventory, loot_items);
+        if let Err(e) = result {
+            println!("Terminal UI failed: {}", e);
+        }
+        match inventory.save() {
+            Ok(()) => println!("Inventory saved!"),
+            Err(e) => println!("Failed to save inventory: {}", e),
+        }
+        return;
+    }
     loop {
+        println!("🕰️  {}", inventory.clock.label());
         match prompt_main_action() {
-            MainAction::PickPocket => pick_pocket(&mut inventory, &loot_items),
-            MainAction::Inventory => inventory.show(),
-            MainAction::Shop => visit_shop(&mut inventory),
-            MainAction::Fight => fight_monster(&mut inventory),
-            MainAction::Tavern => visit_tavern(&mut inventory),
             MainAction::Exit => {
                 if let Err(e) = inventory.save() {
                     println!("Failed to save inventory: {}", e);
@@ -118,6 +257,7 @@ fn main() {
                 println!("Exiting");
                 break;
             }
+            action => take_action(&mut inventory, action, &loot_items),
         }
     }
 }
@@ -0,0 +1,103 @@
+use crate::dungeon::{Door, Dungeon, RoomContent};
+
+pub const LEGEND: &[(char, &str)] = &[
+    ('@', "you"),
+    ('.', "floor"),
+    ('#', "corridor"),
+    ('+', "door"),
+    ('=', "locked door"),
+    ('M', "monster"),
+    ('$', "chest"),
+    ('^', "trap"),
+    ('<', "entrance"),
+    ('>', "stairs down"),
+];
+
+/// Draw the explored part of a dungeon level as a fixed-size character grid.
+/// Rooms appear once visited; a corridor shows once either end has been seen.
+pub fn render_grid(d: &Dungeon) -> Vec<String> {
+    let (w, h) = (d.width as usize, d.height as usize);
+    let mut grid = vec![vec![' '; w]; h];
+    let put = |grid: &mut Vec<Vec<char>>, x: i32, y: i32, c: char| {
+        if x >= 0 && y >= 0 && (x as usize) < w && (y as usize) < h {
+            grid[y as usize][x as usize] = c;
+        }
+    };
+    for room in d.rooms.iter().filter(|r| r.visited) {
+        let rc = room.rect;
+        for y in rc.y - 1..=rc.y + rc.h {
+            for x in rc.x - 1..=rc.x + rc.w {
+                let c = if rc.contains(x, y) {
+                    '.'
+                } else if y == rc.y - 1 || y == rc.y + rc.h {
+                    '-'
+                } else {
+                    '|'
+                };
+                put(&mut grid, x, y, c);
+            }
+        }
+    }
+    for c in &d.corridors {
+        if !(d.rooms[c.a].visited || d.rooms[c.b].visited) {
+            continue;
+        }
+        for &(x, y) in &c.path {
+            put(&mut grid, x, y, '#');
+        }
+        if let Some((x, y)) = c.door_at {
+            put(
+                &mut grid,
+                x,
+                y,
+                if c.door == Door::Locked { '=' } else { '+' },
+            );
+        }
+    }
+    for room in d.rooms.iter().filter(|r| r.visited) {
+        let mark = match (&room.content, room.cleared) {
+            (RoomContent::Entrance, _) => Some('<'),
+            (RoomContent::Stairs, _) => Some('>'),
+            (RoomContent::Monster { .. }, false) => Some('M'),
+            (RoomContent::Chest, false) => Some('$'),
+            (RoomContent::Trap { .. }, _) => Some('^'),
+            _ => None,
+        };
+        if let Some(m) = mark {
+            let (x, y) = room.rect.center();
+            put(&mut grid, x, y, m);
+        }
+    }
+    let (px, py) = d.room().rect.center();
+    // Keep the room's marker visible: stand one tile to its left
+    put(&mut grid, px - 1, py, '@');
+    grid.into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect()
+}
+
+pub fn legend() -> String {
+    LEGEND
+        .iter()
+        .map(|(c, label)| format!("{} {}", c, label))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Full map block shared by the CLI and the browser: title, bordered grid, legend.
+pub fn render_map(d: &Dungeon) -> String {
+    let border = format!("+{}+", "-".repeat(d.width as usize));
+    let mut out = vec![
+        format!(
+            "Dungeon level {} — {}/{} rooms explored",
+            d.depth,
+            d.explored_count(),
+            d.rooms.len()
+        ),
+        border.clone(),
+    ];
+    out.extend(render_grid(d).into_iter().map(|row| format!("|{}|", row)));
+    out.push(border);
+    out.push(legend());
+    out.join("\n")
+}
@@ -0,0 +1,97 @@
+use crate::shop::ShopKind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Supply & demand tuning
+pub const PRICE_STEP_PER_UNIT: f64 = 0.12; // price swing per unit of net trade pressure
+pub const PRICE_MULTIPLIER_MIN: f64 = 0.30;
+pub const PRICE_MULTIPLIER_MAX: f64 = 2.50;
+pub const DEMAND_RECOVERY_PER_HOUR: f64 = 0.85; // pressure kept each hour (decays toward 0)
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerchantState {
+    pub kind: ShopKind,
+    pub purse_cp: u32,
+}
+
+/// Persistent trading state: merchant purses and per-item price pressure.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Market {
+    #[serde(default)]
+    pub merchants: Vec<MerchantState>,
+    // Positive = glut (player sold copies), negative = scarcity (player bought copies)
+    #[serde(default)]
+    pub pressure: HashMap<String, f64>,
+}
+
+fn item_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+impl Market {
+    fn merchant_mut(&mut self, kind: ShopKind) -> &mut MerchantState {
+        if let Some(i) = self.merchants.iter().position(|m| m.kind == kind) {
+            return &mut self.merchants[i];
+        }
+        self.merchants.push(MerchantState {
+            kind,
+            purse_cp: kind.catalog().purse_cp,
+        });
+        self.merchants.last_mut().unwrap()
+    }
+
+    /// Coin the merchant can currently spend on the player's goods.
+    pub fn purse_cp(&self, kind: ShopKind) -> u32 {
+        self.merchants
+            .iter()
+            .find(|m| m.kind == kind)
+            .map(|m| m.purse_cp)
+            .unwrap_or_else(|| kind.catalog().purse_cp)
+    }
+
+    /// Multiplier applied to both buy and sell prices of an item.
+    pub fn price_multiplier(&self, name: &str) -> f64 {
+        let p = self.pressure.get(&item_key(name)).copied().unwrap_or(0.0);
+        (1.0 - p * PRICE_STEP_PER_UNIT).clamp(PRICE_MULTIPLIER_MIN, PRICE_MULTIPLIER_MAX)
+    }
+
+    /// Merchant pays out for goods; returns the amount actually paid (capped by the purse).
+    pub fn pay_out(&mut self, kind: ShopKind, cp: u32) -> u32 {
+        let m = self.merchant_mut(kind);
+        let paid = cp.min(m.purse_cp);
+        m.purse_cp -= paid;
+        paid
+    }
+
+    pub fn take_in(&mut self, kind: ShopKind, cp: u32) {
+        let m = self.merchant_mut(kind);
+        m.purse_cp = m.purse_cp.saturating_add(cp);
+    }
+
+    pub fn record_sale(&mut self, name: &str) {
+        *self.pressure.entry(item_key(name)).or_insert(0.0) += 1.0;
+    }
+
+    pub fn record_purchase(&mut self, name: &str) {
+        *self.pressure.entry(item_key(name)).or_insert(0.0) -= 1.0;
+    }
+
+    /// Let `hours` pass: purses refill toward their cap and prices drift back to normal.
+    pub fn pass_time(&mut self, hours: u32) {
+        if hours == 0 {
+            return;
+        }
+        for m in self.merchants.iter_mut() {
+            let cat = m.kind.catalog();
+            if m.purse_cp < cat.purse_cp {
+                let refill = cat.purse_refill_cp.saturating_mul(hours);
+                m.purse_cp = m.purse_cp.saturating_add(refill).min(cat.purse_cp);
+            }
+        }
+        let keep = DEMAND_RECOVERY_PER_HOUR.powi(hours as i32);
+        self.pressure.retain(|_, p| {
+            *p *= keep;
+            p.abs() >= 0.05
+        });
+    }
+}
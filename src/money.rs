@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// An amount of coin, counted in copper. Arithmetic is checked or saturating, never wrapping.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct Money(u64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+    pub const MAX: Money = Money(u64::MAX);
+
+    pub const fn cp(cp: u64) -> Money {
+        Money(cp)
+    }
+    pub const fn sp(sp: u64) -> Money {
+        Money(sp.saturating_mul(10))
+    }
+    pub const fn gp(gp: u64) -> Money {
+        Money(gp.saturating_mul(100))
+    }
+
+    pub const fn as_cp(self) -> u64 {
+        self.0
+    }
+
+    /// For the many places that still count in `u32` copper; clamps rather than wraps.
+    pub fn as_cp_u32(self) -> u32 {
+        self.0.min(u32::MAX as u64) as u32
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        self.0.checked_add(other.0).map(Money)
+    }
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        self.0.checked_sub(other.0).map(Money)
+    }
+    pub fn checked_mul(self, n: u64) -> Option<Money> {
+        self.0.checked_mul(n).map(Money)
+    }
+    pub fn saturating_add(self, other: Money) -> Money {
+        Money(self.0.saturating_add(other.0))
+    }
+    pub fn saturating_sub(self, other: Money) -> Money {
+        Money(self.0.saturating_sub(other.0))
+    }
+    pub fn saturating_mul(self, n: u64) -> Money {
+        Money(self.0.saturating_mul(n))
+    }
+
+    /// Scale by a factor (discounts, markups, shares), rounding to the nearest copper.
+    pub fn scale(self, factor: f64) -> Money {
+        let v = (self.0 as f64 * factor.max(0.0)).round();
+        if v >= u64::MAX as f64 {
+            Money::MAX
+        } else {
+            Money(v as u64)
+        }
+    }
+}
+
+impl From<u32> for Money {
+    fn from(cp: u32) -> Money {
+        Money(cp as u64)
+    }
+}
+
+impl std::iter::Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Money::saturating_add)
+    }
+}
+
+/// Shown the way prices always have been: gp, sp and cp.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return write!(f, "0 cp");
+        }
+        let parts: Vec<String> = [
+            (self.0 / 100, "gp"),
+            (self.0 / 10 % 10, "sp"),
+            (self.0 % 10, "cp"),
+        ]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, code)| format!("{} {}", n, code))
+        .collect();
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// How a payment may be made from the coins carried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PayMode {
+    /// Hand over coins adding up to exactly the price, or refuse.
+    ExactChange,
+    /// Exact coins where possible, otherwise break the smallest coin that covers the rest.
+    #[default]
+    MakeChange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoneyError {
+    InsufficientFunds,
+    NoExactChange,
+}
+
+impl MoneyError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            MoneyError::InsufficientFunds => "You can't afford that",
+            MoneyError::NoExactChange => "You don't have the exact coins for that",
+        }
+    }
+}
+
+/// What actually crossed the counter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Payment {
+    pub handed_over: Money,
+    pub change: Money,
+}
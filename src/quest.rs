@@ -0,0 +1,387 @@
+use crate::inventory::Inventory;
+use crate::rng::with_rng;
+use crate::stats::StatEvent;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+pub const QUEST_BOARD_SIZE: usize = 3;
+pub const QUEST_MAX_ACTIVE: usize = 3;
+pub const QUEST_BOARD_HOURS: u32 = 30; // how long a rumor stays pinned up
+pub const QUEST_HISTORY_LEN: usize = 20;
+
+// (monster, count, reward cp)
+const SLAY_TEMPLATES: &[(&str, u32, u32)] = &[
+    ("Goblin Sneak", 3, 1_500),
+    ("Cave Rat", 4, 800),
+    ("Skeleton Guard", 2, 2_000),
+    ("Orc Marauder", 2, 3_000),
+    ("Ghoul", 1, 3_000),
+    ("Ogre Brute", 1, 5_000),
+    ("Wyvern", 1, 8_000),
+];
+// (item keyword, recipient, reward cp)
+const DELIVER_TEMPLATES: &[(&str, &str, u32)] = &[
+    ("blood-stained map", "a nervous cartographer", 4_000),
+    ("journal of heresies", "the temple archivist", 3_000),
+    ("iridescent scale", "the alchemist's apprentice", 2_500),
+    ("trilobite fossil", "a travelling scholar", 1_500),
+];
+// (item keyword, mark, reward cp)
+const LIFT_TEMPLATES: &[(&str, &str, u32)] = &[
+    ("signet ring", "a noble", 3_000),
+    ("silver ring", "a moneylender", 1_200),
+    ("engraved locket", "a grieving widow's brother", 2_000),
+    ("jeweled clasp", "a guild master", 2_500),
+];
+const BONUS_ITEMS: &[&str] = &["Potion of Healing", "Scroll of Shielding", "Elixir of Luck"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Objective {
+    Slay { monster: String, count: u32 },
+    Deliver { item: String, recipient: String },
+    Lift { item: String, mark: String },
+}
+
+impl Objective {
+    /// Hours allowed once the quest is accepted.
+    pub fn time_limit(&self) -> u32 {
+        match self {
+            Objective::Slay { .. } => 40,
+            Objective::Deliver { .. } => 60,
+            Objective::Lift { .. } => 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuestStatus {
+    Available,
+    Active,
+    Complete, // objectives met; reward waiting at the tavern
+    TurnedIn,
+    Expired,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quest {
+    pub id: u32,
+    pub objective: Objective,
+    pub progress: u32,
+    pub reward_cp: u32,
+    pub reward_item: Option<String>,
+    pub hours_left: u32,
+    pub status: QuestStatus,
+}
+
+impl Quest {
+    pub fn title(&self) -> String {
+        match &self.objective {
+            Objective::Slay { monster, count } if *count == 1 => {
+                format!("Slay {}", with_article(monster))
+            }
+            Objective::Slay { monster, count } => format!("Slay {} {}s", count, monster),
+            Objective::Deliver { item, .. } => format!("Deliver the {}", item),
+            Objective::Lift { item, mark } => {
+                format!("Lift {} from {}", with_article(item), mark)
+            }
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match &self.objective {
+            Objective::Slay { monster, .. } => {
+                format!(
+                    "The watch posts a bounty on a {} terrorising the roads.",
+                    monster
+                )
+            }
+            Objective::Deliver { item, recipient } => {
+                format!("{} will pay well for a {}.", capitalize(recipient), item)
+            }
+            Objective::Lift { item, mark } => format!(
+                "Someone wants the {} carried by {}. Pick the right pocket.",
+                item, mark
+            ),
+        }
+    }
+
+    pub fn required(&self) -> u32 {
+        match &self.objective {
+            Objective::Slay { count, .. } => *count,
+            _ => 1,
+        }
+    }
+
+    pub fn progress_label(&self) -> String {
+        match self.status {
+            QuestStatus::Complete => "complete — claim at the tavern".into(),
+            QuestStatus::TurnedIn => "rewarded".into(),
+            QuestStatus::Expired => "expired".into(),
+            _ => format!(
+                "{}/{} ({}h left)",
+                self.progress,
+                self.required(),
+                self.hours_left
+            ),
+        }
+    }
+
+    pub fn reward_label(&self) -> String {
+        let coin = crate::inventory::format_cp(self.reward_cp);
+        match &self.reward_item {
+            Some(item) => format!("{} + {}", coin, item),
+            None => coin,
+        }
+    }
+}
+
+fn with_article(s: &str) -> String {
+    let vowel = matches!(
+        s.chars().next().map(|c| c.to_ascii_lowercase()),
+        Some('a' | 'e' | 'i' | 'o' | 'u')
+    );
+    format!("{} {}", if vowel { "an" } else { "a" }, s)
+}
+
+fn capitalize(s: &str) -> String {
+    crate::loot::capitalize_first_letter(s)
+}
+
+fn matches_item(carried: &str, keyword: &str) -> bool {
+    carried.to_lowercase().contains(&keyword.to_lowercase())
+}
+
+/// Things that happen in the world which quests listen to.
+#[derive(Debug, Clone, Copy)]
+pub enum QuestEvent<'a> {
+    MonsterSlain(&'a str),
+    ItemStolen(&'a str),
+}
+
+/// Saved quest state: rumors on the tavern board, accepted quests and a short history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuestLog {
+    #[serde(default)]
+    pub board: Vec<Quest>,
+    #[serde(default)]
+    pub active: Vec<Quest>,
+    #[serde(default)]
+    pub history: Vec<Quest>,
+    #[serde(default)]
+    pub next_id: u32,
+    // Quests ever turned in; the history only keeps the last few
+    #[serde(default)]
+    pub completed: u32,
+}
+
+impl QuestLog {
+    fn roll_quest(&mut self) -> Quest {
+        self.next_id += 1;
+        let id = self.next_id;
+        with_rng(|r| {
+            let (objective, reward_cp) = match r.gen_range(0..3) {
+                0 => {
+                    let (m, c, cp) = *SLAY_TEMPLATES.choose(r).unwrap();
+                    (
+                        Objective::Slay {
+                            monster: m.into(),
+                            count: c,
+                        },
+                        cp,
+                    )
+                }
+                1 => {
+                    let (item, who, cp) = *DELIVER_TEMPLATES.choose(r).unwrap();
+                    (
+                        Objective::Deliver {
+                            item: item.into(),
+                            recipient: who.into(),
+                        },
+                        cp,
+                    )
+                }
+                _ => {
+                    let (item, mark, cp) = *LIFT_TEMPLATES.choose(r).unwrap();
+                    (
+                        Objective::Lift {
+                            item: item.into(),
+                            mark: mark.into(),
+                        },
+                        cp,
+                    )
+                }
+            };
+            let reward_item = r
+                .gen_bool(0.3)
+                .then(|| BONUS_ITEMS.choose(r).unwrap().to_string());
+            Quest {
+                id,
+                objective,
+                progress: 0,
+                reward_cp,
+                reward_item,
+                hours_left: QUEST_BOARD_HOURS,
+                status: QuestStatus::Available,
+            }
+        })
+    }
+
+    /// Top the rumor board back up, avoiding duplicates of pinned or accepted quests.
+    pub fn refresh_board(&mut self) {
+        let mut attempts = 0;
+        while self.board.len() < QUEST_BOARD_SIZE && attempts < 20 {
+            attempts += 1;
+            let q = self.roll_quest();
+            let dup = self
+                .board
+                .iter()
+                .chain(self.active.iter())
+                .any(|o| o.objective == q.objective);
+            if !dup {
+                self.board.push(q);
+            }
+        }
+    }
+
+    pub fn accept(&mut self, id: u32) -> Result<&Quest, &'static str> {
+        if self.active.len() >= QUEST_MAX_ACTIVE {
+            return Err("You already juggle too many quests");
+        }
+        let idx = self
+            .board
+            .iter()
+            .position(|q| q.id == id)
+            .ok_or("No such rumor on the board")?;
+        let mut q = self.board.remove(idx);
+        q.status = QuestStatus::Active;
+        q.hours_left = q.objective.time_limit();
+        self.active.push(q);
+        Ok(self.active.last().unwrap())
+    }
+
+    pub fn abandon(&mut self, id: u32) -> Option<Quest> {
+        let idx = self.active.iter().position(|q| q.id == id)?;
+        Some(self.active.remove(idx))
+    }
+
+    /// Feed a world event to active quests; returns progress notes.
+    pub fn record(&mut self, event: QuestEvent) -> Vec<String> {
+        let mut notes = Vec::new();
+        for q in self.active.iter_mut() {
+            if q.status != QuestStatus::Active {
+                continue;
+            }
+            let hit = match (&q.objective, event) {
+                (Objective::Slay { monster, .. }, QuestEvent::MonsterSlain(name)) => {
+                    monster.eq_ignore_ascii_case(name)
+                }
+                (Objective::Lift { item, .. }, QuestEvent::ItemStolen(name)) => {
+                    matches_item(name, item)
+                }
+                _ => false,
+            };
+            if hit {
+                q.progress += 1;
+                if q.progress >= q.required() {
+                    q.status = QuestStatus::Complete;
+                    notes.push(format!("📜 Quest complete: {}", q.title()));
+                } else {
+                    notes.push(format!("📜 {}: {}", q.title(), q.progress_label()));
+                }
+            }
+        }
+        notes
+    }
+
+    /// Mark delivery quests complete when the requested item is being carried.
+    pub fn sync_inventory(&mut self, items: &[String]) {
+        for q in self.active.iter_mut() {
+            if let Objective::Deliver { item, .. } = &q.objective {
+                let has = items.iter().any(|i| matches_item(i, item));
+                match (q.status, has) {
+                    (QuestStatus::Active, true) => {
+                        q.progress = 1;
+                        q.status = QuestStatus::Complete;
+                    }
+                    (QuestStatus::Complete, false) => {
+                        q.progress = 0;
+                        q.status = QuestStatus::Active;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Let `hours` pass: rumors come down and unfinished quests run out of time.
+    pub fn pass_time(&mut self, hours: u32) -> Vec<String> {
+        let mut notes = Vec::new();
+        self.board.retain_mut(|q| {
+            q.hours_left = q.hours_left.saturating_sub(hours);
+            q.hours_left > 0
+        });
+        let mut expired = Vec::new();
+        self.active.retain_mut(|q| {
+            if q.status != QuestStatus::Active {
+                return true;
+            }
+            q.hours_left = q.hours_left.saturating_sub(hours);
+            if q.hours_left == 0 {
+                q.status = QuestStatus::Expired;
+                notes.push(format!("⌛ Quest expired: {}", q.title()));
+                expired.push(q.clone());
+                return false;
+            }
+            true
+        });
+        for q in expired {
+            self.push_history(q);
+        }
+        notes
+    }
+
+    fn push_history(&mut self, q: Quest) {
+        self.history.push(q);
+        if self.history.len() > QUEST_HISTORY_LEN {
+            self.history.remove(0);
+        }
+    }
+
+    pub fn completed_count(&self) -> usize {
+        self.completed as usize
+    }
+}
+
+/// Hand in every completed quest: delivery items are handed over, rewards paid out.
+pub fn claim_rewards(inv: &mut Inventory) -> Vec<String> {
+    inv.quests.sync_inventory(&inv.items);
+    let (done, still): (Vec<Quest>, Vec<Quest>) = inv
+        .quests
+        .active
+        .drain(..)
+        .partition(|q| q.status == QuestStatus::Complete);
+    inv.quests.active = still;
+    let mut notes = Vec::new();
+    for mut q in done {
+        if let Objective::Deliver { item, .. } = &q.objective
+            && let Some(idx) = inv.items.iter().position(|i| matches_item(i, item))
+        {
+            let handed = inv.remove_item_at(idx);
+            notes.push(format!("You hand over the {}.", handed));
+        }
+        inv.add_copper(q.reward_cp);
+        inv.stats.record(StatEvent::CoinEarned(q.reward_cp));
+        if let Some(item) = &q.reward_item {
+            inv.add_item(item);
+        }
+        notes.push(format!("🏆 {} — reward: {}", q.title(), q.reward_label()));
+        q.status = QuestStatus::TurnedIn;
+        inv.quests.completed = inv.quests.completed.saturating_add(1);
+        inv.quests.push_history(q);
+    }
+    if !notes.is_empty() {
+        inv.save_after_pickup();
+    }
+    notes
+}
@@ -0,0 +1,306 @@
+use crate::actions::Rarity;
+use crate::appraisal;
+use crate::inventory::Inventory;
+use crate::market::Market;
+use crate::money::{Money, MoneyError, Payment};
+use crate::rng::with_rng;
+use crate::stats::StatEvent;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+// Catalog files are embedded so the wasm build needs no filesystem access.
+const GENERAL_CATALOG: &str = include_str!("../data/shops/general.json");
+const BLACKSMITH_CATALOG: &str = include_str!("../data/shops/blacksmith.json");
+const ALCHEMIST_CATALOG: &str = include_str!("../data/shops/alchemist.json");
+const MAGIC_CATALOG: &str = include_str!("../data/shops/magic_emporium.json");
+const FENCE_CATALOG: &str = include_str!("../data/shops/fence.json");
+
+static CATALOGS: OnceLock<Vec<Catalog>> = OnceLock::new();
+
+// Items sold to a shop that stocks them fetch a little more.
+pub const SPECIALTY_SELL_BONUS: f64 = 1.25;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShopKind {
+    #[default]
+    General,
+    Blacksmith,
+    Alchemist,
+    MagicEmporium,
+    Fence,
+}
+
+impl ShopKind {
+    pub const ALL: [ShopKind; 5] = [
+        ShopKind::General,
+        ShopKind::Blacksmith,
+        ShopKind::Alchemist,
+        ShopKind::MagicEmporium,
+        ShopKind::Fence,
+    ];
+
+    pub fn parse(s: &str) -> Option<ShopKind> {
+        match s.trim().to_lowercase().as_str() {
+            "general" | "general store" => Some(ShopKind::General),
+            "blacksmith" | "smith" => Some(ShopKind::Blacksmith),
+            "alchemist" => Some(ShopKind::Alchemist),
+            "magic" | "magic emporium" | "emporium" => Some(ShopKind::MagicEmporium),
+            "fence" => Some(ShopKind::Fence),
+            _ => None,
+        }
+    }
+
+    pub fn catalog(&self) -> &'static Catalog {
+        let all = CATALOGS.get_or_init(|| {
+            [
+                GENERAL_CATALOG,
+                BLACKSMITH_CATALOG,
+                ALCHEMIST_CATALOG,
+                MAGIC_CATALOG,
+                FENCE_CATALOG,
+            ]
+            .iter()
+            .map(|src| serde_json::from_str(src).expect("embedded shop catalog is valid"))
+            .collect()
+        });
+        let idx = ShopKind::ALL.iter().position(|k| k == self).unwrap();
+        &all[idx]
+    }
+
+    pub fn label(&self) -> &'static str {
+        &self.catalog().name
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct RarityWeights {
+    #[serde(default)]
+    pub common: u32,
+    #[serde(default)]
+    pub uncommon: u32,
+    #[serde(default)]
+    pub rare: u32,
+    #[serde(default)]
+    pub epic: u32,
+    #[serde(default)]
+    pub legendary: u32,
+}
+
+impl RarityWeights {
+    fn pairs(&self) -> [(Rarity, u32); 5] {
+        [
+            (Rarity::Common, self.common),
+            (Rarity::Uncommon, self.uncommon),
+            (Rarity::Rare, self.rare),
+            (Rarity::Epic, self.epic),
+            (Rarity::Legendary, self.legendary),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CatalogItem {
+    pub name: String,
+    pub rarity: Rarity,
+    #[serde(default)]
+    pub weight: Option<f64>, // pounds; see `encumbrance::item_weight` for unlisted items
+}
+
+/// A shop's wares plus its buy/sell rules, loaded from `data/shops/*.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Catalog {
+    pub name: String,
+    pub greeting: String,
+    pub stock_size: [u32; 2],
+    pub rarity_weights: RarityWeights,
+    pub markup: f64,    // multiplier on list prices when the player buys
+    pub sell_rate: f64, // share of an item's value paid when the player sells
+    pub buys_stolen: bool,
+    pub purse_cp: u32,        // coin on hand when fully restocked
+    pub purse_refill_cp: u32, // coin regained per hour
+    #[serde(default)]
+    pub appraisal_fee_cp: Option<u32>, // shops that identify mystery items, and for how much
+    pub items: Vec<CatalogItem>,
+}
+
+impl Catalog {
+    pub fn stocks(&self, name: &str) -> bool {
+        self.items.iter().any(|i| i.name.eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockItem {
+    pub name: String,
+    pub rarity: Rarity,
+    pub price_cp: u32,
+}
+
+/// Rarity of a known item in any shop catalog.
+pub fn lookup_rarity(name: &str) -> Option<Rarity> {
+    ShopKind::ALL.iter().find_map(|k| {
+        k.catalog()
+            .items
+            .iter()
+            .find(|i| i.name.eq_ignore_ascii_case(name))
+            .map(|i| i.rarity)
+    })
+}
+
+/// Listed weight (lb) of a catalog item, if any shop sells it.
+pub fn lookup_weight(name: &str) -> Option<f64> {
+    ShopKind::ALL.iter().find_map(|k| {
+        k.catalog()
+            .items
+            .iter()
+            .find(|i| i.name.eq_ignore_ascii_case(name))
+            .and_then(|i| i.weight)
+    })
+}
+
+fn sample_price(r: &mut rand::rngs::SmallRng, rarity: Rarity) -> u32 {
+    let range = rarity.price_range_cp();
+    let mut sample = || r.gen_range(*range.start()..=*range.end());
+    // Weight toward the lower end for the expensive tiers
+    match rarity {
+        Rarity::Epic | Rarity::Legendary => sample().min(sample()),
+        _ => sample(),
+    }
+}
+
+/// Roll a fresh stock for the given shop following its rarity distribution.
+/// Prices react to the player's past trading through the market's demand.
+pub fn generate_stock(kind: ShopKind, market: &Market) -> Vec<StockItem> {
+    let cat = kind.catalog();
+    with_rng(|r| {
+        let [lo, hi] = cat.stock_size;
+        let count = (r.gen_range(lo..=hi.max(lo)) as usize).min(cat.items.len());
+        let mut pool: Vec<&CatalogItem> = cat.items.iter().collect();
+        pool.shuffle(r);
+        let weights = cat.rarity_weights.pairs();
+        let mut out = Vec::with_capacity(count);
+        while out.len() < count && !pool.is_empty() {
+            let rarity = weights
+                .choose_weighted(r, |(_, w)| *w)
+                .map(|(rar, _)| *rar)
+                .unwrap_or(Rarity::Common);
+            // Fall back to any remaining item when the rolled tier is exhausted
+            let idx = pool.iter().position(|i| i.rarity == rarity).unwrap_or(0);
+            let item = pool.remove(idx);
+            let price = (sample_price(r, item.rarity) as f64
+                * cat.markup
+                * market.price_multiplier(&item.name))
+            .round() as u32;
+            out.push(StockItem {
+                name: item.name.clone(),
+                rarity: item.rarity,
+                price_cp: price.max(1),
+            });
+        }
+        out
+    })
+}
+
+/// What this shop would pay for the item at `idx`; `None` means it refuses the item.
+pub fn sell_offer_cp(kind: ShopKind, inv: &Inventory, idx: usize) -> Option<u32> {
+    let name = inv.items.get(idx)?;
+    let cat = kind.catalog();
+    if inv.is_stolen_at(idx) && !cat.buys_stolen {
+        return None;
+    }
+    let base = with_rng(|r| {
+        if appraisal::is_unidentified(name) {
+            return r.gen_range(appraisal::UNIDENTIFIED_OFFER_CP);
+        }
+        match lookup_rarity(name).or_else(|| appraisal::identity_rarity(name)) {
+            Some(rar) => sample_price(r, rar),
+            None => r.gen_range(30..=1000),
+        }
+    });
+    let mut rate = cat.sell_rate * inv.market.price_multiplier(name);
+    if cat.stocks(name) {
+        rate *= SPECIALTY_SELL_BONUS;
+    }
+    Some(((base as f64) * rate).round().max(1.0) as u32)
+}
+
+/// Roll offers for every carried item.
+pub fn sell_offers(kind: ShopKind, inv: &Inventory) -> Vec<Option<u32>> {
+    (0..inv.items.len())
+        .map(|i| sell_offer_cp(kind, inv, i))
+        .collect()
+}
+
+/// Total the shop would pay for the selection, capped by what's left in its purse.
+pub fn sale_total_cp(
+    kind: ShopKind,
+    inv: &Inventory,
+    selected: &[usize],
+    offers: &[Option<u32>],
+) -> u32 {
+    let total = selected
+        .iter()
+        .filter_map(|&i| offers.get(i).copied().flatten())
+        .fold(0u32, |acc, p| acc.saturating_add(p));
+    total.min(inv.market.purse_cp(kind))
+}
+
+/// Remove the selected items and pay out their offers from the merchant's purse;
+/// returns the removed names and total paid.
+pub fn sell_selected(
+    inv: &mut Inventory,
+    kind: ShopKind,
+    selected: &[usize],
+    offers: &[Option<u32>],
+) -> (Vec<String>, u32) {
+    let mut picks: Vec<usize> = selected
+        .iter()
+        .copied()
+        .filter(|&i| i < inv.items.len() && offers.get(i).copied().flatten().is_some())
+        .collect();
+    picks.sort_unstable();
+    picks.dedup();
+    let mut total_cp: u32 = 0;
+    let mut removed = Vec::with_capacity(picks.len());
+    // Remove from the back so earlier indices stay valid
+    for &i in picks.iter().rev() {
+        total_cp = total_cp.saturating_add(offers[i].unwrap_or(0));
+        let name = inv.remove_item_at(i);
+        inv.market.record_sale(&name);
+        removed.push(name);
+    }
+    removed.reverse();
+    let paid = inv.market.pay_out(kind, total_cp);
+    inv.add_copper(paid);
+    inv.stats.record(StatEvent::ItemsSold(removed.len() as u32));
+    inv.stats.record(StatEvent::CoinEarned(paid));
+    (removed, paid)
+}
+
+/// Pay for a basket the player's way (`Inventory::pay_mode`), hand over the goods and book
+/// the sale with the merchant. Nothing changes if the price can't be met.
+pub fn purchase(
+    inv: &mut Inventory,
+    kind: ShopKind,
+    names: &[String],
+    price_cp: u32,
+) -> Result<Payment, MoneyError> {
+    let payment = inv.pay(Money::from(price_cp), inv.pay_mode)?;
+    for n in names {
+        inv.add_item(n);
+    }
+    record_purchase(inv, kind, names, price_cp);
+    inv.save_after_pickup();
+    Ok(payment)
+}
+
+/// Book a completed purchase: the merchant banks the coin and demand for the items rises.
+pub fn record_purchase(inv: &mut Inventory, kind: ShopKind, names: &[String], paid_cp: u32) {
+    inv.market.take_in(kind, paid_cp);
+    inv.stats.record(StatEvent::ItemsBought(names.len() as u32));
+    for n in names {
+        inv.market.record_purchase(n);
+    }
+}
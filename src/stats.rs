@@ -0,0 +1,228 @@
+use crate::inventory::format_cp;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Something worth counting; fed to `Stats::record` from every action.
+#[derive(Debug, Clone, Copy)]
+pub enum StatEvent<'a> {
+    PickpocketAttempt,
+    PickpocketSuccess,
+    Caught,
+    MysteriousFigure,
+    CoinEarned(u32), // cp
+    CoinLost(u32),   // cp
+    MonsterSlain(&'a str),
+    KnockedOut,
+    Died,
+    Fled,
+    ItemsBought(u32),
+    ItemsSold(u32),
+    TavernTip,
+    ItemCrafted,
+}
+
+/// Lifetime statistics; they survive hardcore deaths and new characters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub pickpockets_attempted: u32,
+    pub pickpockets_succeeded: u32,
+    pub times_caught: u32,
+    pub mysterious_figures: u32,
+    pub coin_earned_cp: u64,
+    pub coin_lost_cp: u64,
+    pub monsters_slain: BTreeMap<String, u32>,
+    pub knockouts: u32,
+    pub deaths: u32,
+    pub fights_fled: u32,
+    pub items_bought: u32,
+    pub items_sold: u32,
+    pub tavern_tips: u32,
+    pub items_crafted: u32,
+    pub achievements: Vec<String>, // ids of unlocked achievements, in unlock order
+    #[serde(skip)]
+    unannounced: Vec<String>,
+}
+
+pub struct Achievement {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    earned: fn(&Stats) -> bool,
+}
+
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "first_blood",
+        title: "First Blood",
+        description: "Slay your first monster.",
+        earned: |s| s.total_slain() >= 1,
+    },
+    Achievement {
+        id: "dragon_slayer",
+        title: "Slay the Dragon Wyrm",
+        description: "Bring down a Dragon Wyrm.",
+        earned: |s| s.slain("Dragon Wyrm") >= 1,
+    },
+    Achievement {
+        id: "monster_hunter",
+        title: "Monster Hunter",
+        description: "Slay 50 monsters.",
+        earned: |s| s.total_slain() >= 50,
+    },
+    Achievement {
+        id: "light_fingers",
+        title: "Light Fingers",
+        description: "Pick 10 pockets successfully.",
+        earned: |s| s.pickpockets_succeeded >= 10,
+    },
+    Achievement {
+        id: "usual_suspect",
+        title: "Usual Suspect",
+        description: "Get caught pickpocketing 5 times.",
+        earned: |s| s.times_caught >= 5,
+    },
+    Achievement {
+        id: "shadow_friend",
+        title: "Friend in the Shadows",
+        description: "Meet the Mysterious Figure 3 times.",
+        earned: |s| s.mysterious_figures >= 3,
+    },
+    Achievement {
+        id: "big_spender",
+        title: "Big Spender",
+        description: "Buy 20 items.",
+        earned: |s| s.items_bought >= 20,
+    },
+    Achievement {
+        id: "fence",
+        title: "Fence",
+        description: "Sell 20 items.",
+        earned: |s| s.items_sold >= 20,
+    },
+    Achievement {
+        id: "regular",
+        title: "Regular",
+        description: "Tip the bartender 10 times.",
+        earned: |s| s.tavern_tips >= 10,
+    },
+    Achievement {
+        id: "artisan",
+        title: "Artisan",
+        description: "Craft 10 items.",
+        earned: |s| s.items_crafted >= 10,
+    },
+    Achievement {
+        id: "discretion",
+        title: "Discretion",
+        description: "Flee from 5 fights.",
+        earned: |s| s.fights_fled >= 5,
+    },
+    Achievement {
+        id: "fortune",
+        title: "Fortune Made",
+        description: "Earn 1000 gp over all your adventures.",
+        earned: |s| s.coin_earned_cp >= 100_000,
+    },
+    Achievement {
+        id: "back_again",
+        title: "Back Again",
+        description: "Die in hardcore mode and start over.",
+        earned: |s| s.deaths >= 1,
+    },
+];
+
+pub fn achievement(id: &str) -> Option<&'static Achievement> {
+    ACHIEVEMENTS.iter().find(|a| a.id == id)
+}
+
+impl Stats {
+    /// Count an event and unlock whatever achievements it completes; unlock notes
+    /// wait in `take_announcements` so every frontend can show them after the action.
+    pub fn record(&mut self, event: StatEvent) {
+        match event {
+            StatEvent::PickpocketAttempt => self.pickpockets_attempted += 1,
+            StatEvent::PickpocketSuccess => self.pickpockets_succeeded += 1,
+            StatEvent::Caught => self.times_caught += 1,
+            StatEvent::MysteriousFigure => self.mysterious_figures += 1,
+            StatEvent::CoinEarned(cp) => self.coin_earned_cp += cp as u64,
+            StatEvent::CoinLost(cp) => self.coin_lost_cp += cp as u64,
+            StatEvent::MonsterSlain(name) => {
+                *self.monsters_slain.entry(name.to_string()).or_default() += 1
+            }
+            StatEvent::KnockedOut => self.knockouts += 1,
+            StatEvent::Died => self.deaths += 1,
+            StatEvent::Fled => self.fights_fled += 1,
+            StatEvent::ItemsBought(n) => self.items_bought += n,
+            StatEvent::ItemsSold(n) => self.items_sold += n,
+            StatEvent::TavernTip => self.tavern_tips += 1,
+            StatEvent::ItemCrafted => self.items_crafted += 1,
+        }
+        for a in ACHIEVEMENTS {
+            if !self.has(a.id) && (a.earned)(self) {
+                self.achievements.push(a.id.to_string());
+                self.unannounced.push(format!(
+                    "🏅 Achievement unlocked: {} — {}",
+                    a.title, a.description
+                ));
+            }
+        }
+    }
+
+    /// Unlock notes not shown yet.
+    pub fn take_announcements(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.unannounced)
+    }
+
+    pub fn has(&self, id: &str) -> bool {
+        self.achievements.iter().any(|a| a == id)
+    }
+
+    pub fn slain(&self, monster: &str) -> u32 {
+        self.monsters_slain.get(monster).copied().unwrap_or(0)
+    }
+
+    pub fn total_slain(&self) -> u32 {
+        self.monsters_slain.values().sum()
+    }
+
+    /// Human-readable summary for the CLI.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "Pickpockets: {} attempted, {} succeeded, caught {} time(s)",
+                self.pickpockets_attempted, self.pickpockets_succeeded, self.times_caught
+            ),
+            format!("Mysterious Figure met: {}", self.mysterious_figures),
+            format!(
+                "Coin earned: {} — coin lost: {}",
+                format_cp(self.coin_earned_cp.min(u32::MAX as u64) as u32),
+                format_cp(self.coin_lost_cp.min(u32::MAX as u64) as u32)
+            ),
+            format!(
+                "Monsters slain: {} — fled {} fight(s), knocked out {} time(s), died {} time(s)",
+                self.total_slain(),
+                self.fights_fled,
+                self.knockouts,
+                self.deaths
+            ),
+        ];
+        for (name, n) in &self.monsters_slain {
+            lines.push(format!("  {} × {}", n, name));
+        }
+        lines.push(format!(
+            "Items bought: {} — sold: {} — crafted: {} — tavern tips: {}",
+            self.items_bought, self.items_sold, self.items_crafted, self.tavern_tips
+        ));
+        lines.push(format!(
+            "Achievements ({}/{}):",
+            self.achievements.len(),
+            ACHIEVEMENTS.len()
+        ));
+        for a in ACHIEVEMENTS {
+            let mark = if self.has(a.id) { "🏅" } else { "  ·" };
+            lines.push(format!("{} {} — {}", mark, a.title, a.description));
+        }
+        lines
+    }
+}
@@ -0,0 +1,671 @@
+//! Full-screen terminal front end (`--tui`, built with the `tui` feature).
+//!
+//! Mirrors the web UI's two panels: character, purse and pack on the left; the current
+//! pane (town, shop, tavern or fight) and a scrolling event log on the right. Every
+//! action goes through the same core functions the CLI and web builds use.
+use crate::actions::{TavernOrder, pickpocket_attempt, tavern_order};
+use crate::appraisal;
+use crate::clock::{APPRAISAL_MINUTES, FIGHT_ROUND_MINUTES, SHOP_VISIT_MINUTES, TAVERN_OPEN_HOUR};
+use crate::combat::{self, Encounter, Fall, FightStatus};
+use crate::difficulty;
+use crate::encumbrance;
+use crate::inventory::{Inventory, format_cp};
+use crate::loot::format_items_for_display;
+use crate::shop::{self, ShopKind, StockItem};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+
+// Oldest entries are dropped once the log grows past this
+const LOG_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Town,
+    Shop,
+    Tavern,
+    Fight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShopMode {
+    Buy,
+    Sell,
+}
+
+/// Everything the terminal UI shows, plus the world it drives. Key handling is kept
+/// apart from the terminal so it can be exercised without one.
+pub struct App {
+    pub inv: Inventory,
+    loot: Vec<String>,
+    pub pane: Pane,
+    pub log: Vec<String>,
+    pub cursor: usize,
+    pub shop_kind: ShopKind,
+    pub shop_mode: ShopMode,
+    pub stock: Vec<StockItem>,
+    offers: Vec<Option<u32>>,
+    short_sale: Option<usize>, // sale the merchant can't fully pay for, awaiting a second Enter
+    pub fight: Option<Encounter>,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(inv: Inventory, loot: Vec<String>) -> App {
+        App {
+            inv,
+            loot,
+            pane: Pane::Town,
+            log: vec!["Welcome back. Keys are listed along the bottom.".into()],
+            cursor: 0,
+            shop_kind: ShopKind::General,
+            shop_mode: ShopMode::Buy,
+            stock: Vec::new(),
+            offers: Vec::new(),
+            short_sale: None,
+            fight: None,
+            quit: false,
+        }
+    }
+
+    fn say(&mut self, line: impl Into<String>) {
+        self.log.push(line.into());
+        if self.log.len() > LOG_LIMIT {
+            let extra = self.log.len() - LOG_LIMIT;
+            self.log.drain(..extra);
+        }
+    }
+
+    fn say_all(&mut self, lines: impl IntoIterator<Item = String>) {
+        for l in lines {
+            self.say(l);
+        }
+    }
+
+    // Let world time pass, logging whatever it has to say
+    fn spend(&mut self, minutes: u64) {
+        let notes = self.inv.advance_time(minutes);
+        self.say_all(notes);
+    }
+
+    // Rows the cursor can move over in the current pane
+    fn rows(&self) -> usize {
+        match self.pane {
+            Pane::Town => 0,
+            Pane::Shop => match self.shop_mode {
+                ShopMode::Buy => self.stock.len(),
+                ShopMode::Sell => self.inv.items.len(),
+            },
+            Pane::Tavern => TavernOrder::ALL.len(),
+            Pane::Fight => self.fight.as_ref().map_or(0, |e| e.living().len()),
+        }
+    }
+
+    fn go(&mut self, pane: Pane) {
+        self.pane = pane;
+        self.cursor = 0;
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        if key != KeyCode::Enter {
+            self.short_sale = None;
+        }
+        match key {
+            KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down => {
+                if self.cursor + 1 < self.rows() {
+                    self.cursor += 1;
+                }
+            }
+            _ => match self.pane {
+                Pane::Town => self.town_key(key),
+                Pane::Shop => self.shop_key(key),
+                Pane::Tavern => self.tavern_key(key),
+                Pane::Fight => self.fight_key(key),
+            },
+        }
+        let notes = self.inv.stats.take_announcements();
+        self.say_all(notes);
+    }
+
+    fn help(&self) -> &'static str {
+        match self.pane {
+            Pane::Town => "[P]ickpocket  [S]hop  [T]avern  [F]ight  [Q]uit",
+            Pane::Shop => {
+                "←/→ shop  Tab buy/sell  ↑/↓ pick  Enter buy or sell  [A]ppraise  Esc leave"
+            }
+            Pane::Tavern => "↑/↓ pick  Enter order  Esc leave",
+            Pane::Fight => "↑/↓ target  [A]ttack  [D]efend  [F]lee  Esc withdraw",
+        }
+    }
+
+    // --- Town ---
+    fn town_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('p') => {
+                let report = pickpocket_attempt(&mut self.inv, &self.loot);
+                self.say(format!("— {} —", report.title));
+                self.say_all(report.lines);
+            }
+            KeyCode::Char('s') => self.enter_shop(self.shop_kind),
+            KeyCode::Char('t') => {
+                if self.inv.clock.tavern_open() {
+                    self.say(format!(
+                        "🍺 You enter the tavern. The room is {}.",
+                        self.inv.clock.tavern_crowd().label().to_lowercase()
+                    ));
+                    self.go(Pane::Tavern);
+                } else {
+                    self.say(format!(
+                        "🚪 The tavern is shuttered until {:02}:00.",
+                        TAVERN_OPEN_HOUR
+                    ));
+                }
+            }
+            KeyCode::Char('f') => self.start_fight(),
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            _ => {}
+        }
+    }
+
+    // --- Shops ---
+    fn enter_shop(&mut self, kind: ShopKind) {
+        self.spend(SHOP_VISIT_MINUTES);
+        self.shop_kind = kind;
+        self.stock = shop::generate_stock(kind, &self.inv.market);
+        self.offers = shop::sell_offers(kind, &self.inv);
+        self.shop_mode = ShopMode::Buy;
+        self.say(kind.catalog().greeting.clone());
+        self.go(Pane::Shop);
+    }
+
+    fn next_shop(&mut self, step: isize) {
+        let all = ShopKind::ALL;
+        let at = all.iter().position(|k| *k == self.shop_kind).unwrap_or(0) as isize;
+        let next = (at + step).rem_euclid(all.len() as isize) as usize;
+        self.enter_shop(all[next]);
+    }
+
+    fn shop_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Left => self.next_shop(-1),
+            KeyCode::Right => self.next_shop(1),
+            KeyCode::Tab => {
+                self.shop_mode = match self.shop_mode {
+                    ShopMode::Buy => ShopMode::Sell,
+                    ShopMode::Sell => ShopMode::Buy,
+                };
+                self.cursor = 0;
+            }
+            KeyCode::Enter => match self.shop_mode {
+                ShopMode::Buy => self.buy_selected(),
+                ShopMode::Sell => self.sell_selected(),
+            },
+            KeyCode::Char('a') if self.shop_mode == ShopMode::Sell => self.appraise_selected(),
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.say("You leave the shop.");
+                self.go(Pane::Town);
+            }
+            _ => {}
+        }
+    }
+
+    fn buy_selected(&mut self) {
+        let Some(item) = self.stock.get(self.cursor).cloned() else {
+            return;
+        };
+        let names = [item.name.clone()];
+        match shop::purchase(&mut self.inv, self.shop_kind, &names, item.price_cp) {
+            Ok(_) => {
+                self.stock.remove(self.cursor);
+                self.cursor = self.cursor.min(self.stock.len().saturating_sub(1));
+                self.offers = shop::sell_offers(self.shop_kind, &self.inv);
+                self.say(format!(
+                    "🛒 Bought {} for {}.",
+                    item.name,
+                    format_cp(item.price_cp)
+                ));
+            }
+            Err(e) => self.say(format!("{}.", e.message())),
+        }
+    }
+
+    fn sell_selected(&mut self) {
+        let idx = self.cursor;
+        if self.offers.len() != self.inv.items.len() {
+            self.offers = shop::sell_offers(self.shop_kind, &self.inv);
+        }
+        if self.offers.get(idx).copied().flatten().is_none() {
+            if idx < self.inv.items.len() {
+                self.say("🤨 The shopkeeper won't touch that. Try a fence.");
+            }
+            return;
+        }
+        let asked = self.offers[idx].unwrap_or(0);
+        let spare = shop::sale_total_cp(self.shop_kind, &self.inv, &[idx], &self.offers);
+        if spare < asked && self.short_sale != Some(idx) {
+            self.short_sale = Some(idx);
+            self.say(format!(
+                "👛 The merchant only has {} to spare (you asked {}). Enter again to sell anyway.",
+                format_cp(spare),
+                format_cp(asked)
+            ));
+            return;
+        }
+        self.short_sale = None;
+        let (sold, paid) = shop::sell_selected(&mut self.inv, self.shop_kind, &[idx], &self.offers);
+        self.offers = shop::sell_offers(self.shop_kind, &self.inv);
+        self.cursor = self.cursor.min(self.inv.items.len().saturating_sub(1));
+        self.inv.save_after_pickup();
+        self.say(format!(
+            "💰 Sold {} for {}.",
+            format_items_for_display(&sold),
+            format_cp(paid)
+        ));
+    }
+
+    fn appraise_selected(&mut self) {
+        match appraisal::appraise_at_shop(&mut self.inv, self.shop_kind, self.cursor) {
+            Ok(found) => {
+                self.spend(APPRAISAL_MINUTES);
+                self.offers = shop::sell_offers(self.shop_kind, &self.inv);
+                self.say(format!("🔍 {}.", found.summary()));
+            }
+            Err(e) => self.say(format!("{}.", e.message())),
+        }
+    }
+
+    // --- Tavern ---
+    fn tavern_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Enter => {
+                let order = TavernOrder::ALL[self.cursor.min(TavernOrder::ALL.len() - 1)];
+                let lines = tavern_order(&mut self.inv, order);
+                self.say_all(lines);
+                if !self.inv.clock.tavern_open() {
+                    self.say("The barkeep blows out the lamps and shows you the door.");
+                    self.go(Pane::Town);
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.say("You leave the tavern.");
+                self.go(Pane::Town);
+            }
+            _ => {}
+        }
+    }
+
+    // --- Fights ---
+    fn start_fight(&mut self) {
+        combat::prepare_player(&mut self.inv);
+        let tod = self.inv.clock.time_of_day();
+        let enc = Encounter::new(difficulty::scaled_encounter(&self.inv, tod));
+        self.say(format!("⚔️  You face {}!", enc.title()));
+        self.fight = Some(enc);
+        self.go(Pane::Fight);
+    }
+
+    fn fight_key(&mut self, key: KeyCode) {
+        let Some(mut enc) = self.fight.take() else {
+            self.go(Pane::Town);
+            return;
+        };
+        let (lines, status) = match key {
+            KeyCode::Char('a') | KeyCode::Enter => {
+                let target = enc.living().get(self.cursor).copied();
+                self.spend(FIGHT_ROUND_MINUTES);
+                enc.round(&mut self.inv, target)
+            }
+            KeyCode::Char('d') => {
+                self.spend(FIGHT_ROUND_MINUTES);
+                enc.defend(&mut self.inv)
+            }
+            KeyCode::Char('f') => {
+                if let Some(why) = encumbrance::flee_blocked(&self.inv) {
+                    self.say(why);
+                    self.fight = Some(enc);
+                    return;
+                }
+                self.spend(FIGHT_ROUND_MINUTES);
+                let (gold, items) = combat::settle_flee(&mut self.inv);
+                self.say(format!(
+                    "🏃 You flee, dropping {} gp and {} item(s).",
+                    gold,
+                    items.len()
+                ));
+                self.go(Pane::Town);
+                return;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                if let Some(why) = encumbrance::flee_blocked(&self.inv) {
+                    self.say(why);
+                    self.fight = Some(enc);
+                    return;
+                }
+                self.say("You withdraw from the battle.");
+                self.go(Pane::Town);
+                return;
+            }
+            _ => {
+                self.fight = Some(enc);
+                return;
+            }
+        };
+        self.say_all(lines);
+        match status {
+            FightStatus::Ongoing => {
+                self.cursor = self.cursor.min(enc.living().len().saturating_sub(1));
+                self.fight = Some(enc);
+            }
+            FightStatus::Victory => {
+                let spoils = combat::settle_victory(&mut self.inv, &enc);
+                self.say(format!(
+                    "🏆 You defeat {} and gain {} gp.",
+                    enc.title(),
+                    spoils.gold
+                ));
+                if !spoils.drops.is_empty() {
+                    self.say(format!(
+                        "Drops: {}",
+                        format_items_for_display(&spoils.drops)
+                    ));
+                }
+                self.say_all(spoils.notes);
+                self.go(Pane::Town);
+            }
+            FightStatus::Escaped => {
+                self.say(format!("💨 {} got away: no spoils.", enc.title()));
+                self.go(Pane::Town);
+            }
+            FightStatus::Defeat => {
+                match combat::settle_fall(&mut self.inv, &enc) {
+                    Fall::KnockedOut { loss, removed } => self.say(format!(
+                        "💀 Defeated by {}. Lost {} gp and {} item(s).",
+                        enc.title(),
+                        loss,
+                        removed.len()
+                    )),
+                    Fall::Perished(grave) => {
+                        self.say(grave.epitaph());
+                        self.say("A new adventurer steps forward...");
+                    }
+                }
+                self.go(Pane::Town);
+            }
+        }
+    }
+
+    // --- Drawing ---
+    pub fn draw(&self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(10), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(38), Constraint::Percentage(62)])
+                .areas(main);
+        self.draw_character(frame, left);
+        let [pane, log] =
+            Layout::vertical([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(right);
+        match self.pane {
+            Pane::Town => self.draw_town(frame, pane),
+            Pane::Shop => self.draw_shop(frame, pane),
+            Pane::Tavern => self.draw_tavern(frame, pane),
+            Pane::Fight => self.draw_fight(frame, pane),
+        }
+        self.draw_log(frame, log);
+        frame.render_widget(
+            Paragraph::new(self.help()).style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn draw_character(&self, frame: &mut Frame, area: Rect) {
+        let inv = &self.inv;
+        let [info, hp, pack] = Layout::vertical([
+            Constraint::Length(7),
+            Constraint::Length(3),
+            Constraint::Min(3),
+        ])
+        .areas(area);
+        let title = inv
+            .character
+            .as_ref()
+            .map_or_else(|| "Nameless rogue".to_string(), |c| c.title());
+        let mut coins = format!(
+            "💰 {} gp  {} sp  {} cp",
+            inv.gold_pieces, inv.silver_pieces, inv.copper_pieces
+        );
+        for (code, n) in &inv.coins {
+            coins.push_str(&format!("  {} {}", n, code));
+        }
+        let lines = vec![
+            Line::from(title),
+            Line::from(format!("🕰️  {}", inv.clock.label())),
+            Line::from(format!(
+                "⭐ Level {} ({} XP), {}",
+                inv.level(),
+                inv.experience,
+                inv.difficulty.label()
+            )),
+            Line::from(coins),
+            Line::from(if inv.luck_boost {
+                "🍀 Luck stored".to_string()
+            } else {
+                "🍀 No luck stored".to_string()
+            }),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" HERO ")),
+            info,
+        );
+        let ratio = if inv.max_hp == 0 {
+            0.0
+        } else {
+            (inv.current_hp as f64 / inv.max_hp as f64).clamp(0.0, 1.0)
+        };
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::default().borders(Borders::ALL).title(" HP "))
+                .gauge_style(Style::default().fg(Color::Red))
+                .ratio(ratio)
+                .label(format!("{}/{}", inv.current_hp, inv.max_hp)),
+            hp,
+        );
+        let mut rows: Vec<ListItem> = inv
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, i)| {
+                let mut label = i.clone();
+                if inv.is_stolen_at(idx) {
+                    label.push_str(" [hot]");
+                }
+                if appraisal::is_unidentified(i) {
+                    label.push_str(" ?");
+                }
+                ListItem::new(label)
+            })
+            .collect();
+        for c in &inv.party.companions {
+            rows.push(ListItem::new(format!(
+                "🤝 {} ({})",
+                c.title(),
+                c.status_label()
+            )));
+        }
+        let title = format!(" INVENTORY — {} ", encumbrance::load(inv).label());
+        frame.render_widget(
+            List::new(rows).block(Block::default().borders(Borders::ALL).title(title)),
+            pack,
+        );
+    }
+
+    fn draw_town(&self, frame: &mut Frame, area: Rect) {
+        let lines = vec![
+            Line::from("Market Street hums around you."),
+            Line::from(""),
+            Line::from("[P] Pickpocket a passer-by"),
+            Line::from("[S] Visit the shops"),
+            Line::from("[T] Step into the tavern"),
+            Line::from("[F] Pick a fight"),
+            Line::from("[Q] Save and quit"),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(" TOWN ")),
+            area,
+        );
+    }
+
+    fn selectable(
+        &self,
+        rows: Vec<ListItem<'static>>,
+        title: String,
+    ) -> (List<'static>, ListState) {
+        let list = List::new(rows)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("▶ ");
+        let mut state = ListState::default();
+        state.select(Some(self.cursor));
+        (list, state)
+    }
+
+    fn draw_shop(&self, frame: &mut Frame, area: Rect) {
+        let [tabs, body] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(3)]).areas(area);
+        let labels: Vec<&str> = ShopKind::ALL.iter().map(|k| k.label()).collect();
+        let at = ShopKind::ALL
+            .iter()
+            .position(|k| *k == self.shop_kind)
+            .unwrap_or(0);
+        frame.render_widget(
+            Tabs::new(labels)
+                .select(at)
+                .block(Block::default().borders(Borders::ALL).title(" SHOPS "))
+                .highlight_style(Style::default().add_modifier(Modifier::BOLD)),
+            tabs,
+        );
+        let purse = format_cp(self.inv.market.purse_cp(self.shop_kind));
+        let (rows, title): (Vec<ListItem>, String) = match self.shop_mode {
+            ShopMode::Buy => (
+                self.stock
+                    .iter()
+                    .map(|s| {
+                        ListItem::new(format!(
+                            "{} ({}) — {}",
+                            s.name,
+                            s.rarity.label(),
+                            format_cp(s.price_cp)
+                        ))
+                    })
+                    .collect(),
+                format!(" BUY — merchant purse {} ", purse),
+            ),
+            ShopMode::Sell => (
+                self.inv
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| {
+                        let offer = match self.offers.get(i).copied().flatten() {
+                            Some(cp) => format_cp(cp),
+                            None => "refused".into(),
+                        };
+                        ListItem::new(format!("{} — {}", name, offer))
+                    })
+                    .collect(),
+                match self.shop_kind.catalog().appraisal_fee_cp {
+                    Some(fee) => format!(" SELL — appraisal {} ", format_cp(fee)),
+                    None => format!(" SELL — merchant purse {} ", purse),
+                },
+            ),
+        };
+        let (list, mut state) = self.selectable(rows, title);
+        frame.render_stateful_widget(list, body, &mut state);
+    }
+
+    fn draw_tavern(&self, frame: &mut Frame, area: Rect) {
+        let rows = TavernOrder::ALL
+            .iter()
+            .map(|o| ListItem::new(o.label()))
+            .collect();
+        let title = format!(
+            " TAVERN — {} ",
+            self.inv.clock.tavern_crowd().label().to_lowercase()
+        );
+        let (list, mut state) = self.selectable(rows, title);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_fight(&self, frame: &mut Frame, area: Rect) {
+        let Some(enc) = &self.fight else {
+            return;
+        };
+        let mut rows: Vec<ListItem> = enc
+            .living()
+            .iter()
+            .map(|&i| {
+                let f = &enc.foes[i];
+                ListItem::new(format!(
+                    "{} — {}/{} HP",
+                    f.monster.name,
+                    f.hp,
+                    f.monster.max_hp()
+                ))
+            })
+            .collect();
+        for c in &self.inv.party.companions {
+            rows.push(ListItem::new(format!("🤝 {} — {} HP", c.name, c.hp)));
+        }
+        let title = format!(" FIGHT — {} ", enc.title());
+        let (list, mut state) = self.selectable(rows, title);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn draw_log(&self, frame: &mut Frame, area: Rect) {
+        let height = area.height.saturating_sub(2) as usize;
+        let start = self.log.len().saturating_sub(height);
+        let lines: Vec<Line> = self.log[start..]
+            .iter()
+            .map(|l| Line::from(l.as_str()))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL).title(" LOG ")),
+            area,
+        );
+    }
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> io::Result<()> {
+    while !app.quit {
+        terminal.draw(|f| app.draw(f))?;
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            let code = match key.code {
+                KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+                other => other,
+            };
+            app.handle_key(code);
+        }
+    }
+    Ok(())
+}
+
+/// Take over the terminal until the player quits. The inventory is handed back for saving
+/// even when the terminal fails, so a broken screen never costs the game in progress.
+pub fn run(inv: Inventory, loot: Vec<String>) -> (Inventory, io::Result<()>) {
+    let mut terminal = match ratatui::try_init() {
+        Ok(t) => t,
+        Err(e) => return (inv, Err(e)),
+    };
+    let mut app = App::new(inv, loot);
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    (app.inv, result)
+}
@@ -1,4 +1,6 @@
+use crate::difficulty::Difficulty;
 use crate::inventory::Inventory;
+use crate::stats::Stats;
 
 #[derive(Debug, Clone, Copy)]
 pub enum MainAction {
@@ -6,14 +8,36 @@ pub enum MainAction {
     Inventory,
     Shop,
     Fight,
+    Dungeon,
+    Map,
     Tavern,
+    Bank,
+    Craft,
+    Stats,
+    Trade,   // hot seat only
+    EndTurn, // hot seat only
     Exit,
 }
 
 pub fn prompt_main_action() -> MainAction {
+    prompt_action(false)
+}
+
+/// The main menu for the player on turn at a hot-seat table: adds trading and passing
+/// the keyboard on.
+pub fn prompt_turn_action() -> MainAction {
+    prompt_action(true)
+}
+
+fn prompt_action(hot_seat: bool) -> MainAction {
     use std::io::{self, Write};
     println!("\n===== Actions =====");
-    println!("[P]ickpocket  [I]nventory  [S]hop  [F]ight  [T]avern  E[x]it / [Q]uit");
+    println!(
+        "[P]ickpocket  [I]nventory  [S]hop  [F]ight  [D]ungeon  [M]ap  [T]avern  [B]ank  [C]raft  [A]chievements  E[x]it / [Q]uit"
+    );
+    if hot_seat {
+        println!("T[r]ade with another player  [N]ext player");
+    }
     print!("Enter choice: ");
     let _ = io::stdout().flush();
     let mut line = String::new();
@@ -29,19 +53,73 @@ pub fn prompt_main_action() -> MainAction {
         'i' => MainAction::Inventory,
         's' => MainAction::Shop,
         'f' => MainAction::Fight,
+        'd' => MainAction::Dungeon,
+        'm' => MainAction::Map,
         't' => MainAction::Tavern,
+        'b' => MainAction::Bank,
+        'c' => MainAction::Craft,
+        'a' => MainAction::Stats,
+        'r' if hot_seat => MainAction::Trade,
+        'n' if hot_seat => MainAction::EndTurn,
         'x' | 'q' | 'e' => MainAction::Exit,
         other => {
-            println!("Unrecognized option '{}'. (P/I/S/F/T/Q)", other);
+            let keys = if hot_seat {
+                "P/I/S/F/D/M/T/B/A/R/N/Q"
+            } else {
+                "P/I/S/F/D/M/T/B/A/Q"
+            };
+            println!("Unrecognized option '{}'. ({})", other, keys);
             MainAction::PickPocket
         }
     }
 }
 
+/// Asked once, when a new character is created. Empty input keeps Normal.
+pub fn prompt_difficulty() -> Difficulty {
+    use std::io::{self, Write};
+    println!("\n===== New Character =====");
+    println!("Choose a difficulty: [E]asy  [N]ormal  [H]ard");
+    println!("  Easy: weaker foes, lighter losses, smaller purses.");
+    println!("  Hard: tougher foes, heavier losses, richer rewards.");
+    loop {
+        print!("Difficulty (default Normal): ");
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).is_err() || line.trim().is_empty() {
+            return Difficulty::Normal;
+        }
+        match Difficulty::parse(&line) {
+            Some(d) => return d,
+            None => println!("Unrecognized difficulty '{}'. (E/N/H)", line.trim()),
+        }
+    }
+}
+
+/// Asked once, when a new character is created. Anything but yes keeps a normal run.
+pub fn prompt_hardcore() -> bool {
+    use std::io::{self, Write};
+    println!("Hardcore mode: falling in battle ends the run and sends you to the graveyard.");
+    print!("Play hardcore? [y/N]: ");
+    let _ = io::stdout().flush();
+    let mut line = String::new();
+    if io::stdin().read_line(&mut line).is_err() {
+        return false;
+    }
+    matches!(line.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
 pub fn print_simple_header(title: &str) {
     println!("\n──── {} ────", title);
 }
 
+/// Lifetime stats and the achievement list (main menu and `dungeon stats`).
+pub fn print_stats(stats: &Stats) {
+    print_simple_header("Lifetime Stats");
+    for line in stats.lines() {
+        println!("{}", line);
+    }
+}
+
 pub fn print_event_summary(
     title: &str,
     before: &Inventory,
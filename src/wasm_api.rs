@@ -1,12 +1,30 @@
 use crate::{
-    actions::{
-        HAGGLE_SUCCESS_CHANCE, Rarity, TAVERN_DRINK_COST_SP, TAVERN_FLIRT_COST_GP,
-        TAVERN_FLIRT_KISS_CHANCE, TAVERN_FOOD_COST_SP, TAVERN_LUCK_CHANCE, TAVERN_STAY_COST_GP,
-        TAVERN_TIP_COST_GP, fight_monster_outcome, pick_pocket,
-    },
+    actions::{TavernOrder, fight_monster_outcome, pickpocket_attempt, tavern_order},
     apply_pickpocket_penalty,
+    appraisal::{self, Appraisal, AppraisalError},
+    bank,
+    character::{self, Background, Character, CharacterClass},
+    clock::{
+        APPRAISAL_MINUTES, BANK_CLOSE_HOUR, BANK_OPEN_HOUR, BANK_VISIT_MINUTES,
+        FIGHT_ROUND_MINUTES, SHOP_VISIT_MINUTES, TAVERN_GAMBLE_MINUTES, TAVERN_HIRE_MINUTES,
+        TAVERN_OPEN_HOUR,
+    },
+    combat::{self, Encounter, Fall, FightStatus},
+    companion::{self, Companion, CompanionKind},
+    crafting::{self, CraftError, Crafted},
+    currency,
+    difficulty::{self, Difficulty},
+    dungeon::{self, Door, RoomContent},
+    encumbrance,
+    gamble::{DiceWager, play_rune_dice},
+    graveyard::Grave,
+    haggle::{HaggleContext, HaggleStep, Negotiation},
     inventory::Inventory,
-    loot::parse_and_format_loot_cached,
+    loot::{format_items_for_display, parse_and_format_loot_cached},
+    money::PayMode,
+    quest,
+    shop::{self, ShopKind},
+    stats::ACHIEVEMENTS,
 };
 use rand::Rng;
 use rand::SeedableRng;
@@ -19,9 +37,185 @@ pub struct WasmInventory {
     pub gp: u32,
     pub sp: u32,
     pub cp: u32,
+    #[serde(default)]
+    pub coins: Vec<CoinStack>, // platinum, electrum and any other non-cp/sp/gp coins
     pub luck: bool,
     pub max_hp: u32,
     pub current_hp: u32,
+    #[serde(default)]
+    pub notoriety: u32,
+    #[serde(default)]
+    pub stolen: Vec<bool>, // index-aligned with `items`
+    // World clock (read-only view; not restored from JS)
+    #[serde(default)]
+    pub clock: String,
+    #[serde(default)]
+    pub day: u64,
+    #[serde(default)]
+    pub hour: u32,
+    #[serde(default)]
+    pub minute: u32,
+    #[serde(default)]
+    pub time_of_day: String,
+    #[serde(default)]
+    pub tavern_crowd: String,
+    #[serde(default)]
+    pub difficulty: String,
+    #[serde(default)]
+    pub level: u32,
+    #[serde(default)]
+    pub experience: u32,
+    #[serde(default)]
+    pub hardcore: bool,
+    // Pay shops, the tavern and companions with exact coins only
+    #[serde(default)]
+    pub exact_change: bool,
+    // Character sheet (empty for a classless character)
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub class: String,
+    #[serde(default)]
+    pub background: String,
+    // Encumbrance (read-only view)
+    #[serde(default)]
+    pub load_lb: f64,
+    #[serde(default)]
+    pub capacity_lb: f64,
+    #[serde(default)]
+    pub overloaded: bool,
+    // Hired companions (read-only view)
+    #[serde(default)]
+    pub party: Vec<CompanionView>,
+    // Indices of items still awaiting appraisal (read-only view)
+    #[serde(default)]
+    pub unidentified: Vec<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CompanionView {
+    pub name: String,
+    pub kind: String,
+    pub hp: u32,
+    pub max_hp: u32,
+    pub morale: u32,
+    pub daily_wage_cp: u32,
+    pub owed_cp: u32,
+}
+
+impl From<&Companion> for CompanionView {
+    fn from(c: &Companion) -> Self {
+        Self {
+            name: c.name.clone(),
+            kind: c.kind.label().to_string(),
+            hp: c.hp,
+            max_hp: c.kind.max_hp(),
+            morale: c.morale,
+            daily_wage_cp: c.kind.daily_wage_cp(),
+            owed_cp: c.owed_cp,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HireOption {
+    pub id: String,
+    pub label: String,
+    pub description: String,
+    pub daily_wage_cp: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecipeView {
+    pub id: String,
+    pub known: bool,
+    pub makes: Option<String>,   // None until discovered
+    pub formula: Option<String>, // None until discovered
+    pub hint: String,
+    pub craftable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WasmCraftState {
+    pub state: WasmInventory,
+    pub message: String,
+    pub recipes: Vec<RecipeView>,
+    pub discovered: u32,
+    pub total: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WasmPartyState {
+    pub state: WasmInventory,
+    pub message: String,
+    pub companions: Vec<CompanionView>,
+    pub hires: Vec<HireOption>,
+    pub max_size: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CharacterOption {
+    pub id: String,
+    pub label: String,
+    pub description: String,
+    pub kit: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CharacterOptions {
+    pub classes: Vec<CharacterOption>,
+    pub backgrounds: Vec<CharacterOption>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WasmBankState {
+    pub state: WasmInventory,
+    pub message: String,
+    pub open: bool,
+    pub balance_cp: u32,
+    pub fees_owed_cp: u32,
+    pub daily_fee_cp: u32,
+    pub vault: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SlainCount {
+    pub monster: String,
+    pub count: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AchievementView {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub unlocked: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WasmStats {
+    pub pickpockets_attempted: u32,
+    pub pickpockets_succeeded: u32,
+    pub times_caught: u32,
+    pub mysterious_figures: u32,
+    pub coin_earned_cp: u64,
+    pub coin_lost_cp: u64,
+    pub monsters_slain: Vec<SlainCount>,
+    pub knockouts: u32,
+    pub deaths: u32,
+    pub fights_fled: u32,
+    pub items_bought: u32,
+    pub items_sold: u32,
+    pub tavern_tips: u32,
+    pub achievements: Vec<AchievementView>,
+    pub new_achievements: Vec<String>, // unlock notes not shown yet
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CoinStack {
+    pub code: String,
+    pub name: String,
+    pub amount: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -42,18 +236,107 @@ pub struct ShopItem {
 pub struct ShopState {
     pub items: Vec<ShopItem>,
     pub haggle_applied: bool,
+    pub kind: String,
+    pub name: String,
+    pub greeting: String,
+    pub purse_cp: u32,
+    pub appraisal_fee_cp: Option<u32>, // null when this shop doesn't appraise
+    pub sell_offers: Vec<SellOffer>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SaleQuote {
+    pub asked_cp: u32,
+    pub paid_cp: u32,
+}
+
+// Sum of the offers for a selection, before the merchant's purse caps it
+fn listed_total_cp(selected: &[usize], offers: &[Option<u32>]) -> u32 {
+    selected
+        .iter()
+        .filter_map(|&i| offers.get(i).copied().flatten())
+        .fold(0u32, |acc, p| acc.saturating_add(p))
+}
+
+// A rolled offer and the carried item it was rolled for
+#[derive(Clone)]
+struct SellQuote {
+    item: String,
+    stolen: bool,
+    offer_cp: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SellOffer {
+    pub index: u32,
+    pub name: String,
+    pub offer_cp: Option<u32>,
+    pub stolen: bool,
+    pub unidentified: bool,
 }
 
 impl From<Inventory> for WasmInventory {
     fn from(i: Inventory) -> Self {
+        let clock = i.clock;
+        let level = i.level();
+        let load = encumbrance::load(&i);
+        let stolen = (0..i.items.len()).map(|idx| i.is_stolen_at(idx)).collect();
+        let unidentified = appraisal::unidentified_indices(&i)
+            .into_iter()
+            .map(|idx| idx as u32)
+            .collect();
         Self {
             items: i.items,
             gp: i.gold_pieces,
             sp: i.silver_pieces,
             cp: i.copper_pieces,
+            coins: i
+                .coins
+                .iter()
+                .map(|(code, &amount)| CoinStack {
+                    code: code.clone(),
+                    name: currency::active()
+                        .get(code)
+                        .map_or_else(|| code.clone(), |d| d.plural.clone()),
+                    amount,
+                })
+                .collect(),
             luck: i.luck_boost,
             max_hp: i.max_hp,
             current_hp: i.current_hp,
+            notoriety: i.notoriety,
+            stolen,
+            clock: clock.label(),
+            day: clock.day(),
+            hour: clock.hour(),
+            minute: clock.minute(),
+            time_of_day: clock.time_of_day().label().to_string(),
+            tavern_crowd: clock.tavern_crowd().label().to_string(),
+            difficulty: i.difficulty.label().to_string(),
+            level,
+            experience: i.experience,
+            hardcore: i.hardcore,
+            exact_change: i.pay_mode == PayMode::ExactChange,
+            name: i
+                .character
+                .as_ref()
+                .map(|c| c.name.clone())
+                .unwrap_or_default(),
+            class: i
+                .character
+                .as_ref()
+                .map(|c| c.class.label().to_string())
+                .unwrap_or_default(),
+            background: i
+                .character
+                .as_ref()
+                .map(|c| c.background.label().to_string())
+                .unwrap_or_default(),
+            load_lb: load.carried_lb,
+            capacity_lb: load.capacity_lb,
+            overloaded: load.overloaded(),
+            party: i.party.companions.iter().map(CompanionView::from).collect(),
+            unidentified,
         }
     }
 }
@@ -65,6 +348,7 @@ impl From<WasmInventory> for Inventory {
             gold_pieces: w.gp,
             silver_pieces: w.sp,
             copper_pieces: w.cp,
+            coins: w.coins.into_iter().map(|c| (c.code, c.amount)).collect(),
             luck_boost: w.luck,
             max_hp: if w.max_hp == 0 { 20 } else { w.max_hp },
             current_hp: if w.current_hp == 0 {
@@ -72,6 +356,29 @@ impl From<WasmInventory> for Inventory {
             } else {
                 w.current_hp.min(w.max_hp.max(20))
             },
+            notoriety: w.notoriety,
+            stolen: w.stolen,
+            difficulty: Difficulty::parse(&w.difficulty).unwrap_or_default(),
+            experience: w.experience,
+            hardcore: w.hardcore,
+            pay_mode: if w.exact_change {
+                PayMode::ExactChange
+            } else {
+                PayMode::MakeChange
+            },
+            character: match (
+                CharacterClass::parse(&w.class),
+                Background::parse(&w.background),
+            ) {
+                (Some(class), Some(background)) if !w.name.is_empty() => Some(Character {
+                    name: w.name,
+                    class,
+                    background,
+                }),
+                _ => None,
+            },
+            // World state (market, clock etc.) is not part of the inventory view
+            ..Inventory::new()
         }
     }
 }
@@ -81,13 +388,73 @@ impl From<WasmInventory> for Inventory {
 pub struct Game {
     inv: Inventory,
     shop: Option<Vec<ShopItem>>,
-    active_fight: Option<FightEncounter>,
+    shop_kind: ShopKind,
+    sell_offers: Vec<SellQuote>,
+    active_fight: Option<Encounter>,
+    haggle: Option<HaggleSession>,
+    notices: Vec<String>,
+    graves: Vec<Grave>, // hardcore characters lost this session
 }
 
-#[derive(Clone)]
-struct FightEncounter {
-    monster: crate::actions::Monster,
-    monster_hp: u32,
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DungeonExit {
+    pub room: u32,
+    pub direction: String,
+    pub label: String,
+    pub locked: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WasmDungeonState {
+    pub state: WasmInventory,
+    pub message: String,
+    pub depth: u32,
+    pub room: u32,
+    pub room_label: String,
+    pub explored: u32,
+    pub rooms: u32,
+    pub exits: Vec<DungeonExit>,
+    pub can_descend: bool,
+    pub in_fight: bool,
+    pub monster: Option<String>,
+    pub lines: Vec<String>,
+}
+
+// Auto-generated pickpocket / chest candidates (currency + trinket)
+fn random_loot_candidates() -> Vec<String> {
+    const TRINKETS: &[&str] = &[
+        "silver ring",
+        "brass key",
+        "tiny idol",
+        "opal shard",
+        "bloodstone",
+        "engraved locket",
+        "vellum scroll",
+        "jeweled clasp",
+        "murky potion",
+        "unusual ring",
+        "strange wand",
+        "carved bone die",
+        "amber bead",
+        "ancient coin",
+        "silk ribbon",
+    ];
+    let mut rng = rand::rngs::SmallRng::from_entropy();
+    let count = 5;
+    let mut items = Vec::with_capacity(count);
+    for _ in 0..count {
+        let gp = rng.gen_range(1..=25); // modest gold range
+        let trinket = TRINKETS[rng.gen_range(0..TRINKETS.len())];
+        // 50% chance to mix silver instead of gp for variety
+        let desc = if rng.gen_bool(0.5) {
+            let sp = rng.gen_range(2..=40);
+            format!("{} gp and {} sp and a {}", gp, sp, trinket)
+        } else {
+            format!("{} gp and a {}", gp, trinket)
+        };
+        items.push(desc);
+    }
+    items
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -95,11 +462,79 @@ pub struct WasmFightState {
     pub inventory: WasmInventory,
     pub message: String,
     pub in_fight: bool,
+    // First living foe (kept for single-target front ends)
     pub monster: Option<String>,
     pub monster_hp: u32,
     pub monster_max_hp: u32,
     pub player_hp: u32,
     pub player_max_hp: u32,
+    pub combatants: Vec<CombatantView>,
+    pub lines: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CombatantView {
+    pub index: u32,
+    pub name: String,
+    pub hp: u32,
+    pub max_hp: u32,
+    pub alive: bool,
+    pub fled: bool,
+    pub ability: String, // empty when the monster has no special trick
+}
+
+#[derive(Clone)]
+struct HaggleSession {
+    deal: Negotiation,
+    names: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WasmHaggleState {
+    pub state: WasmInventory,
+    pub message: String,
+    pub active: bool,
+    pub items: Vec<String>,
+    pub asking_cp: u32,
+    pub current_ask_cp: u32,
+    pub mood: String,
+    pub patience: u32,
+    pub round: u32,
+    pub lines: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct QuestView {
+    pub id: u32,
+    pub title: String,
+    pub description: String,
+    pub progress: String,
+    pub reward: String,
+    pub hours_left: u32,
+    pub status: String,
+}
+
+impl From<&quest::Quest> for QuestView {
+    fn from(q: &quest::Quest) -> Self {
+        Self {
+            id: q.id,
+            title: q.title(),
+            description: q.description(),
+            progress: q.progress_label(),
+            reward: q.reward_label(),
+            hours_left: q.hours_left,
+            status: format!("{:?}", q.status),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WasmQuestState {
+    pub state: WasmInventory,
+    pub message: String,
+    pub board: Vec<QuestView>,
+    pub active: Vec<QuestView>,
+    pub completed: u32,
     pub lines: Vec<String>,
 }
 
@@ -110,7 +545,12 @@ impl Game {
         Game {
             inv: Inventory::new(),
             shop: None,
+            shop_kind: ShopKind::General,
+            sell_offers: Vec::new(),
             active_fight: None,
+            haggle: None,
+            notices: Vec::new(),
+            graves: Vec::new(),
         }
     }
 
@@ -119,6 +559,17 @@ impl Game {
         serde_wasm_bindgen::to_value(&WasmInventory::from(self.inv.clone())).unwrap()
     }
 
+    // Let world time pass; notices wait for the next quest query
+    fn advance(&mut self, minutes: u64) {
+        let notes = self.inv.advance_time(minutes);
+        self.notices.extend(notes);
+    }
+
+    fn tavern_closed(&self) -> Option<String> {
+        (!self.inv.clock.tavern_open())
+            .then(|| format!("The tavern is shuttered until {:02}:00", TAVERN_OPEN_HOUR))
+    }
+
     fn wrap(&self, msg: impl Into<String>) -> JsValue {
         let res = WasmResult {
             state: WasmInventory::from(self.inv.clone()),
@@ -152,40 +603,12 @@ impl Game {
             .filter(|s| !s.is_empty())
             .collect();
         if items.is_empty() {
-            // Auto-generate 5 random candidate loot descriptions (currency + trinket)
-            const TRINKETS: &[&str] = &[
-                "silver ring",
-                "brass key",
-                "tiny idol",
-                "opal shard",
-                "bloodstone",
-                "engraved locket",
-                "vellum scroll",
-                "jeweled clasp",
-                "carved bone die",
-                "amber bead",
-                "ancient coin",
-                "silk ribbon",
-            ];
-            let mut rng = rand::rngs::SmallRng::from_entropy();
-            let count = 5;
-            for _ in 0..count {
-                let gp = rng.gen_range(1..=25); // modest gold range
-                let trinket = TRINKETS[rng.gen_range(0..TRINKETS.len())];
-                // 50% chance to mix silver instead of gp for variety
-                let desc = if rng.gen_bool(0.5) {
-                    let sp = rng.gen_range(2..=40);
-                    format!("{} gp and {} sp and a {}", gp, sp, trinket)
-                } else {
-                    format!("{} gp and a {}", gp, trinket)
-                };
-                items.push(desc);
-            }
+            items = random_loot_candidates();
         }
         let before_gp = self.inv.gold_pieces;
         let before_items = self.inv.items.len();
         let had_luck = self.inv.luck_boost;
-        pick_pocket(&mut self.inv, &items);
+        let report = pickpocket_attempt(&mut self.inv, &items);
         let gained_gp = self.inv.gold_pieces.saturating_sub(before_gp);
         let added_items = self.inv.items.len().saturating_sub(before_items);
         let mut parts = Vec::new();
@@ -201,13 +624,23 @@ impl Game {
         if parts.is_empty() {
             parts.push("no gain".into());
         }
-        self.wrap(format!("Pickpocket: {}", parts.join(", ")))
+        let mut msg = format!("{}: {}", report.title, parts.join(", "));
+        for line in &report.lines {
+            msg.push_str(&format!("\n{}", line));
+        }
+        self.notices.extend(report.lines);
+        self.wrap(msg)
     }
 
     /// Simulate a monster fight (random outcome & reward internally)
     #[wasm_bindgen]
     pub fn fight(&mut self) -> JsValue {
         let outcome = fight_monster_outcome(&mut self.inv);
+        if let Some(grave) = outcome.grave {
+            let msg = format!("{} A new adventurer steps forward.", grave.epitaph());
+            self.graves.push(grave);
+            return self.wrap(msg);
+        }
         let msg = if outcome.victory {
             format!(
                 "Victory over {} (+{} gp)",
@@ -221,135 +654,332 @@ impl Game {
         self.wrap(msg)
     }
 
-    /// Simulate buying items (name1[:rarity]|name2 ...) applying haggle flag; rarity optional (Common,Uncommon,Rare,Epic,Legendary)
-    #[wasm_bindgen]
-    pub fn shop_buy(&mut self, items_spec: &str, attempt_haggle: bool, luck: bool) -> JsValue {
-        use rand::Rng;
-        let mut rng = rand::rngs::SmallRng::from_entropy();
-        if luck {
-            self.inv.luck_boost = true;
-        }
-        let mut total_cp = 0u32;
-        let mut parsed: Vec<(String, Rarity, u32)> = Vec::new();
-        for raw in items_spec
-            .split('|')
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-        {
-            let mut parts = raw.split(':');
-            let name = parts.next().unwrap().trim();
-            let rar = parts
-                .next()
-                .map(|r| match r.to_lowercase().as_str() {
-                    "uncommon" => Rarity::Uncommon,
-                    "rare" => Rarity::Rare,
-                    "epic" => Rarity::Epic,
-                    "legendary" => Rarity::Legendary,
-                    _ => Rarity::Common,
-                })
-                .unwrap_or(Rarity::Common);
-            let (min, max) = match rar {
-                Rarity::Common => (5, 50),
-                Rarity::Uncommon => (50, 500),
-                Rarity::Rare => (500, 5_000),
-                Rarity::Epic => (5_000, 20_000),
-                Rarity::Legendary => (20_000, 50_000),
-            };
-            let price = rng.gen_range(min..=max);
-            total_cp = total_cp.saturating_add(price);
-            parsed.push((name.to_string(), rar, price));
-        }
-        if attempt_haggle && !parsed.is_empty() {
-            let success_chance = if self.inv.luck_boost { 0.85 } else { 0.50 };
-            let success = rng.gen_bool(success_chance);
-            if success {
-                total_cp = ((total_cp as f64) * 0.75).round() as u32;
-            } else {
-                total_cp = ((total_cp as f64) * 1.10).round() as u32;
-            }
-            if self.inv.luck_boost {
-                self.inv.luck_boost = false;
-            }
-        }
-        let msg = if self.inv.total_cp() >= total_cp {
-            let _ = self.inv.try_spend_cp(total_cp);
-            for (n, _, _) in parsed {
-                self.inv.add_item(&n);
-            }
-            format!("Purchased items total {} cp", total_cp)
-        } else {
-            format!("Insufficient funds for {} cp purchase", total_cp)
-        };
-        self.wrap(msg)
-    }
-
     // --- Enhanced gameplay style APIs ---
     #[wasm_bindgen]
     pub fn reset(&mut self) -> JsValue {
         self.inv = Inventory::new();
         self.shop = None;
+        self.sell_offers.clear();
         self.active_fight = None;
+        self.haggle = None;
+        self.notices.clear();
         self.wrap("Inventory reset")
     }
 
+    /// Pay shops, the tavern and companions with exact coins only (or let them make change).
+    #[wasm_bindgen]
+    pub fn set_exact_change(&mut self, exact: bool) -> JsValue {
+        self.inv.pay_mode = if exact {
+            PayMode::ExactChange
+        } else {
+            PayMode::MakeChange
+        };
+        self.wrap(if exact {
+            "You'll pay with exact coins only"
+        } else {
+            "Merchants may make change for you"
+        })
+    }
+
+    /// Start a fresh character at `easy|normal|hard` (unknown values fall back to normal).
+    /// In hardcore mode dropping to 0 HP ends the run.
+    #[wasm_bindgen]
+    pub fn new_game(&mut self, difficulty: &str, hardcore: bool) -> JsValue {
+        let chosen = Difficulty::parse(difficulty).unwrap_or_default();
+        let stats = std::mem::take(&mut self.inv.stats);
+        self.reset();
+        self.inv.stats = stats;
+        self.inv.difficulty = chosen;
+        self.inv.hardcore = hardcore;
+        self.wrap(format!(
+            "A new {}adventure begins on {} difficulty",
+            if hardcore { "hardcore " } else { "" },
+            chosen.label()
+        ))
+    }
+
+    /// Create a named character: `class` is `rogue|fighter|bard`, `background` is
+    /// `urchin|soldier|noble|wanderer`. Returns the new state, or the old one with an error message.
+    #[wasm_bindgen]
+    pub fn create_character(
+        &mut self,
+        name: &str,
+        class: &str,
+        background: &str,
+        difficulty: &str,
+        hardcore: bool,
+    ) -> JsValue {
+        let Some(class) = CharacterClass::parse(class) else {
+            return self.wrap("Unknown class (rogue, fighter or bard)");
+        };
+        let Some(background) = Background::parse(background) else {
+            return self.wrap("Unknown background (urchin, soldier, noble or wanderer)");
+        };
+        let difficulty = Difficulty::parse(difficulty).unwrap_or_default();
+        match character::create(name, class, background, difficulty, hardcore) {
+            Ok(inv) => {
+                let stats = std::mem::take(&mut self.inv.stats);
+                self.reset();
+                self.inv = inv;
+                self.inv.stats = stats;
+                let title = self.inv.character.as_ref().map(|c| c.title());
+                self.wrap(format!(
+                    "{} sets out with {}",
+                    title.unwrap_or_default(),
+                    self.inv.items.join(", ")
+                ))
+            }
+            Err(e) => self.wrap(e),
+        }
+    }
+
+    /// Classes and backgrounds on offer, with their descriptions and starting kits.
+    #[wasm_bindgen]
+    pub fn character_options(&self) -> JsValue {
+        let classes: Vec<CharacterOption> = CharacterClass::ALL
+            .iter()
+            .map(|c| CharacterOption {
+                id: c.label().to_lowercase(),
+                label: c.label().to_string(),
+                description: c.description().to_string(),
+                kit: c.starting_kit().iter().map(|s| s.to_string()).collect(),
+            })
+            .collect();
+        let backgrounds: Vec<CharacterOption> = Background::ALL
+            .iter()
+            .map(|b| CharacterOption {
+                id: b.label().to_lowercase(),
+                label: b.label().to_string(),
+                description: b.description().to_string(),
+                kit: b.kit().iter().map(|s| s.to_string()).collect(),
+            })
+            .collect();
+        serde_wasm_bindgen::to_value(&CharacterOptions {
+            classes,
+            backgrounds,
+        })
+        .unwrap()
+    }
+
+    /// Headstones of hardcore characters lost this session (oldest first).
+    #[wasm_bindgen]
+    pub fn get_graveyard(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.graves).unwrap()
+    }
+
+    /// Lifetime stats and achievements (kept across new characters; cleared by `reset`).
+    /// `new_achievements` holds unlock notes not shown yet and is emptied by this call.
+    #[wasm_bindgen]
+    pub fn get_stats(&mut self) -> JsValue {
+        let new_achievements = self.inv.stats.take_announcements();
+        let s = &self.inv.stats;
+        let view = WasmStats {
+            pickpockets_attempted: s.pickpockets_attempted,
+            pickpockets_succeeded: s.pickpockets_succeeded,
+            times_caught: s.times_caught,
+            mysterious_figures: s.mysterious_figures,
+            coin_earned_cp: s.coin_earned_cp,
+            coin_lost_cp: s.coin_lost_cp,
+            monsters_slain: s
+                .monsters_slain
+                .iter()
+                .map(|(monster, &count)| SlainCount {
+                    monster: monster.clone(),
+                    count,
+                })
+                .collect(),
+            knockouts: s.knockouts,
+            deaths: s.deaths,
+            fights_fled: s.fights_fled,
+            items_bought: s.items_bought,
+            items_sold: s.items_sold,
+            tavern_tips: s.tavern_tips,
+            achievements: ACHIEVEMENTS
+                .iter()
+                .map(|a| AchievementView {
+                    id: a.id.to_string(),
+                    title: a.title.to_string(),
+                    description: a.description.to_string(),
+                    unlocked: s.has(a.id),
+                })
+                .collect(),
+            new_achievements,
+        };
+        serde_wasm_bindgen::to_value(&view).unwrap()
+    }
+
+    /// Produce a new General Store stock (kept for older front ends; see `enter_shop`).
     #[wasm_bindgen]
     pub fn generate_shop(&mut self) -> JsValue {
-        use rand::Rng;
-        use rand::seq::SliceRandom;
-        const STOCK: &[(&str, Rarity)] = &[
-            ("Rope (50ft)", Rarity::Common),
-            ("Torch", Rarity::Common),
-            ("Lantern", Rarity::Common),
-            ("Oil Flask", Rarity::Common),
-            ("Iron Rations", Rarity::Common),
-            ("Waterskin", Rarity::Common),
-            ("Lockpicks", Rarity::Uncommon),
-            ("Bedroll", Rarity::Common),
-            ("Backpack", Rarity::Common),
-            ("Shovel", Rarity::Common),
-            ("Grappling Hook", Rarity::Uncommon),
-            ("Hammer & Pitons", Rarity::Common),
-            ("Herb Bundle", Rarity::Common),
-            ("Ink & Quill", Rarity::Common),
-            ("Chalk Pouch", Rarity::Common),
-            ("Potion of Healing", Rarity::Uncommon),
-            ("Potion of Invisibility", Rarity::Rare),
-            ("Scroll of Fireball", Rarity::Rare),
-            ("Scroll of Shielding", Rarity::Uncommon),
-            ("Ring of Protection", Rarity::Epic),
-            ("Amulet of Light", Rarity::Epic),
-            ("Wand of Sparks", Rarity::Uncommon),
-            ("Boots of Silence", Rarity::Rare),
-            ("Cloak of Shadows", Rarity::Epic),
-            ("Elixir of Luck", Rarity::Rare),
-            ("Orb of Annihilation Shard", Rarity::Legendary),
-        ];
-        let mut pool: Vec<(&str, Rarity)> = STOCK.to_vec();
-        let mut rng = rand::rngs::SmallRng::from_entropy();
-        pool.shuffle(&mut rng);
-        let count = rng.gen_range(6..=10).min(pool.len() as u32) as usize;
-        let chosen = pool.into_iter().take(count);
-        let items: Vec<ShopItem> = chosen
+        self.enter_shop("general")
+    }
+
+    /// Enter a shop by kind (`general|blacksmith|alchemist|magic|fence`) and roll its stock.
+    #[wasm_bindgen]
+    pub fn enter_shop(&mut self, kind: &str) -> JsValue {
+        let Some(kind) = ShopKind::parse(kind) else {
+            return self.wrap("Unknown shop (general, blacksmith, alchemist, magic or fence)");
+        };
+        self.advance(SHOP_VISIT_MINUTES);
+        let items: Vec<ShopItem> = shop::generate_stock(kind, &self.inv.market)
+            .into_iter()
             .enumerate()
-            .map(|(id, (name, rar))| {
-                let range = rar.price_range_cp();
-                let price = rng.gen_range(*range.start()..=*range.end());
-                ShopItem {
-                    id: id as u32,
-                    name: name.to_string(),
-                    rarity: rar.label().to_string(),
-                    price_cp: price,
-                }
+            .map(|(id, it)| ShopItem {
+                id: id as u32,
+                name: it.name,
+                rarity: it.rarity.label().to_string(),
+                price_cp: it.price_cp,
             })
             .collect();
         self.shop = Some(items.clone());
+        self.shop_kind = kind;
+        self.sell_offers.clear();
+        self.refresh_sell_offers();
         serde_wasm_bindgen::to_value(&ShopState {
             items,
             haggle_applied: false,
+            kind: format!("{:?}", kind),
+            name: kind.label().to_string(),
+            greeting: kind.catalog().greeting.clone(),
+            purse_cp: self.inv.market.purse_cp(kind),
+            appraisal_fee_cp: kind.catalog().appraisal_fee_cp,
+            sell_offers: self.sell_offer_list(),
         })
         .unwrap()
     }
 
+    // Offers are rolled once per carried item and kept while it stays in the pack, so
+    // re-reading the list can't fish for better prices. Anything new or changed (picked up,
+    // appraised, reshuffled by a theft) gets a fresh roll.
+    fn refresh_sell_offers(&mut self) {
+        let mut cached = std::mem::take(&mut self.sell_offers);
+        self.sell_offers = (0..self.inv.items.len())
+            .map(|i| {
+                let item = &self.inv.items[i];
+                let stolen = self.inv.is_stolen_at(i);
+                match cached
+                    .iter()
+                    .position(|q| q.item == *item && q.stolen == stolen)
+                {
+                    Some(at) => cached.remove(at),
+                    None => SellQuote {
+                        item: item.clone(),
+                        stolen,
+                        offer_cp: shop::sell_offer_cp(self.shop_kind, &self.inv, i),
+                    },
+                }
+            })
+            .collect();
+    }
+
+    fn sell_offer_list(&self) -> Vec<SellOffer> {
+        self.inv
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, name)| SellOffer {
+                index: i as u32,
+                name: name.clone(),
+                offer_cp: self.sell_offers.get(i).and_then(|q| q.offer_cp),
+                stolen: self.inv.is_stolen_at(i),
+                unidentified: appraisal::is_unidentified(name),
+            })
+            .collect()
+    }
+
+    /// Current shop's offers for each carried item (`offer_cp` is null when refused).
+    #[wasm_bindgen]
+    pub fn shop_sell_offers(&mut self) -> JsValue {
+        self.refresh_sell_offers();
+        serde_wasm_bindgen::to_value(&self.sell_offer_list()).unwrap()
+    }
+
+    /// What the current shop would pay for a selection: `asked_cp` from the offers and
+    /// `paid_cp` after the merchant's purse runs dry.
+    #[wasm_bindgen]
+    pub fn shop_sale_quote(&mut self, indices: Vec<u32>) -> JsValue {
+        self.refresh_sell_offers();
+        let selected: Vec<usize> = indices.iter().map(|i| *i as usize).collect();
+        let offers: Vec<Option<u32>> = self.sell_offers.iter().map(|q| q.offer_cp).collect();
+        let quote = SaleQuote {
+            asked_cp: listed_total_cp(&selected, &offers),
+            paid_cp: shop::sale_total_cp(self.shop_kind, &self.inv, &selected, &offers),
+        };
+        serde_wasm_bindgen::to_value(&quote).unwrap()
+    }
+
+    /// Sell carried items (by inventory index) to the current shop. If the merchant can't
+    /// pay the full offers the sale is refused unless `accept_short_purse` is set (check
+    /// `shop_sale_quote` first).
+    #[wasm_bindgen]
+    pub fn shop_sell(&mut self, indices: Vec<u32>, accept_short_purse: bool) -> JsValue {
+        if self.shop.is_none() {
+            return self.wrap("No shop entered yet");
+        }
+        self.refresh_sell_offers();
+        let selected: Vec<usize> = indices.iter().map(|i| *i as usize).collect();
+        let offers: Vec<Option<u32>> = self.sell_offers.iter().map(|q| q.offer_cp).collect();
+        let asked = listed_total_cp(&selected, &offers);
+        let spare = shop::sale_total_cp(self.shop_kind, &self.inv, &selected, &offers);
+        if spare < asked && !accept_short_purse {
+            return self.wrap(format!(
+                "The merchant only has {} cp to spare (you asked {} cp); nothing sold",
+                spare, asked
+            ));
+        }
+        let refused = selected
+            .iter()
+            .filter(|&&i| offers.get(i).copied().flatten().is_none())
+            .count();
+        let (removed, total) =
+            shop::sell_selected(&mut self.inv, self.shop_kind, &selected, &offers);
+        self.refresh_sell_offers();
+        let mut msg = if removed.is_empty() {
+            "Nothing sold".to_string()
+        } else {
+            format!(
+                "Sold {} item(s) to the {} for {} cp",
+                removed.len(),
+                self.shop_kind.label(),
+                total
+            )
+        };
+        if refused > 0 {
+            msg.push_str(&format!(" ({} refused as stolen)", refused));
+        }
+        self.wrap(msg)
+    }
+
+    /// Pay the current shop to identify the mystery item at inventory `index`.
+    #[wasm_bindgen]
+    pub fn shop_appraise(&mut self, index: u32) -> JsValue {
+        if self.shop.is_none() {
+            return self.wrap("No shop entered yet");
+        }
+        let result = appraisal::appraise_at_shop(&mut self.inv, self.shop_kind, index as usize);
+        self.appraisal_result(result)
+    }
+
+    /// Read a carried Scroll of Identify over the mystery item at inventory `index`.
+    #[wasm_bindgen]
+    pub fn read_identify_scroll(&mut self, index: u32) -> JsValue {
+        let result = appraisal::read_identify_scroll(&mut self.inv, index as usize);
+        self.appraisal_result(result)
+    }
+
+    fn appraisal_result(&mut self, result: Result<Appraisal, AppraisalError>) -> JsValue {
+        match result {
+            Ok(found) => {
+                self.advance(APPRAISAL_MINUTES);
+                self.refresh_sell_offers();
+                self.wrap(found.summary())
+            }
+            Err(e) => self.wrap(e.message()),
+        }
+    }
+
+    /// Buy selected items at the listed price. With `attempt_haggle` this opens a negotiation
+    /// instead and returns a `WasmHaggleState` (continue with `haggle_offer` / `haggle_accept`).
     #[wasm_bindgen]
     pub fn shop_purchase(
         &mut self,
@@ -357,16 +987,25 @@ impl Game {
         attempt_haggle: bool,
         spend_luck: bool,
     ) -> JsValue {
-        if self.shop.is_none() {
-            return self.wrap("No shop stock generated yet");
-        }
-        if spend_luck {
-            self.inv.luck_boost = true;
+        if attempt_haggle {
+            return self.haggle_start(indices, spend_luck);
         }
-        let stock = self.shop.as_ref().unwrap();
-        if indices.is_empty() {
+        let Some((names, total)) = self.shop_selection(&indices) else {
+            return self.wrap("No shop stock generated yet");
+        };
+        if names.is_empty() {
             return self.wrap("No items selected");
         }
+        if total == 0 {
+            return self.wrap("Selection invalid");
+        }
+        let msg = self.complete_purchase(&names, total);
+        self.wrap(msg)
+    }
+
+    // --- Interactive haggle API (browser) ---
+    fn shop_selection(&self, indices: &[u32]) -> Option<(Vec<String>, u32)> {
+        let stock = self.shop.as_ref()?;
         let mut total: u32 = 0;
         let mut names: Vec<String> = Vec::new();
         for i in indices.iter() {
@@ -375,126 +1014,560 @@ impl Game {
                 names.push(it.name.clone());
             }
         }
-        if total == 0 {
-            return self.wrap("Selection invalid");
-        }
-        let mut final_total = total;
-        let mut haggle_msg = String::new();
-        if attempt_haggle {
-            let chance = if self.inv.luck_boost {
-                0.85
-            } else {
-                HAGGLE_SUCCESS_CHANCE
-            };
-            let mut rng = rand::rngs::SmallRng::from_entropy();
-            let success = rng.gen_bool(chance);
-            if success {
-                final_total = ((final_total as f64) * 0.75).round() as u32;
-                haggle_msg = format!("Haggle success ({}%)", (chance * 100.0) as u32);
-            } else {
-                final_total = ((final_total as f64) * 1.10).round() as u32;
-                haggle_msg = format!("Haggle failed ({}%)", (chance * 100.0) as u32);
-            }
-            if self.inv.luck_boost {
-                self.inv.luck_boost = false;
-            }
-        }
-        if self.inv.total_cp() < final_total {
-            return self.wrap(format!(
+        Some((names, total))
+    }
+
+    fn complete_purchase(&mut self, names: &[String], price_cp: u32) -> String {
+        match shop::purchase(&mut self.inv, self.shop_kind, names, price_cp) {
+            Ok(payment) if !payment.change.is_zero() => format!(
+                "Bought {} item(s) for {} cp ({} in change)",
+                names.len(),
+                price_cp,
+                payment.change
+            ),
+            Ok(_) => format!("Bought {} item(s) for {} cp", names.len(), price_cp),
+            Err(_) => format!(
                 "Need {} cp but only have {} cp",
-                final_total,
+                price_cp,
                 self.inv.total_cp()
-            ));
+            ),
+        }
+    }
+
+    fn haggle_state(&self, message: impl Into<String>, lines: Vec<String>) -> JsValue {
+        let hs = match &self.haggle {
+            Some(h) => WasmHaggleState {
+                state: WasmInventory::from(self.inv.clone()),
+                message: message.into(),
+                active: !h.deal.is_over(),
+                items: h.names.clone(),
+                asking_cp: h.deal.asking_cp,
+                current_ask_cp: h.deal.current_ask_cp,
+                mood: h.deal.mood.label().to_string(),
+                patience: h.deal.patience,
+                round: h.deal.round,
+                lines,
+            },
+            None => WasmHaggleState {
+                state: WasmInventory::from(self.inv.clone()),
+                message: message.into(),
+                active: false,
+                items: vec![],
+                asking_cp: 0,
+                current_ask_cp: 0,
+                mood: String::new(),
+                patience: 0,
+                round: 0,
+                lines,
+            },
+        };
+        serde_wasm_bindgen::to_value(&hs).unwrap()
+    }
+
+    /// Open a negotiation over the selected shop items.
+    #[wasm_bindgen]
+    pub fn haggle_start(&mut self, indices: Vec<u32>, spend_luck: bool) -> JsValue {
+        if self.haggle.as_ref().is_some_and(|h| !h.deal.is_over()) {
+            return self.haggle_state("Already haggling", vec![]);
         }
-        let _ = self.inv.try_spend_cp(final_total);
-        for n in names.iter() {
-            self.inv.add_item(n);
+        let Some((names, total)) = self.shop_selection(&indices) else {
+            return self.haggle_state("No shop stock generated yet", vec![]);
+        };
+        if names.is_empty() || total == 0 {
+            return self.haggle_state("No items selected", vec![]);
         }
-        self.wrap(format!(
-            "Bought {} item(s) for {} cp. {}",
-            names.len(),
-            final_total,
-            haggle_msg
-        ))
+        let ctx = HaggleContext::from_inventory(&self.inv, spend_luck);
+        if ctx.luck {
+            self.inv.luck_boost = false;
+        }
+        let deal = Negotiation::new(total, ctx);
+        let mut lines = vec![format!(
+            "The merchant asks {} cp ({} mood, patience {})",
+            total,
+            deal.mood.label(),
+            deal.patience
+        )];
+        if ctx.luck {
+            lines.push("Your stored luck is spent in the negotiation".into());
+        }
+        self.haggle = Some(HaggleSession { deal, names });
+        self.haggle_state("Negotiation opened", lines)
     }
 
+    /// Make a counter-offer (in cp) in the active negotiation.
     #[wasm_bindgen]
-    pub fn tavern(&mut self, action: &str) -> JsValue {
-        use rand::Rng;
-        let mut rng = rand::rngs::SmallRng::from_entropy();
-        let msg = match action.to_lowercase().as_str() {
-            "drink" => {
-                if !self.inv.try_spend_cp(TAVERN_DRINK_COST_SP * 10) {
-                    "Not enough coin for drink".into()
-                } else {
-                    "Enjoyed a stiff drink".into()
-                }
-            }
-            "food" => {
-                if !self.inv.try_spend_cp(TAVERN_FOOD_COST_SP * 10) {
-                    "Can't afford meal".into()
-                } else {
-                    "A hearty meal restores you".into()
-                }
+    pub fn haggle_offer(&mut self, offer_cp: u32) -> JsValue {
+        let Some(h) = self.haggle.as_mut().filter(|h| !h.deal.is_over()) else {
+            return self.haggle_state("No active negotiation", vec![]);
+        };
+        let step = h.deal.offer(offer_cp);
+        self.finish_haggle_step(step, format!("You offer {} cp", offer_cp))
+    }
+
+    /// Accept the merchant's current asking price.
+    #[wasm_bindgen]
+    pub fn haggle_accept(&mut self) -> JsValue {
+        let Some(h) = self.haggle.as_mut().filter(|h| !h.deal.is_over()) else {
+            return self.haggle_state("No active negotiation", vec![]);
+        };
+        let step = h.deal.accept();
+        self.finish_haggle_step(step, "You accept the asking price".into())
+    }
+
+    /// Break off the negotiation without buying.
+    #[wasm_bindgen]
+    pub fn haggle_walk(&mut self) -> JsValue {
+        let Some(h) = self.haggle.as_mut().filter(|h| !h.deal.is_over()) else {
+            return self.haggle_state("No active negotiation", vec![]);
+        };
+        h.deal.walk_away();
+        self.haggle = None;
+        self.haggle_state("You walk away from the deal", vec![])
+    }
+
+    fn finish_haggle_step(&mut self, step: HaggleStep, first_line: String) -> JsValue {
+        let mut lines = vec![first_line];
+        match step {
+            HaggleStep::Accepted { price_cp } => {
+                let names = self.haggle.take().map(|h| h.names).unwrap_or_default();
+                lines.push(format!("Deal struck at {} cp", price_cp));
+                let msg = self.complete_purchase(&names, price_cp);
+                self.haggle_state(msg, lines)
             }
-            "stay" => {
-                if !self.inv.try_spend_cp(TAVERN_STAY_COST_GP * 100) {
-                    "Not enough for room".into()
-                } else {
-                    "You rest peacefully".into()
-                }
+            HaggleStep::Countered { ask_cp } => {
+                lines.push(format!("The merchant counters at {} cp", ask_cp));
+                self.haggle_state("Merchant counters", lines)
             }
-            "tip" => {
-                if self.inv.luck_boost {
-                    "Luck already stored".into()
-                } else if !self.inv.try_spend_cp(TAVERN_TIP_COST_GP * 100) {
-                    "Need more gold to tip".into()
-                } else if rng.gen_bool(TAVERN_LUCK_CHANCE) {
-                    self.inv.luck_boost = true;
-                    "Luck granted from generous tip".into()
-                } else {
-                    "Tip given, no luck".into()
-                }
+            HaggleStep::WalkedAway => {
+                lines.push("The merchant waves you out of the shop".into());
+                let msg = self.haggle_state("The merchant walks away", lines);
+                self.haggle = None;
+                msg
             }
-            "flirt" => {
-                if !self.inv.try_spend_cp(TAVERN_FLIRT_COST_GP * 100) {
-                    "Can't afford to flirt".into()
-                } else if rng.gen_bool(TAVERN_FLIRT_KISS_CHANCE) {
-                    if !self.inv.luck_boost {
-                        self.inv.luck_boost = true;
-                        "A kiss grants you luck".into()
-                    } else {
-                        "Another kiss, luck unchanged".into()
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn tavern(&mut self, action: &str) -> JsValue {
+        if let Some(msg) = self.tavern_closed() {
+            return self.wrap(msg);
+        }
+        let Some(order) = TavernOrder::parse(action) else {
+            return self.wrap(format!("Unknown action: {}", action));
+        };
+        let mut lines = tavern_order(&mut self.inv, order).into_iter();
+        let msg = lines.next().unwrap_or_default();
+        self.notices.extend(lines);
+        self.wrap(msg)
+    }
+
+    // --- Crafting ---
+    fn craft_state(&self, message: impl Into<String>) -> JsValue {
+        let ready = crafting::craftable(&self.inv);
+        let res = WasmCraftState {
+            state: WasmInventory::from(self.inv.clone()),
+            message: message.into(),
+            recipes: crafting::recipes()
+                .iter()
+                .map(|r| {
+                    let known = self.inv.recipes.knows(&r.id);
+                    RecipeView {
+                        id: r.id.clone(),
+                        known,
+                        makes: known.then(|| r.makes.clone()),
+                        formula: known.then(|| r.formula()),
+                        hint: r.hint.clone(),
+                        craftable: ready.iter().any(|c| c.id == r.id),
                     }
+                })
+                .collect(),
+            discovered: self.inv.recipes.discovered.len() as u32,
+            total: crafting::recipes().len() as u32,
+        };
+        serde_wasm_bindgen::to_value(&res).unwrap()
+    }
+
+    fn craft_result(&mut self, result: Result<Crafted, CraftError>) -> JsValue {
+        match result {
+            Ok(made) => {
+                self.advance(made.minutes);
+                self.craft_state(made.summary())
+            }
+            Err(e) => self.craft_state(e.message()),
+        }
+    }
+
+    /// The recipe book: discovered recipes in full, hints for the rest.
+    #[wasm_bindgen]
+    pub fn get_recipes(&self) -> JsValue {
+        self.craft_state("")
+    }
+
+    /// Make a discovered recipe from carried items.
+    #[wasm_bindgen]
+    pub fn craft(&mut self, recipe_id: &str) -> JsValue {
+        let result = crafting::craft(&mut self.inv, recipe_id);
+        self.craft_result(result)
+    }
+
+    /// Experiment: combine the inventory items at `indices`; a match is made and learned.
+    #[wasm_bindgen]
+    pub fn craft_combine(&mut self, indices: Vec<u32>) -> JsValue {
+        let selected: Vec<usize> = indices.iter().map(|i| *i as usize).collect();
+        let result = crafting::combine(&mut self.inv, &selected);
+        self.craft_result(result)
+    }
+
+    // --- Companions ---
+    fn party_state(&self, message: impl Into<String>) -> JsValue {
+        let res = WasmPartyState {
+            state: WasmInventory::from(self.inv.clone()),
+            message: message.into(),
+            companions: self
+                .inv
+                .party
+                .companions
+                .iter()
+                .map(CompanionView::from)
+                .collect(),
+            hires: CompanionKind::ALL
+                .iter()
+                .map(|k| HireOption {
+                    id: format!("{:?}", k).to_lowercase(),
+                    label: k.label().to_string(),
+                    description: k.description().to_string(),
+                    daily_wage_cp: k.daily_wage_cp(),
+                })
+                .collect(),
+            max_size: companion::PARTY_MAX as u32,
+        };
+        serde_wasm_bindgen::to_value(&res).unwrap()
+    }
+
+    /// Current companions plus who can be hired at the tavern.
+    #[wasm_bindgen]
+    pub fn get_party(&self) -> JsValue {
+        self.party_state("")
+    }
+
+    /// Hire a companion at the tavern: `kind` is `sellsword|apprenticethief|healer`.
+    #[wasm_bindgen]
+    pub fn tavern_hire(&mut self, kind: &str) -> JsValue {
+        if let Some(msg) = self.tavern_closed() {
+            return self.party_state(msg);
+        }
+        let Some(kind) = CompanionKind::parse(kind) else {
+            return self.party_state(format!("No one here answers to '{}'", kind));
+        };
+        match companion::hire(&mut self.inv, kind) {
+            Ok(title) => {
+                self.advance(TAVERN_HIRE_MINUTES);
+                self.party_state(format!("{} joins you", title))
+            }
+            Err(e) => self.party_state(e.message()),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn dismiss_companion(&mut self, index: u32) -> JsValue {
+        match companion::dismiss(&mut self.inv, index as usize) {
+            Ok(c) => self.party_state(format!("{} takes their leave", c.title())),
+            Err(e) => self.party_state(e.message()),
+        }
+    }
+
+    /// Play a round of Rune Dice: `wager` is `under|seven|over`; luck rerolls a losing throw.
+    #[wasm_bindgen]
+    pub fn tavern_gamble(&mut self, bet_cp: u32, wager: &str, spend_luck: bool) -> JsValue {
+        let Some(wager) = DiceWager::parse(wager) else {
+            return self.wrap(format!("Unknown wager: {}", wager));
+        };
+        if let Some(msg) = self.tavern_closed() {
+            return self.wrap(msg);
+        }
+        let msg = match play_rune_dice(&mut self.inv, bet_cp, wager, spend_luck) {
+            Ok(outcome) => {
+                self.advance(TAVERN_GAMBLE_MINUTES);
+                if outcome.luck_spent {
+                    format!("{} (luck spent)", outcome.summary())
                 } else {
-                    "No luck this time".into()
+                    outcome.summary()
                 }
             }
-            other => format!("Unknown action: {}", other),
+            Err(e) => e.message(),
         };
         self.wrap(msg)
     }
 
-    // --- Interactive fight API (browser) ---
-    fn fight_state(&self, message: impl Into<String>, lines: Vec<String>) -> JsValue {
-        let (monster, m_hp, m_max) = if let Some(f) = &self.active_fight {
-            (
-                Some(f.monster.name.to_string()),
-                f.monster_hp,
-                f.monster.max_hp(),
-            )
+    // --- Bank ---
+    fn bank_state(&self, message: impl Into<String>) -> JsValue {
+        let bank = &self.inv.bank;
+        let res = WasmBankState {
+            state: WasmInventory::from(self.inv.clone()),
+            message: message.into(),
+            open: self.inv.clock.bank_open(),
+            balance_cp: bank.balance_cp,
+            fees_owed_cp: bank.fees_owed_cp,
+            daily_fee_cp: bank.daily_fee_cp(),
+            vault: bank.vault.iter().map(|v| v.name.clone()).collect(),
+        };
+        serde_wasm_bindgen::to_value(&res).unwrap()
+    }
+
+    // Run one counter transaction: refused while the bank is shut, time passes on success
+    fn bank_action(
+        &mut self,
+        action: impl FnOnce(&mut Inventory) -> Result<String, String>,
+    ) -> JsValue {
+        if !self.inv.clock.bank_open() {
+            return self.bank_state(format!(
+                "The bank keeps hours from {:02}:00 to {:02}:00",
+                BANK_OPEN_HOUR, BANK_CLOSE_HOUR
+            ));
+        }
+        match action(&mut self.inv) {
+            Ok(msg) => {
+                self.advance(BANK_VISIT_MINUTES);
+                self.bank_state(msg)
+            }
+            Err(e) => self.bank_state(e),
+        }
+    }
+
+    /// Account balance, overdue fees and vault contents (coin and items here are safe).
+    #[wasm_bindgen]
+    pub fn get_bank(&self) -> JsValue {
+        self.bank_state("")
+    }
+
+    #[wasm_bindgen]
+    pub fn bank_deposit(&mut self, cp: u32) -> JsValue {
+        self.bank_action(|inv| {
+            bank::deposit(inv, cp)
+                .map(|banked| format!("Deposited {} cp", banked))
+                .map_err(|e| e.message())
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn bank_withdraw(&mut self, cp: u32) -> JsValue {
+        self.bank_action(|inv| {
+            bank::withdraw(inv, cp)
+                .map(|cp| format!("Withdrew {} cp", cp))
+                .map_err(|e| e.message())
+        })
+    }
+
+    /// Lock the carried item at `index` in the vault.
+    #[wasm_bindgen]
+    pub fn bank_store(&mut self, index: u32) -> JsValue {
+        self.bank_action(|inv| {
+            bank::store_item(inv, index as usize)
+                .map(|n| format!("The {} is locked away", n))
+                .map_err(|e| e.message())
+        })
+    }
+
+    /// Trade `amount` carried coins of `from` (`pp|gp|ep|sp|cp`) for `to`, less the changer's fee.
+    #[wasm_bindgen]
+    pub fn bank_exchange(&mut self, from: &str, amount: u32, to: &str) -> JsValue {
+        self.bank_action(|inv| {
+            currency::exchange(inv, from, amount, to)
+                .map(|x| x.summary())
+                .map_err(|e| e.message())
+        })
+    }
+
+    /// Take the vault item at `index` back out.
+    #[wasm_bindgen]
+    pub fn bank_retrieve(&mut self, index: u32) -> JsValue {
+        self.bank_action(|inv| {
+            bank::retrieve_item(inv, index as usize)
+                .map(|n| format!("You take back the {}", n))
+                .map_err(|e| e.message())
+        })
+    }
+
+    // --- Quests (tavern rumor board) ---
+    fn quest_state(&mut self, message: impl Into<String>, mut lines: Vec<String>) -> JsValue {
+        self.inv.quests.sync_inventory(&self.inv.items);
+        let mut all = std::mem::take(&mut self.notices);
+        all.append(&mut lines);
+        let qs = WasmQuestState {
+            state: WasmInventory::from(self.inv.clone()),
+            message: message.into(),
+            board: self.inv.quests.board.iter().map(QuestView::from).collect(),
+            active: self.inv.quests.active.iter().map(QuestView::from).collect(),
+            completed: self.inv.quests.completed_count() as u32,
+            lines: all,
+        };
+        serde_wasm_bindgen::to_value(&qs).unwrap()
+    }
+
+    /// Current quest log (board, active quests, completed count) plus pending notices.
+    #[wasm_bindgen]
+    pub fn get_quests(&mut self) -> JsValue {
+        self.quest_state("Quest log", vec![])
+    }
+
+    /// Read the tavern rumor board, pinning new quests if there's room.
+    #[wasm_bindgen]
+    pub fn quest_board(&mut self) -> JsValue {
+        self.inv.quests.refresh_board();
+        self.quest_state("You read the rumor board", vec![])
+    }
+
+    #[wasm_bindgen]
+    pub fn quest_accept(&mut self, id: u32) -> JsValue {
+        let msg = match self.inv.quests.accept(id) {
+            Ok(q) => format!("Quest accepted: {}", q.title()),
+            Err(e) => e.to_string(),
+        };
+        self.quest_state(msg, vec![])
+    }
+
+    #[wasm_bindgen]
+    pub fn quest_abandon(&mut self, id: u32) -> JsValue {
+        let msg = match self.inv.quests.abandon(id) {
+            Some(q) => format!("Quest abandoned: {}", q.title()),
+            None => "No such active quest".to_string(),
+        };
+        self.quest_state(msg, vec![])
+    }
+
+    /// Turn in every completed quest at the tavern.
+    #[wasm_bindgen]
+    pub fn quest_claim(&mut self) -> JsValue {
+        let lines = quest::claim_rewards(&mut self.inv);
+        let msg = if lines.is_empty() {
+            "Nothing to claim yet"
         } else {
-            (None, 0, 0)
+            "Quest rewards claimed"
+        };
+        self.quest_state(msg, lines)
+    }
+
+    // --- Dungeon crawl ---
+    fn dungeon_state(&self, message: impl Into<String>, lines: Vec<String>) -> JsValue {
+        let d = self.inv.dungeon.as_ref();
+        let exits = d
+            .map(|d| {
+                d.exits()
+                    .into_iter()
+                    .map(|e| DungeonExit {
+                        room: e.room as u32,
+                        direction: e.direction.to_string(),
+                        label: e.label,
+                        locked: e.door == Door::Locked,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let ds = WasmDungeonState {
+            state: WasmInventory::from(self.inv.clone()),
+            message: message.into(),
+            depth: d.map_or(0, |d| d.depth),
+            room: d.map_or(0, |d| d.current as u32),
+            room_label: d.map(|d| d.room().label()).unwrap_or_default(),
+            explored: d.map_or(0, |d| d.explored_count() as u32),
+            rooms: d.map_or(0, |d| d.rooms.len() as u32),
+            exits,
+            can_descend: d.is_some_and(|d| d.room().content == RoomContent::Stairs),
+            in_fight: self.active_fight.is_some(),
+            monster: self.active_fight.as_ref().map(|f| f.title()),
+            lines,
         };
+        serde_wasm_bindgen::to_value(&ds).unwrap()
+    }
+
+    /// Enter (or resume) the dungeon; the first visit generates level 1.
+    #[wasm_bindgen]
+    pub fn dungeon_enter(&mut self) -> JsValue {
+        let depth = dungeon::enter(&mut self.inv).depth;
+        self.dungeon_state(format!("Dungeon level {}", depth), vec![])
+    }
+
+    /// Move to a neighbouring room by id; a guarded room starts a fight (continue with `fight_attack`).
+    #[wasm_bindgen]
+    pub fn dungeon_move(&mut self, room: u32) -> JsValue {
+        if self.active_fight.is_some() {
+            return self.dungeon_state("You are in battle", vec![]);
+        }
+        let loot = random_loot_candidates();
+        match dungeon::move_to(&mut self.inv, room as usize, &loot) {
+            Ok(report) => {
+                let mut lines = report.lines;
+                let msg = match report.encounter {
+                    Some(monster) => {
+                        combat::prepare_player(&mut self.inv);
+                        let mut enc = Encounter::new(vec![monster]);
+                        enc.in_dungeon = true;
+                        self.active_fight = Some(enc);
+                        lines.push(format!(
+                            "A {} appears with {} HP!",
+                            monster.name,
+                            monster.max_hp()
+                        ));
+                        format!("A {} blocks the way!", monster.name)
+                    }
+                    None => self
+                        .inv
+                        .dungeon
+                        .as_ref()
+                        .map(|d| format!("You enter the {}", d.room().label()))
+                        .unwrap_or_default(),
+                };
+                self.dungeon_state(msg, lines)
+            }
+            Err(e) => self.dungeon_state(e, vec![]),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn dungeon_descend(&mut self) -> JsValue {
+        match dungeon::descend(&mut self.inv) {
+            Ok(depth) => self.dungeon_state(format!("You descend to level {}", depth), vec![]),
+            Err(e) => self.dungeon_state(e, vec![]),
+        }
+    }
+
+    /// ASCII map of the explored dungeon level with a legend (empty before the first visit).
+    #[wasm_bindgen]
+    pub fn render_map(&self) -> String {
+        self.inv
+            .dungeon
+            .as_ref()
+            .map(crate::map::render_map)
+            .unwrap_or_default()
+    }
+
+    // --- Interactive fight API (browser) ---
+    fn fight_state(&self, message: impl Into<String>, lines: Vec<String>) -> JsValue {
+        let combatants: Vec<CombatantView> = self
+            .active_fight
+            .iter()
+            .flat_map(|enc| enc.foes.iter().enumerate())
+            .map(|(i, f)| CombatantView {
+                index: i as u32,
+                name: f.monster.name.to_string(),
+                hp: f.hp,
+                max_hp: f.monster.max_hp(),
+                alive: f.alive(),
+                fled: f.fled,
+                ability: f.monster.ability.label().to_string(),
+            })
+            .collect();
+        let lead = combatants.iter().find(|c| c.alive);
         let fs = WasmFightState {
             inventory: WasmInventory::from(self.inv.clone()),
             message: message.into(),
             in_fight: self.active_fight.is_some(),
-            monster,
-            monster_hp: m_hp,
-            monster_max_hp: m_max,
+            monster: lead.map(|c| c.name.clone()),
+            monster_hp: lead.map_or(0, |c| c.hp),
+            monster_max_hp: lead.map_or(0, |c| c.max_hp),
             player_hp: self.inv.current_hp,
             player_max_hp: self.inv.max_hp,
+            combatants,
             lines,
         };
         serde_wasm_bindgen::to_value(&fs).unwrap()
@@ -505,168 +1578,130 @@ impl Game {
         if self.active_fight.is_some() {
             return self.fight_state("Already in battle", vec![]);
         }
-        // Initialize player hp if needed
-        if self.inv.max_hp == 0 {
-            self.inv.max_hp = 20;
-        }
-        if self.inv.current_hp == 0 || self.inv.current_hp > self.inv.max_hp {
-            self.inv.current_hp = self.inv.max_hp;
-        }
-        // Monster table (mirror of CLI)
-        const MONSTERS: &[crate::actions::Monster] = &[
-            crate::actions::Monster {
-                name: "Goblin Sneak",
-                strength: 1,
-            },
-            crate::actions::Monster {
-                name: "Cave Rat",
-                strength: 1,
-            },
-            crate::actions::Monster {
-                name: "Skeleton Guard",
-                strength: 2,
-            },
-            crate::actions::Monster {
-                name: "Orc Marauder",
-                strength: 3,
-            },
-            crate::actions::Monster {
-                name: "Ghoul",
-                strength: 4,
-            },
-            crate::actions::Monster {
-                name: "Ogre Brute",
-                strength: 5,
-            },
-            crate::actions::Monster {
-                name: "Wyvern",
-                strength: 6,
-            },
-            crate::actions::Monster {
-                name: "Vampire Stalker",
-                strength: 7,
-            },
-            crate::actions::Monster {
-                name: "Stone Golem",
-                strength: 8,
-            },
-            crate::actions::Monster {
-                name: "Ancient Lich",
-                strength: 9,
-            },
-            crate::actions::Monster {
-                name: "Dragon Wyrm",
-                strength: 10,
-            },
-        ];
-        let mut rng = rand::rngs::SmallRng::from_entropy();
-        use rand::seq::SliceRandom;
-        let monster = *MONSTERS.choose(&mut rng).unwrap();
-        let encounter = FightEncounter {
-            monster,
-            monster_hp: monster.max_hp(),
-        };
-        self.active_fight = Some(encounter);
-        self.fight_state(
-            format!("A {} appears!", monster.name),
-            vec![format!(
-                "A {} appears with {} HP!",
-                monster.name,
-                monster.max_hp()
-            )],
-        )
+        combat::prepare_player(&mut self.inv);
+        let tod = self.inv.clock.time_of_day();
+        let enc = Encounter::new(difficulty::scaled_encounter(&self.inv, tod));
+        let lines = enc
+            .foes
+            .iter()
+            .map(|f| format!("A {} appears with {} HP!", f.monster.name, f.hp))
+            .collect();
+        let msg = format!("You face {}!", enc.title());
+        self.active_fight = Some(enc);
+        self.fight_state(msg, lines)
     }
 
+    /// Attack the foe at `target` (index into `combatants`; first living foe if omitted).
+    /// Every living foe strikes back.
     #[wasm_bindgen]
-    pub fn fight_attack(&mut self) -> JsValue {
-        if self.active_fight.is_none() {
+    pub fn fight_attack(&mut self, target: Option<u32>) -> JsValue {
+        let Some(mut enc) = self.active_fight.take() else {
             return self.fight_state("No active fight", vec![]);
-        }
-        let mut rng = rand::rngs::SmallRng::from_entropy();
-        // Player attack
-        let mut lines: Vec<String> = Vec::new();
-        if let Some(enc) = &mut self.active_fight {
-            let dmg = rng.gen_range(2..=6);
-            enc.monster_hp = enc.monster_hp.saturating_sub(dmg);
-            lines.push(format!(
-                "You strike the {} for {} damage",
-                enc.monster.name, dmg
-            ));
-            if enc.monster_hp == 0 {
-                // Victory & reward
-                let min_gp = (10 * (enc.monster.strength as u32).max(1)).max(5);
-                let max_gp = (40 * enc.monster.strength as u32).min(400).max(min_gp + 5);
-                let reward = rng.gen_range(min_gp..=max_gp);
-                self.inv.gold_pieces = self.inv.gold_pieces.saturating_add(reward);
-                self.inv.save_after_pickup();
-                let name = enc.monster.name.to_string();
-                self.active_fight = None;
-                lines.push(format!("You defeat the {} and gain {} gp", name, reward));
-                return self.fight_state(format!("Victory over {}", name), lines);
+        };
+        self.advance(FIGHT_ROUND_MINUTES);
+        let (lines, status) = enc.round(&mut self.inv, target.map(|t| t as usize));
+        self.finish_round(enc, lines, status)
+    }
+
+    /// Spend the round on guard (higher AC, half fire damage) while every foe attacks.
+    #[wasm_bindgen]
+    pub fn fight_defend(&mut self) -> JsValue {
+        let Some(mut enc) = self.active_fight.take() else {
+            return self.fight_state("No active fight", vec![]);
+        };
+        self.advance(FIGHT_ROUND_MINUTES);
+        let (lines, status) = enc.defend(&mut self.inv);
+        self.finish_round(enc, lines, status)
+    }
+
+    fn finish_round(
+        &mut self,
+        enc: Encounter,
+        mut lines: Vec<String>,
+        status: FightStatus,
+    ) -> JsValue {
+        match status {
+            FightStatus::Ongoing => {
+                self.active_fight = Some(enc);
+                self.fight_state("Exchange blows", lines)
             }
-        }
-        // Monster retaliates if still alive
-        if let Some(enc) = &mut self.active_fight {
-            let dmg = rng.gen_range(enc.monster.damage_range());
-            self.inv.current_hp = self.inv.current_hp.saturating_sub(dmg);
-            lines.push(format!(
-                "The {} hits you for {} damage",
-                enc.monster.name, dmg
-            ));
-            if self.inv.current_hp == 0 {
-                // Defeat penalty: 10% gold & up to 3 items, restore hp
-                let before_gp = self.inv.gold_pieces;
-                let loss = ((before_gp as f64) * 0.10).round() as u32;
-                let loss = loss.clamp(0, self.inv.gold_pieces);
-                self.inv.gold_pieces -= loss;
-                // Remove up to 3 random items
-                for _ in 0..3 {
-                    if self.inv.items.is_empty() {
-                        break;
-                    }
-                    let idx = rng.gen_range(0..self.inv.items.len());
-                    self.inv.items.remove(idx);
+            FightStatus::Victory => {
+                let spoils = combat::settle_victory(&mut self.inv, &enc);
+                if enc.in_dungeon {
+                    dungeon::clear_current(&mut self.inv);
                 }
-                self.inv.current_hp = self.inv.max_hp; // restore
-                self.inv.save_after_pickup();
-                let name = enc.monster.name.to_string();
-                self.active_fight = None;
-                lines.push(format!("You are defeated by {} (-{} gp)", name, loss));
-                return self.fight_state(format!("Defeated by {}", name), lines);
+                lines.push(format!(
+                    "You defeat {} and gain {} gp",
+                    enc.title(),
+                    spoils.gold
+                ));
+                if !spoils.drops.is_empty() {
+                    lines.push(format!(
+                        "Drops: {}",
+                        format_items_for_display(&spoils.drops)
+                    ));
+                }
+                lines.extend(spoils.notes);
+                self.fight_state(format!("Victory over {}", enc.title()), lines)
+            }
+            FightStatus::Escaped => {
+                lines.push(format!("{} got away: no spoils", enc.title()));
+                self.fight_state("Your foes escape", lines)
             }
+            FightStatus::Defeat => match combat::settle_fall(&mut self.inv, &enc) {
+                Fall::KnockedOut { loss, .. } => {
+                    if enc.in_dungeon {
+                        dungeon::return_to_entrance(&mut self.inv);
+                    }
+                    lines.push(format!(
+                        "You are defeated by {} (-{} gp)",
+                        enc.title(),
+                        loss
+                    ));
+                    self.fight_state(format!("Defeated by {}", enc.title()), lines)
+                }
+                Fall::Perished(grave) => {
+                    lines.push(grave.epitaph());
+                    lines.push("A new adventurer steps forward...".into());
+                    self.graves.push(grave);
+                    self.fight_state("You have died", lines)
+                }
+            },
         }
-        self.fight_state("Exchange blows", lines)
     }
 
     #[wasm_bindgen]
     pub fn fight_flee(&mut self) -> JsValue {
-        if self.active_fight.is_none() {
+        let Some(enc) = self.active_fight.take() else {
             return self.fight_state("No active fight", vec![]);
+        };
+        if let Some(why) = encumbrance::flee_blocked(&self.inv) {
+            self.active_fight = Some(enc);
+            return self.fight_state(why.clone(), vec![why]);
+        }
+        self.advance(FIGHT_ROUND_MINUTES);
+        let (gold_loss, _) = combat::settle_flee(&mut self.inv);
+        if enc.in_dungeon {
+            dungeon::retreat(&mut self.inv);
         }
-        // Flee penalty: lose 5% gold (rounded) & 1 random item
-        let mut rng = rand::rngs::SmallRng::from_entropy();
-        let before_gold = self.inv.gold_pieces;
-        let gold_loss = ((before_gold as f64) * 0.05).round() as u32;
-        let gold_loss = gold_loss.clamp(0, self.inv.gold_pieces);
-        self.inv.gold_pieces -= gold_loss;
-        if !self.inv.items.is_empty() {
-            let idx = rng.gen_range(0..self.inv.items.len());
-            self.inv.items.remove(idx);
-        }
-        self.inv.save_after_pickup();
-        self.active_fight = None;
         self.fight_state(
-            format!("You flee"),
+            "You flee".to_string(),
             vec![format!("You flee, dropping {} gp", gold_loss)],
         )
     }
 
     #[wasm_bindgen]
     pub fn fight_quit(&mut self) -> JsValue {
-        if self.active_fight.is_none() {
+        let Some(enc) = self.active_fight.take() else {
             return self.fight_state("No active fight", vec![]);
+        };
+        if let Some(why) = encumbrance::flee_blocked(&self.inv) {
+            self.active_fight = Some(enc);
+            return self.fight_state(why.clone(), vec![why]);
+        }
+        if enc.in_dungeon {
+            dungeon::retreat(&mut self.inv);
         }
-        self.active_fight = None;
         self.fight_state("You withdraw", vec!["You withdraw from the battle".into()])
     }
 }
@@ -0,0 +1,120 @@
+mod common;
+
+use common::carrying;
+use dungeon_core::{
+    appraisal::{self, AppraisalError, IDENTIFY_SCROLL, MYSTERIES},
+    bank,
+    rng::reseed,
+    shop::{self, ShopKind},
+};
+
+fn identities(appearance: &str) -> Vec<&'static str> {
+    MYSTERIES
+        .iter()
+        .find(|m| m.appearance == appearance)
+        .unwrap()
+        .identities
+        .iter()
+        .map(|(name, _, _)| *name)
+        .collect()
+}
+
+#[test]
+fn unidentified_goods_fetch_little_until_appraised() {
+    reseed(48);
+    let inv = carrying(&["Murky potion"]);
+    for _ in 0..20 {
+        let mystery = shop::sell_offer_cp(ShopKind::Alchemist, &inv, 0).unwrap();
+        assert!(mystery <= *appraisal::UNIDENTIFIED_OFFER_CP.end());
+    }
+    // Identities no catalog stocks are still priced by their rarity
+    let ring = carrying(&["Moonstone ring"]);
+    assert!(shop::sell_offer_cp(ShopKind::General, &ring, 0).unwrap() >= 40);
+}
+
+#[test]
+fn a_shop_appraisal_costs_its_fee_and_keeps_the_item_in_place() {
+    reseed(3);
+    let mut inv = carrying(&["Torch", "jeweled clasp", "Rope (50ft)"]);
+    inv.set_stolen_at(1, true);
+    assert_eq!(
+        appraisal::appraise_at_shop(&mut inv, ShopKind::MagicEmporium, 1),
+        Err(AppraisalError::InsufficientFunds)
+    );
+    inv.gold_pieces = 1;
+    assert_eq!(
+        appraisal::appraise_at_shop(&mut inv, ShopKind::General, 1),
+        Err(AppraisalError::NoAppraiser)
+    );
+    assert_eq!(
+        appraisal::appraise_at_shop(&mut inv, ShopKind::MagicEmporium, 0),
+        Err(AppraisalError::AlreadyKnown)
+    );
+    let found = appraisal::appraise_at_shop(&mut inv, ShopKind::MagicEmporium, 1).unwrap();
+    let fee = ShopKind::MagicEmporium.catalog().appraisal_fee_cp.unwrap();
+    assert_eq!(found.fee_cp, fee);
+    assert_eq!(inv.total_cp(), 100 - fee);
+    assert_eq!(inv.items[1], found.now);
+    assert!(identities("Jeweled clasp").contains(&found.now.as_str()));
+    assert!(inv.is_stolen_at(1) && !inv.is_stolen_at(0));
+    assert!(appraisal::unidentified_indices(&inv).is_empty());
+}
+
+#[test]
+fn reading_a_scroll_of_identify_uses_it_up() {
+    let mut inv = carrying(&[IDENTIFY_SCROLL, "Strange wand"]);
+    let found = appraisal::read_identify_scroll(&mut inv, 1).unwrap();
+    assert_eq!(found.fee_cp, 0);
+    assert_eq!(inv.items, vec![found.now.clone()]);
+    assert!(identities("Strange wand").contains(&found.now.as_str()));
+
+    let mut plain = carrying(&["Vellum scroll"]);
+    assert_eq!(
+        appraisal::read_identify_scroll(&mut plain, 0),
+        Err(AppraisalError::NoScroll)
+    );
+    assert_eq!(
+        appraisal::read_identify_scroll(&mut plain, 4),
+        Err(AppraisalError::NoSuchItem)
+    );
+}
+
+#[test]
+fn only_magic_loot_can_arrive_disguised() {
+    reseed(11);
+    assert_eq!(
+        appraisal::disguise("Stale bread"),
+        ("Stale bread".into(), None)
+    );
+    assert_eq!(
+        appraisal::disguise("Copper ring"),
+        ("Copper ring".into(), None)
+    );
+    let rolls: Vec<(String, Option<String>)> = (0..40)
+        .map(|_| appraisal::disguise("Potion of Healing"))
+        .collect();
+    let healing = Some("Potion of Healing".to_string());
+    assert!(rolls.contains(&("Murky potion".into(), healing)));
+    assert!(rolls.contains(&("Potion of Healing".into(), None)));
+    // Whatever a clasp turns out to be, a quest to lift a jeweled clasp still counts it
+    for name in identities("Jeweled clasp") {
+        assert!(name.to_lowercase().contains("jeweled clasp"));
+    }
+}
+
+#[test]
+fn disguised_loot_turns_out_to_be_what_it_always_was() {
+    for seed in 0..20 {
+        reseed(seed);
+        let mut inv = carrying(&[IDENTIFY_SCROLL]);
+        inv.push_disguised("Murky potion".into(), Some("Elixir of Luck".into()), true);
+        // Banking it and taking it back out doesn't lose what it is
+        bank::store_item(&mut inv, 1).unwrap();
+        bank::retrieve_item(&mut inv, 0).unwrap();
+        let found = appraisal::read_identify_scroll(&mut inv, 1).unwrap();
+        assert_eq!(found.now, "Elixir of Luck");
+        assert_eq!(inv.items, vec!["Elixir of Luck".to_string()]);
+        assert!(inv.is_stolen_at(0));
+        assert_eq!(inv.true_name_at(0), None);
+    }
+}
@@ -0,0 +1,84 @@
+use dungeon_core::{
+    bank::{self, BankError, VAULT_FEE_CP_PER_DAY},
+    bestiary::find,
+    combat::{self, Encounter},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+fn saver() -> Inventory {
+    let mut inv = Inventory::new();
+    inv.gold_pieces = 100;
+    inv
+}
+
+#[test]
+fn deposits_and_withdrawals_move_coin_between_purse_and_account() {
+    let mut inv = saver();
+    assert_eq!(bank::deposit(&mut inv, 6_000), Ok(6_000));
+    assert_eq!((inv.total_cp(), inv.bank.balance_cp), (4_000, 6_000));
+    assert_eq!(
+        bank::deposit(&mut inv, 5_000),
+        Err(BankError::InsufficientFunds)
+    );
+    assert_eq!(
+        bank::withdraw(&mut inv, 7_000),
+        Err(BankError::InsufficientBalance)
+    );
+    assert_eq!(bank::withdraw(&mut inv, 0), Err(BankError::ZeroAmount));
+    assert_eq!(bank::withdraw(&mut inv, 1_000), Ok(1_000));
+    assert_eq!((inv.total_cp(), inv.bank.balance_cp), (5_000, 5_000));
+}
+
+#[test]
+fn only_carried_coin_is_at_risk() {
+    reseed(42);
+    let mut inv = saver();
+    bank::deposit(&mut inv, 8_000).unwrap();
+    inv.items = vec!["Lantern".into()];
+    bank::store_item(&mut inv, 0).unwrap();
+    let enc = Encounter::new(vec![find("Ogre Brute").unwrap()]);
+    let _ = combat::settle_fall(&mut inv, &enc);
+    let _ = combat::settle_flee(&mut inv);
+    assert!(inv.gold_pieces < 20);
+    assert_eq!(inv.bank.balance_cp, 8_000);
+    assert_eq!(inv.bank.vault.len(), 1);
+}
+
+#[test]
+fn savings_earn_interest_and_the_vault_charges_by_the_day() {
+    let mut inv = saver();
+    bank::deposit(&mut inv, 10_000).unwrap();
+    // Less than a day: nothing settles yet
+    inv.advance_time(23 * 60);
+    assert_eq!(inv.bank.balance_cp, 10_000);
+    inv.advance_time(60);
+    assert_eq!(inv.bank.balance_cp, 10_050);
+
+    let mut inv = Inventory::new();
+    inv.items = vec!["Rope".into(), "Torch".into()];
+    bank::store_item(&mut inv, 0).unwrap();
+    bank::store_item(&mut inv, 0).unwrap();
+    let notes = inv.advance_time(2 * 24 * 60);
+    assert_eq!(inv.bank.fees_owed_cp, 4 * VAULT_FEE_CP_PER_DAY);
+    assert!(notes.iter().any(|n| n.contains("overdue")));
+}
+
+#[test]
+fn overdue_fees_hold_the_vault_and_stolen_goods_stay_hot() {
+    let mut inv = saver();
+    inv.items = vec!["Silver Ring".into()];
+    inv.set_stolen_at(0, true);
+    bank::store_item(&mut inv, 0).unwrap();
+    assert!(inv.items.is_empty() && inv.stolen.is_empty());
+    inv.advance_time(24 * 60);
+    let owed = inv.bank.fees_owed_cp;
+    assert_eq!(
+        bank::retrieve_item(&mut inv, 0),
+        Err(BankError::FeesOwed(owed))
+    );
+    // A deposit settles the debt before anything is credited
+    assert_eq!(bank::deposit(&mut inv, 100), Ok(100 - owed));
+    assert_eq!(bank::retrieve_item(&mut inv, 0), Ok("Silver Ring".into()));
+    assert!(inv.is_stolen_at(0));
+}
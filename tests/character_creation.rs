@@ -0,0 +1,114 @@
+use dungeon_core::{
+    actions::{pickpocket_success_chance, tavern_luck_chance},
+    bestiary::find,
+    character::{self, Background, CharacterClass},
+    combat::{self, Encounter},
+    difficulty::Difficulty,
+    haggle::{HaggleContext, Mood, Negotiation},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+fn make(class: CharacterClass) -> Inventory {
+    character::create(
+        "Tess",
+        class,
+        Background::Wanderer,
+        Difficulty::Normal,
+        false,
+    )
+    .unwrap()
+}
+
+#[test]
+fn creation_applies_class_hp_and_both_kits() {
+    let inv = character::create(
+        "  Brannoc  ",
+        CharacterClass::Fighter,
+        Background::Soldier,
+        Difficulty::Hard,
+        true,
+    )
+    .unwrap();
+    let c = inv.character.as_ref().unwrap();
+    assert_eq!(c.title(), "Brannoc the Soldier Fighter");
+    assert_eq!((inv.max_hp, inv.current_hp), (30, 30));
+    assert!(inv.items.contains(&"Longsword".to_string()));
+    assert!(inv.items.contains(&"Bedroll".to_string()));
+    assert_eq!(inv.gold_pieces, 5);
+    assert!(inv.hardcore && inv.difficulty == Difficulty::Hard);
+    assert!(combat::has_heavy_weapon(&inv));
+
+    let err = character::create(
+        "   ",
+        CharacterClass::Bard,
+        Background::Noble,
+        Difficulty::Easy,
+        false,
+    );
+    assert!(err.is_err());
+}
+
+#[test]
+fn class_shifts_pickpocket_and_tavern_odds() {
+    let plain = Inventory::new();
+    let rogue = make(CharacterClass::Rogue);
+    let fighter = make(CharacterClass::Fighter);
+    let bard = make(CharacterClass::Bard);
+    assert!(pickpocket_success_chance(&rogue) > pickpocket_success_chance(&plain));
+    assert!(pickpocket_success_chance(&fighter) < pickpocket_success_chance(&plain));
+    assert!(tavern_luck_chance(&bard) > tavern_luck_chance(&plain));
+    assert_eq!(tavern_luck_chance(&rogue), tavern_luck_chance(&plain));
+}
+
+#[test]
+fn bards_haggle_with_charisma() {
+    let bard = make(CharacterClass::Bard);
+    let ctx = HaggleContext::from_inventory(&bard, false);
+    assert_eq!(ctx.charisma, 2);
+    let deal = Negotiation::new(1000, ctx);
+    assert_eq!(deal.mood, Mood::Friendly);
+    let plain = Negotiation::new(
+        1000,
+        HaggleContext::from_inventory(&Inventory::new(), false),
+    );
+    assert!(deal.patience > plain.patience);
+}
+
+#[test]
+fn fighters_hit_surer_and_harder() {
+    reseed(40);
+    let mut inv = make(CharacterClass::Fighter);
+    inv.max_hp = 10_000;
+    inv.current_hp = 10_000;
+    let mut enc = Encounter::new(vec![find("Skeleton Guard").unwrap()]);
+    let (lines, _) = enc.round(&mut inv, None);
+    let bonus = combat::PLAYER_ATTACK_BONUS + CharacterClass::Fighter.attack_bonus();
+    assert!(lines[0].contains(&format!("+ {} = ", bonus)));
+    for _ in 0..30 {
+        let (lines, _) = enc.round(&mut inv, None);
+        for l in lines.iter().filter(|l| l.starts_with("You strike")) {
+            let dmg: u32 = l
+                .split(" for ")
+                .nth(1)
+                .unwrap()
+                .split(' ')
+                .next()
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert!(dmg >= combat::PLAYER_DAMAGE_MIN + 2, "{}", l);
+        }
+        enc.foes[0].hp = enc.foes[0].monster.max_hp();
+    }
+}
+
+#[test]
+fn character_is_saved_and_old_saves_stay_classless() {
+    let inv = make(CharacterClass::Bard);
+    let back: Inventory = serde_json::from_str(&serde_json::to_string(&inv).unwrap()).unwrap();
+    assert_eq!(back.character, inv.character);
+    let legacy = r#"{"items":[],"copper_pieces":0,"silver_pieces":0,"gold_pieces":0}"#;
+    let old: Inventory = serde_json::from_str(legacy).unwrap();
+    assert!(character::class_of(&old).is_none());
+}
@@ -0,0 +1,97 @@
+use dungeon_core::{
+    bestiary::{self, GROUPS},
+    clock::TimeOfDay,
+    combat::{self, Encounter, FightStatus},
+    inventory::Inventory,
+    quest::{Objective, Quest, QuestStatus},
+    rng::reseed,
+};
+
+fn goblin_pack() -> Encounter {
+    let g = GROUPS.iter().find(|g| g.name == "Goblin pack").unwrap();
+    Encounter::new(g.monsters())
+}
+
+#[test]
+fn groups_are_built_from_the_bestiary_and_respect_the_hour() {
+    for g in GROUPS {
+        assert_eq!(g.monsters().len(), g.members.len(), "{}", g.name);
+    }
+    reseed(34);
+    for _ in 0..200 {
+        for m in bestiary::random_encounter(TimeOfDay::Day) {
+            assert!(m.activity.active_at(TimeOfDay::Day), "{}", m.name);
+        }
+    }
+}
+
+#[test]
+fn player_picks_a_target_and_every_living_foe_strikes_back() {
+    reseed(1);
+    let mut inv = Inventory::new();
+    inv.max_hp = 10_000;
+    inv.current_hp = 10_000;
+    let mut enc = goblin_pack();
+    let (lines, status) = enc.round(&mut inv, Some(2));
+    assert_eq!(status, FightStatus::Ongoing);
+    assert!(enc.foes[2].hp < enc.foes[2].monster.max_hp());
+    assert_eq!(enc.foes[0].hp, enc.foes[0].monster.max_hp());
+    assert_eq!(lines.iter().filter(|l| l.contains("vs your AC")).count(), 3);
+    // Slain foes drop out of the retaliation
+    enc.foes[0].hp = 0;
+    let (lines, _) = enc.round(&mut inv, Some(0));
+    assert_eq!(lines.iter().filter(|l| l.contains("vs your AC")).count(), 2);
+}
+
+#[test]
+fn victory_pays_and_counts_every_foe() {
+    reseed(2);
+    let mut inv = Inventory::new();
+    inv.quests.board.push(Quest {
+        id: 1,
+        objective: Objective::Slay {
+            monster: "Skeleton Guard".into(),
+            count: 3,
+        },
+        progress: 0,
+        reward_cp: 100,
+        reward_item: None,
+        hours_left: 10,
+        status: QuestStatus::Available,
+    });
+    inv.quests.accept(1).unwrap();
+    // Skeletons never run, so all three have to be cut down
+    let mut enc = Encounter::new(vec![bestiary::find("Skeleton Guard").unwrap(); 3]);
+    let status = loop {
+        inv.current_hp = inv.max_hp;
+        let (_, status) = enc.round(&mut inv, None);
+        if status != FightStatus::Ongoing {
+            break status;
+        }
+    };
+    assert_eq!(status, FightStatus::Victory);
+    let gold = combat::settle_victory(&mut inv, &enc).gold;
+    assert!(gold >= 3 * combat::reward_range_gp(&enc.foes[0].monster).start());
+    assert_eq!(inv.gold_pieces, gold);
+    assert_eq!(inv.quests.active[0].status, QuestStatus::Complete);
+}
+
+#[test]
+fn defeat_and_flight_cost_gold_and_items() {
+    reseed(3);
+    let mut inv = Inventory::new();
+    inv.gold_pieces = 100;
+    inv.items = vec![
+        "Torch".into(),
+        "Rope".into(),
+        "Lantern".into(),
+        "Map".into(),
+    ];
+    inv.current_hp = 0;
+    let (loss, removed) = combat::settle_defeat(&mut inv);
+    assert_eq!((loss, removed.len()), (10, 3));
+    assert_eq!(inv.current_hp, inv.max_hp);
+    let (loss, removed) = combat::settle_flee(&mut inv);
+    assert_eq!((loss, removed.len()), (5, 1));
+    assert!(inv.items.is_empty());
+}
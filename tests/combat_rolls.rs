@@ -0,0 +1,78 @@
+mod common;
+
+use common::tough_player;
+use dungeon_core::{
+    bestiary::find,
+    combat::{self, Encounter},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+fn skeletons() -> Encounter {
+    Encounter::new(vec![find("Skeleton Guard").unwrap(); 2])
+}
+
+// Log of `rounds` rounds against two skeletons that never die
+fn battle_log(seed: u64, rounds: usize) -> Vec<String> {
+    reseed(seed);
+    let mut inv = tough_player();
+    let mut enc = skeletons();
+    let mut log = Vec::new();
+    for _ in 0..rounds {
+        let (lines, _) = enc.round(&mut inv, None);
+        log.extend(lines);
+        enc.foes[0].hp = enc.foes[0].monster.max_hp();
+    }
+    log
+}
+
+#[test]
+fn every_attack_reports_its_roll() {
+    let log = battle_log(36, 1);
+    let ac = find("Skeleton Guard").unwrap().armor_class();
+    assert!(log[0].starts_with("You roll "));
+    assert!(log[0].contains(&format!("+ {} = ", combat::PLAYER_ATTACK_BONUS)));
+    assert!(log[0].contains(&format!("vs AC {}", ac)));
+    let monster_rolls = log
+        .iter()
+        .filter(|l| l.starts_with("The Skeleton Guard rolls"))
+        .count();
+    assert_eq!(monster_rolls, 2);
+}
+
+#[test]
+fn crits_and_fumbles_show_up_and_replay_under_a_seed() {
+    let log = battle_log(99, 200);
+    assert!(log.iter().any(|l| l.contains("critical hit!")));
+    assert!(log.iter().any(|l| l.contains("fumble!")));
+    assert!(log.iter().any(|l| l.ends_with("— miss.")));
+    assert_eq!(log, battle_log(99, 200));
+}
+
+#[test]
+fn armor_raises_the_players_armor_class() {
+    let mut inv = Inventory::new();
+    assert_eq!(combat::player_armor_class(&inv), combat::PLAYER_BASE_AC);
+    inv.add_item("Chain Shirt");
+    inv.add_item("Wooden Shield");
+    inv.add_item("Wooden Shield");
+    assert_eq!(combat::player_armor_class(&inv), combat::PLAYER_BASE_AC + 4);
+}
+
+#[test]
+fn defending_skips_the_attack_and_raises_ac() {
+    reseed(4);
+    let mut inv = tough_player();
+    let mut enc = skeletons();
+    let (lines, _) = enc.defend(&mut inv);
+    let guarded = combat::PLAYER_BASE_AC + combat::DEFEND_AC_BONUS;
+    assert!(lines[0].contains(&format!("AC {}", guarded)));
+    assert!(lines.iter().all(|l| !l.starts_with("You strike")));
+    assert!(enc.foes.iter().all(|f| f.hp == f.monster.max_hp()));
+    assert!(
+        lines
+            .iter()
+            .filter(|l| l.contains("rolls"))
+            .all(|l| l.contains(&format!("vs your AC {}", guarded)))
+    );
+}
@@ -0,0 +1,26 @@
+// Fixtures shared by the integration tests; each test crate uses only some of them
+#![allow(dead_code)]
+
+use dungeon_core::inventory::Inventory;
+
+/// A fresh character with enough HP to outlast any fight.
+pub fn tough_player() -> Inventory {
+    let mut inv = Inventory::new();
+    inv.max_hp = 10_000;
+    inv.current_hp = 10_000;
+    inv
+}
+
+/// A fresh character carrying `items` (clean, none stolen).
+pub fn carrying(items: &[&str]) -> Inventory {
+    let mut inv = Inventory::new();
+    inv.items = items.iter().map(|s| s.to_string()).collect();
+    inv
+}
+
+/// Send headstones to a temp file so test runs don't litter the repo's graveyard.json.
+/// Call before anything can die.
+pub fn temp_graveyard() {
+    let file = std::env::temp_dir().join(format!("dungeon-graveyard-{}.json", std::process::id()));
+    dungeon_core::graveyard::set_path(file);
+}
@@ -0,0 +1,91 @@
+use dungeon_core::{
+    actions::pickpocket_success_chance,
+    bestiary::find,
+    combat::{self, Encounter, FightStatus},
+    companion::{self, CompanionKind, HireError, MORALE_START, PARTY_MAX},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+const DAY_MINUTES: u64 = 24 * 60;
+
+#[test]
+fn hiring_pays_the_first_day_up_front() {
+    reseed(46);
+    let mut inv = Inventory::new();
+    inv.gold_pieces = 10;
+    let title = companion::hire(&mut inv, CompanionKind::Sellsword).unwrap();
+    assert!(title.ends_with("the Sellsword"));
+    assert_eq!(
+        inv.total_cp(),
+        1_000 - CompanionKind::Sellsword.daily_wage_cp()
+    );
+    companion::hire(&mut inv, CompanionKind::Healer).unwrap();
+    assert_eq!(inv.party.companions.len(), PARTY_MAX);
+    assert_eq!(
+        companion::hire(&mut inv, CompanionKind::ApprenticeThief),
+        Err(HireError::PartyFull)
+    );
+    let gone = companion::dismiss(&mut inv, 0).unwrap();
+    assert_eq!(gone.kind, CompanionKind::Sellsword);
+
+    let mut broke = Inventory::new();
+    assert_eq!(
+        companion::hire(&mut broke, CompanionKind::Healer),
+        Err(HireError::InsufficientFunds)
+    );
+    assert!(broke.party.is_empty());
+}
+
+#[test]
+fn wages_come_out_daily_and_unpaid_companions_desert() {
+    let mut inv = Inventory::new();
+    let wage = CompanionKind::ApprenticeThief.daily_wage_cp();
+    inv.add_copper(wage * 2);
+    companion::hire(&mut inv, CompanionKind::ApprenticeThief).unwrap();
+    inv.advance_time(DAY_MINUTES);
+    assert_eq!(inv.total_cp(), 0);
+    assert!(inv.party.companions[0].morale > MORALE_START);
+
+    let notes = inv.advance_time(DAY_MINUTES);
+    assert_eq!(inv.party.companions[0].owed_cp, wage);
+    assert!(notes.iter().any(|n| n.contains("couldn't pay")));
+    let notes = inv.advance_time(DAY_MINUTES);
+    assert!(inv.party.is_empty());
+    assert!(notes.iter().any(|n| n.contains("deserted")));
+}
+
+#[test]
+fn defeats_sap_morale_until_companions_walk_out() {
+    let mut inv = Inventory::new();
+    inv.gold_pieces = 100;
+    companion::hire(&mut inv, CompanionKind::Sellsword).unwrap();
+    combat::settle_defeat(&mut inv);
+    assert!(inv.advance_time(0).is_empty());
+    combat::settle_defeat(&mut inv);
+    let notes = inv.advance_time(0);
+    assert!(inv.party.is_empty());
+    assert!(notes.iter().any(|n| n.contains("lost faith")));
+}
+
+#[test]
+fn companions_keep_watch_and_join_the_fight() {
+    reseed(7);
+    let mut inv = Inventory::new();
+    let alone = pickpocket_success_chance(&inv);
+    inv.gold_pieces = 10;
+    companion::hire(&mut inv, CompanionKind::ApprenticeThief).unwrap();
+    assert!(pickpocket_success_chance(&inv) > alone);
+
+    let name = inv.party.companions[0].name.clone();
+    let mut enc = Encounter::new(vec![find("Skeleton Guard").unwrap()]);
+    let mut log = Vec::new();
+    for _ in 0..30 {
+        let (lines, status) = enc.round(&mut inv, None);
+        log.extend(lines);
+        if status != FightStatus::Ongoing {
+            break;
+        }
+    }
+    assert!(log.iter().any(|l| l.starts_with(&name)));
+}
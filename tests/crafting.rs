@@ -0,0 +1,82 @@
+mod common;
+
+use common::carrying;
+use dungeon_core::crafting::{self, CraftError};
+
+#[test]
+fn recipe_file_loads_with_unique_ids() {
+    let recipes = crafting::recipes();
+    assert!(recipes.len() >= 5);
+    for r in recipes {
+        assert_eq!(recipes.iter().filter(|o| o.id == r.id).count(), 1);
+        assert!(!r.ingredients.is_empty() && !r.hint.is_empty());
+    }
+    let healing = crafting::recipe("healing_draught").unwrap();
+    assert_eq!(healing.makes, "Potion of Healing");
+    assert!(healing.formula().contains("Empty vial"));
+}
+
+#[test]
+fn experimenting_discovers_a_recipe_that_can_then_be_repeated() {
+    let mut inv = carrying(&["Herb Bundle", "Torch", "Empty flask (3 cp)"]);
+    assert_eq!(
+        crafting::craft(&mut inv, "healing_draught"),
+        Err(CraftError::UnknownRecipe)
+    );
+    let made = crafting::combine(&mut inv, &[0, 2]).unwrap();
+    assert!(made.discovered);
+    assert_eq!(made.used, vec!["Herb Bundle", "Empty flask (3 cp)"]);
+    assert_eq!(inv.items, vec!["Torch", "Potion of Healing"]);
+    assert!(inv.recipes.knows("healing_draught"));
+    assert_eq!(inv.stats.items_crafted, 1);
+
+    inv.items
+        .extend(["Mushrooms".to_string(), "Empty vial".to_string()]);
+    assert_eq!(crafting::craftable(&inv).len(), 1);
+    let again = crafting::craft(&mut inv, "healing_draught").unwrap();
+    assert!(!again.discovered);
+    assert_eq!(
+        inv.items
+            .iter()
+            .filter(|i| *i == "Potion of Healing")
+            .count(),
+        2
+    );
+    assert!(matches!(
+        crafting::craft(&mut inv, "healing_draught"),
+        Err(CraftError::MissingIngredients(_))
+    ));
+}
+
+#[test]
+fn tools_are_needed_but_not_used_up() {
+    let mut inv = carrying(&["Dull knife", "Whetstone (2 cp)"]);
+    // The whetstone isn't an ingredient, so offering it as one combines into nothing
+    assert_eq!(
+        crafting::combine(&mut inv, &[0, 1]),
+        Err(CraftError::NothingHappens)
+    );
+    let made = crafting::combine(&mut inv, &[0]).unwrap();
+    assert_eq!(made.made, "Sharp knife");
+    assert_eq!(inv.items, vec!["Whetstone (2 cp)", "Sharp knife"]);
+
+    let mut blunt = carrying(&["Dull knife"]);
+    assert_eq!(
+        crafting::combine(&mut blunt, &[0]),
+        Err(CraftError::NothingHappens)
+    );
+    assert_eq!(
+        crafting::combine(&mut blunt, &[3]),
+        Err(CraftError::NoSuchItem)
+    );
+}
+
+#[test]
+fn poisonous_mushrooms_and_stolen_goods_carry_through() {
+    let mut inv = carrying(&["Mushrooms (poisonous)", "Empty vial"]);
+    inv.set_stolen_at(1, true);
+    let made = crafting::combine(&mut inv, &[0, 1]).unwrap();
+    assert_eq!(made.made, "Poison Vial");
+    assert_eq!(inv.items, vec!["Poison Vial".to_string()]);
+    assert!(inv.is_stolen_at(0));
+}
@@ -0,0 +1,102 @@
+use dungeon_core::{
+    currency::{self, CurrencyError, CurrencyTable},
+    inventory::Inventory,
+    loot::{format_items_for_display, parse_loot_into_items},
+};
+
+#[test]
+fn loot_reads_platinum_and_electrum() {
+    let items = parse_loot_into_items("A platinum coin, 3 electrum pieces, 2 pp and a crude map");
+    assert_eq!(items, vec!["1 pp", "3 ep", "2 pp", "Crude map"]);
+    assert_eq!(
+        format_items_for_display(&items[..2]),
+        "1 platinum piece and 3 electrum pieces"
+    );
+    let mut inv = Inventory::new();
+    for it in &items {
+        inv.add_item(it);
+    }
+    assert_eq!((inv.coin_count("pp"), inv.coin_count("ep")), (3, 3));
+    assert_eq!(inv.items, vec!["Crude map"]);
+    assert_eq!(inv.total_cp(), 3_150);
+}
+
+#[test]
+fn a_coin_wrapped_in_something_is_both() {
+    assert_eq!(
+        parse_loot_into_items("A platinum coin wrapped in a crude map, a salted fish"),
+        vec!["1 pp", "Crude map", "Salted fish"]
+    );
+    // Only coins come apart from their wrapping
+    assert_eq!(
+        parse_loot_into_items("A dagger wrapped in silk"),
+        vec!["Dagger wrapped in silk"]
+    );
+}
+
+#[test]
+fn spending_hands_over_exact_coins_before_breaking_large_ones() {
+    let mut inv = Inventory::new();
+    inv.add_item("2 pp");
+    inv.add_item("1 ep");
+    inv.add_item("3 gp");
+    // 2 gp and the electrum piece make 250 cp exactly
+    assert!(inv.try_spend_cp(250));
+    assert_eq!((inv.coin_count("pp"), inv.coin_count("ep")), (2, 0));
+    assert_eq!(inv.gold_pieces, 1);
+    // The last gold piece isn't enough: one platinum is broken for change
+    assert!(inv.try_spend_cp(500));
+    assert_eq!(inv.coin_count("pp"), 1);
+    assert_eq!(inv.gold_pieces, 6);
+    assert_eq!(inv.total_cp(), 1_600);
+    assert!(!inv.try_spend_cp(5_000));
+}
+
+#[test]
+fn the_money_changer_takes_a_cut_and_gives_change() {
+    let mut inv = Inventory::new();
+    inv.gold_pieces = 25;
+    let x = currency::exchange(&mut inv, "gp", 25, "pp").unwrap();
+    // 2500 cp less 125 cp fee buys 2 pp with 375 cp over
+    assert_eq!((x.received, x.fee_cp, x.change_cp), (2, 125, 375));
+    assert_eq!(inv.coin_count("pp"), 2);
+    assert_eq!(inv.total_cp(), 2_375);
+    assert!(x.summary().contains("2 platinum pieces"));
+    assert_eq!(
+        currency::exchange(&mut inv, "pp", 5, "gp"),
+        Err(CurrencyError::InsufficientCoins)
+    );
+    assert_eq!(
+        currency::exchange(&mut inv, "sp", 1, "pp"),
+        Err(CurrencyError::TooSmall)
+    );
+    assert_eq!(
+        currency::exchange(&mut inv, "zz", 1, "gp"),
+        Err(CurrencyError::UnknownCoin("zz".into()))
+    );
+}
+
+#[test]
+fn custom_tables_must_keep_the_base_coins() {
+    let json = r#"{"name": "Crowns", "denominations": [
+        {"code": "cp", "singular": "copper penny", "plural": "copper pennies", "value_cp": 1},
+        {"code": "cr", "singular": "crown", "plural": "crowns", "value_cp": 500},
+        {"code": "sp", "singular": "silver shilling", "plural": "silver shillings", "value_cp": 10},
+        {"code": "gp", "singular": "gold sovereign", "plural": "gold sovereigns", "value_cp": 100}
+    ]}"#;
+    let table = CurrencyTable::from_json(json).unwrap();
+    let codes: Vec<&str> = table
+        .denominations
+        .iter()
+        .map(|d| d.code.as_str())
+        .collect();
+    assert_eq!(codes, vec!["cr", "gp", "sp", "cp"]);
+    assert_eq!(table.extra().count(), 1);
+
+    let no_gold = json.replace(r#""value_cp": 100"#, r#""value_cp": 120"#);
+    assert!(matches!(
+        CurrencyTable::from_json(&no_gold),
+        Err(CurrencyError::Invalid(_))
+    ));
+    assert!(CurrencyTable::standard().validate().is_ok());
+}
@@ -0,0 +1,91 @@
+use dungeon_core::{
+    bestiary::find,
+    clock::TimeOfDay,
+    combat::{self, Encounter},
+    difficulty::{self, Difficulty, OVERREACH, XP_PER_LEVEL},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+#[test]
+fn fresh_characters_never_meet_a_dragon() {
+    reseed(37);
+    let inv = Inventory::new();
+    let cap = difficulty::threat_cap(&inv);
+    assert_eq!(cap, 3);
+    for _ in 0..500 {
+        for m in difficulty::scaled_encounter(&inv, TimeOfDay::Day) {
+            assert!((m.strength as u32) <= cap + OVERREACH, "{}", m.name);
+        }
+        let m = difficulty::scaled_monster(&inv, TimeOfDay::Day);
+        assert!((m.strength as u32) <= cap + OVERREACH, "{}", m.name);
+    }
+}
+
+#[test]
+fn threat_cap_follows_level_gear_hp_and_difficulty() {
+    let mut inv = Inventory::new();
+    let base = difficulty::threat_cap(&inv);
+    inv.experience = 3 * XP_PER_LEVEL;
+    let levelled = difficulty::threat_cap(&inv);
+    assert!(levelled > base);
+    inv.add_item("Warhammer");
+    inv.add_item("Chain Shirt");
+    let geared = difficulty::threat_cap(&inv);
+    assert!(geared > levelled);
+    inv.current_hp = 1;
+    assert!(difficulty::threat_cap(&inv) < geared);
+    inv.current_hp = inv.max_hp;
+    inv.difficulty = Difficulty::Easy;
+    let easy = difficulty::threat_cap(&inv);
+    inv.difficulty = Difficulty::Hard;
+    assert!(difficulty::threat_cap(&inv) > easy);
+}
+
+fn slain_skeleton() -> Encounter {
+    let mut enc = Encounter::new(vec![find("Skeleton Guard").unwrap()]);
+    enc.foes[0].hp = 0;
+    enc
+}
+
+#[test]
+fn rewards_and_penalties_scale_with_difficulty() {
+    let run = |d: Difficulty| {
+        reseed(8);
+        let mut inv = Inventory::new();
+        inv.difficulty = d;
+        let gold = combat::settle_victory(&mut inv, &slain_skeleton()).gold;
+        inv.gold_pieces = 1000;
+        inv.items = (0..6).map(|i| format!("Trinket {}", i)).collect();
+        let (loss, removed) = combat::settle_defeat(&mut inv);
+        (gold, loss, removed.len())
+    };
+    let (easy_gold, easy_loss, easy_items) = run(Difficulty::Easy);
+    let (gold, loss, items) = run(Difficulty::Normal);
+    let (hard_gold, hard_loss, hard_items) = run(Difficulty::Hard);
+    assert!(easy_gold < gold && gold < hard_gold);
+    assert_eq!((easy_loss, loss, hard_loss), (50, 100, 150));
+    assert!(easy_items < items && items < hard_items);
+}
+
+#[test]
+fn victories_earn_experience_and_levels() {
+    let mut inv = Inventory::new();
+    inv.experience = XP_PER_LEVEL - 5;
+    let notes = combat::settle_victory(&mut inv, &slain_skeleton()).notes;
+    assert_eq!(inv.level(), 2);
+    assert!(notes.iter().any(|n| n.contains("level 2")));
+}
+
+#[test]
+fn difficulty_is_saved_and_old_saves_default_to_normal() {
+    let mut inv = Inventory::new();
+    inv.difficulty = Difficulty::Hard;
+    let json = serde_json::to_string(&inv).unwrap();
+    let back: Inventory = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.difficulty, Difficulty::Hard);
+    let legacy = r#"{"items":[],"copper_pieces":0,"silver_pieces":0,"gold_pieces":0}"#;
+    let old: Inventory = serde_json::from_str(legacy).unwrap();
+    assert_eq!(old.difficulty, Difficulty::Normal);
+    assert_eq!(old.level(), 1);
+}
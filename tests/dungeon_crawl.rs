@@ -0,0 +1,149 @@
+use dungeon_core::{
+    dungeon::{self, Door, Dungeon, RoomContent},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+// Level with every door open and room 1 holding the given content
+fn staged(content: RoomContent) -> Inventory {
+    let mut d = Dungeon::generate(77, 1);
+    for c in d.corridors.iter_mut() {
+        c.door = Door::Open;
+    }
+    d.rooms[1].content = content;
+    d.rooms[1].cleared = false;
+    let mut inv = Inventory::new();
+    inv.dungeon = Some(d);
+    inv
+}
+
+#[test]
+fn same_seed_same_layout_and_every_room_reachable() {
+    let a = Dungeon::generate(1234, 1);
+    let b = Dungeon::generate(1234, 1);
+    assert_eq!(
+        serde_json::to_string(&a).unwrap(),
+        serde_json::to_string(&b).unwrap()
+    );
+    assert!(a.rooms.len() >= dungeon::DUNGEON_MIN_ROOMS);
+    assert_eq!(a.rooms[0].content, RoomContent::Entrance);
+    assert_eq!(a.rooms.last().unwrap().content, RoomContent::Stairs);
+    let mut seen = vec![0usize];
+    let mut i = 0;
+    while i < seen.len() {
+        for c in &a.corridors {
+            if let Some(o) = c.other(seen[i])
+                && !seen.contains(&o)
+            {
+                seen.push(o);
+            }
+        }
+        i += 1;
+    }
+    assert_eq!(seen.len(), a.rooms.len());
+}
+
+#[test]
+fn monsters_wait_until_slain_and_chests_pay_out() {
+    let mut inv = staged(RoomContent::Monster {
+        name: "Cave Rat".into(),
+    });
+    let report = dungeon::move_to(&mut inv, 1, &[]).unwrap();
+    assert_eq!(report.encounter.unwrap().name, "Cave Rat");
+    dungeon::retreat(&mut inv);
+    assert_eq!(inv.dungeon.as_ref().unwrap().current, 0);
+    assert!(
+        dungeon::move_to(&mut inv, 1, &[])
+            .unwrap()
+            .encounter
+            .is_some()
+    );
+    dungeon::clear_current(&mut inv);
+    assert!(
+        dungeon::move_to(&mut inv, 0, &[])
+            .unwrap()
+            .encounter
+            .is_none()
+    );
+    assert!(
+        dungeon::move_to(&mut inv, 1, &[])
+            .unwrap()
+            .encounter
+            .is_none()
+    );
+
+    reseed(5);
+    let mut inv = staged(RoomContent::Chest);
+    dungeon::move_to(&mut inv, 1, &["a brass key".to_string()]).unwrap();
+    assert!(inv.total_cp() > 0);
+    assert!(inv.items.iter().any(|i| i.contains("key")));
+    assert!(inv.dungeon.as_ref().unwrap().rooms[1].cleared);
+}
+
+#[test]
+fn traps_hurt_but_never_kill() {
+    for seed in 0..10 {
+        reseed(seed);
+        let mut inv = staged(RoomContent::Trap { damage: 500 });
+        inv.current_hp = 4;
+        dungeon::move_to(&mut inv, 1, &[]).unwrap();
+        assert!(inv.current_hp >= 1);
+    }
+}
+
+#[test]
+fn stairs_lead_deeper_and_state_survives_a_save() {
+    let mut inv = staged(RoomContent::Empty);
+    assert!(dungeon::descend(&mut inv).is_err());
+    let stairs = inv.dungeon.as_ref().unwrap().rooms.len() - 1;
+    let far = (2..=stairs)
+        .find(|&r| {
+            let d = inv.dungeon.as_ref().unwrap();
+            !d.corridors.iter().any(|c| c.other(0) == Some(r))
+        })
+        .unwrap();
+    assert!(
+        dungeon::move_to(&mut inv, far, &[]).is_err(),
+        "Not adjacent"
+    );
+    inv.dungeon.as_mut().unwrap().current = stairs;
+    assert_eq!(dungeon::descend(&mut inv).unwrap(), 2);
+    let json = serde_json::to_string(&inv).unwrap();
+    let back: Inventory = serde_json::from_str(&json).unwrap();
+    let d = back.dungeon.unwrap();
+    assert_eq!((d.depth, d.current), (2, 0));
+}
+
+#[test]
+fn only_a_skeleton_key_opens_a_locked_door_and_chests_can_hold_one() {
+    let locked = |items: &[&str]| {
+        let mut inv = staged(RoomContent::Empty);
+        let d = inv.dungeon.as_mut().unwrap();
+        for c in d.corridors.iter_mut().filter(|c| c.other(0) == Some(1)) {
+            c.door = Door::Locked;
+        }
+        inv.items = items.iter().map(|s| s.to_string()).collect();
+        inv
+    };
+    for seed in 0..10 {
+        reseed(seed);
+        let mut inv = locked(&["Whiskey", "Monkey charm"]);
+        let report = dungeon::move_to(&mut inv, 1, &[]).unwrap();
+        assert!(!report.lines.iter().any(|l| l.contains("key turns")));
+        assert_eq!(inv.items.len(), 2);
+    }
+
+    let mut inv = locked(&["Whiskey", dungeon::DUNGEON_KEY]);
+    let report = dungeon::move_to(&mut inv, 1, &[]).unwrap();
+    assert!(report.lines[0].contains("key turns"));
+    assert_eq!(inv.dungeon.as_ref().unwrap().current, 1);
+    assert_eq!(inv.items, vec!["Whiskey".to_string()]);
+
+    let keyed = (0..20).any(|seed| {
+        reseed(seed);
+        let mut inv = staged(RoomContent::Chest);
+        dungeon::move_to(&mut inv, 1, &[]).unwrap();
+        inv.items.iter().any(|i| i == dungeon::DUNGEON_KEY)
+    });
+    assert!(keyed);
+}
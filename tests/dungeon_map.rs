@@ -0,0 +1,55 @@
+use dungeon_core::{
+    dungeon::{self, Door, Dungeon},
+    inventory::Inventory,
+    map::{self, render_grid, render_map},
+};
+
+fn opened(seed: u64) -> Inventory {
+    let mut d = Dungeon::generate(seed, 1);
+    for c in d.corridors.iter_mut() {
+        c.door = Door::Open;
+    }
+    let mut inv = Inventory::new();
+    inv.dungeon = Some(d);
+    inv
+}
+
+#[test]
+fn grid_has_fixed_dimensions_and_one_explorer() {
+    let d = Dungeon::generate(9, 1);
+    let grid = render_grid(&d);
+    assert_eq!(grid.len(), d.height as usize);
+    assert!(
+        grid.iter()
+            .all(|row| row.chars().count() == d.width as usize)
+    );
+    let at: usize = grid.iter().map(|r| r.matches('@').count()).sum();
+    assert_eq!(at, 1);
+    assert!(grid.iter().any(|r| r.contains('<')));
+}
+
+#[test]
+fn fog_hides_unvisited_rooms_until_entered() {
+    let mut inv = opened(9);
+    let before = render_map(inv.dungeon.as_ref().unwrap());
+    let floor_before = before.matches('.').count();
+    assert!(
+        !render_grid(inv.dungeon.as_ref().unwrap())
+            .iter()
+            .any(|r| r.contains('>'))
+    );
+    dungeon::move_to(&mut inv, 1, &[]).unwrap();
+    let after = render_map(inv.dungeon.as_ref().unwrap());
+    assert!(after.matches('.').count() > floor_before, "Room 1 revealed");
+}
+
+#[test]
+fn map_includes_title_and_legend() {
+    let d = Dungeon::generate(3, 2);
+    let out = render_map(&d);
+    assert!(out.starts_with("Dungeon level 2"));
+    assert!(out.ends_with(&map::legend()));
+    for (c, label) in map::LEGEND {
+        assert!(out.contains(&format!("{} {}", c, label)));
+    }
+}
@@ -0,0 +1,88 @@
+use dungeon_core::{
+    actions::pickpocket_success_chance,
+    bank,
+    character::{self, Background, CharacterClass},
+    difficulty::Difficulty,
+    encumbrance::{self, BASE_CAPACITY_LB},
+    inventory::Inventory,
+};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn items_and_coins_have_weight() {
+    assert!(close(encumbrance::item_weight("Shovel"), 5.0));
+    assert!(close(encumbrance::item_weight("Dwarven Plate"), 65.0));
+    // Loot the shops don't list is judged by its name
+    assert!(close(
+        encumbrance::item_weight("set of merchant's weights"),
+        5.0
+    ));
+    assert!(close(encumbrance::item_weight("brass ring"), 0.1));
+    assert!(close(encumbrance::item_weight("tiny figurine"), 1.0));
+
+    let mut inv = Inventory::new();
+    inv.add_coins("gp", 300);
+    inv.add_coins("sp", 150);
+    inv.add_coins("pp", 50);
+    assert!(close(encumbrance::coin_weight(&inv), 10.0));
+    inv.items = vec!["Shovel".into(), "Shovel".into()];
+    assert!(close(encumbrance::carried_weight(&inv), 20.0));
+}
+
+#[test]
+fn capacity_grows_with_class_level_and_containers() {
+    let mut inv = Inventory::new();
+    assert!(close(encumbrance::capacity(&inv), BASE_CAPACITY_LB));
+    inv.items = vec!["Backpack".into(), "Backpack".into()];
+    // Only one backpack is worn
+    assert!(close(encumbrance::capacity(&inv), BASE_CAPACITY_LB + 30.0));
+    inv.items.push("Bag of Holding".into());
+    assert!(close(encumbrance::capacity(&inv), BASE_CAPACITY_LB + 180.0));
+
+    let fighter = character::create(
+        "Brom",
+        CharacterClass::Fighter,
+        Background::ALL[0],
+        Difficulty::Normal,
+        false,
+    )
+    .unwrap();
+    let rogue = character::create(
+        "Vex",
+        CharacterClass::Rogue,
+        Background::ALL[0],
+        Difficulty::Normal,
+        false,
+    )
+    .unwrap();
+    assert!(encumbrance::capacity(&fighter) > encumbrance::capacity(&rogue));
+
+    let mut veteran = Inventory::new();
+    veteran.experience = 100_000;
+    assert!(encumbrance::capacity(&veteran) > BASE_CAPACITY_LB);
+}
+
+#[test]
+fn overloading_hurts_pickpocketing_and_blocks_fleeing() {
+    let mut inv = Inventory::new();
+    let light = pickpocket_success_chance(&inv);
+    assert!(encumbrance::flee_blocked(&inv).is_none());
+    inv.items = vec!["Dwarven Plate".into(), "Shovel".into()];
+    assert!(encumbrance::is_overloaded(&inv));
+    assert!(pickpocket_success_chance(&inv) < light);
+    let why = encumbrance::flee_blocked(&inv).unwrap();
+    assert!(why.contains("overloaded"));
+}
+
+#[test]
+fn banking_a_heavy_purse_lifts_the_load() {
+    let mut inv = Inventory::new();
+    inv.add_coins("gp", 3_000); // 60 lb of gold
+    assert!(encumbrance::is_overloaded(&inv));
+    bank::deposit(&mut inv, 200_000).unwrap();
+    assert!(!encumbrance::is_overloaded(&inv));
+    assert!(close(encumbrance::carried_weight(&inv), 20.0));
+}
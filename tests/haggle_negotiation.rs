@@ -0,0 +1,83 @@
+use dungeon_core::{
+    haggle::{HaggleContext, HaggleStep, Mood, Negotiation},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+#[test]
+fn offer_at_asking_price_is_accepted() {
+    let mut deal = Negotiation::new(1_000, HaggleContext::default());
+    assert_eq!(deal.offer(1_000), HaggleStep::Accepted { price_cp: 1_000 });
+    assert!(deal.is_over());
+}
+
+#[test]
+fn counters_move_toward_offer_but_never_below_floor() {
+    reseed(11);
+    let mut deal = Negotiation::new(1_000, HaggleContext::default());
+    let mut last_ask = deal.current_ask_cp;
+    // A fair-ish offer above the hidden floor either closes the deal or lowers the ask
+    loop {
+        match deal.offer(800) {
+            HaggleStep::Accepted { price_cp } => {
+                assert_eq!(price_cp, 800);
+                break;
+            }
+            HaggleStep::Countered { ask_cp } => {
+                assert!(ask_cp < last_ask, "Ask should drop after a counter");
+                assert!(ask_cp >= 750, "Neutral merchant floor is 25% off");
+                last_ask = ask_cp;
+            }
+            HaggleStep::WalkedAway => break,
+        }
+    }
+}
+
+#[test]
+fn insulting_offers_make_merchant_walk_away() {
+    reseed(3);
+    let mut deal = Negotiation::new(2_000, HaggleContext::default());
+    let mut steps = 0;
+    while !deal.is_over() {
+        deal.offer(100);
+        steps += 1;
+    }
+    assert_eq!(deal.outcome, Some(HaggleStep::WalkedAway));
+    assert!(steps <= 2, "Two insults should exhaust a neutral merchant");
+}
+
+#[test]
+fn notoriety_sours_mood_and_luck_sweetens_it() {
+    let mut inv = Inventory::new();
+    inv.notoriety = 7;
+    let grumpy = Negotiation::new(500, HaggleContext::from_inventory(&inv, false));
+    assert_eq!(grumpy.mood, Mood::Irritated);
+    let mut lucky_inv = Inventory::new();
+    lucky_inv.luck_boost = true;
+    let ctx = HaggleContext::from_inventory(&lucky_inv, true);
+    let lucky = Negotiation::new(500, ctx);
+    assert_eq!(lucky.mood, Mood::Friendly);
+    assert!(lucky.patience > grumpy.patience);
+    assert!(lucky.luck_used);
+}
+
+#[test]
+fn seasoned_adventurers_get_more_room_to_haggle() {
+    let mut novice = Negotiation::new(
+        1_000,
+        HaggleContext::from_inventory(&Inventory::new(), false),
+    );
+    let mut veteran_inv = Inventory::new();
+    veteran_inv.experience = 10_000;
+    let ctx = HaggleContext::from_inventory(&veteran_inv, false);
+    assert!(ctx.level >= 5);
+    let mut veteran = Negotiation::new(1_000, ctx);
+    assert_eq!(novice.mood, Mood::Neutral);
+    assert_eq!(veteran.mood, Mood::Friendly);
+    assert!(veteran.patience > novice.patience);
+    // 30% off is a lowball to a stranger but within reach for a veteran
+    novice.offer(700);
+    veteran.offer(700);
+    assert_eq!(novice.mood, Mood::Irritated);
+    assert_eq!(veteran.mood, Mood::Friendly);
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::temp_graveyard;
+use dungeon_core::{
+    actions::fight_monster_outcome,
+    bestiary::find,
+    combat::{self, Encounter, Fall, FightStatus},
+    difficulty::Difficulty,
+    inventory::Inventory,
+    rng::reseed,
+};
+
+fn doomed(hardcore: bool) -> Inventory {
+    let mut inv = Inventory::new();
+    inv.hardcore = hardcore;
+    inv.difficulty = Difficulty::Hard;
+    inv.gold_pieces = 250;
+    inv.experience = 120;
+    inv.items = vec!["Lantern".into()];
+    inv.max_hp = 1;
+    inv.current_hp = 1;
+    inv
+}
+
+fn lose(inv: &mut Inventory) -> Encounter {
+    let mut enc = Encounter::new(vec![find("Ogre Brute").unwrap()]);
+    loop {
+        let (_, status) = enc.round(inv, None);
+        if status == FightStatus::Defeat {
+            return enc;
+        }
+        enc.foes[0].hp = enc.foes[0].monster.max_hp();
+    }
+}
+
+// Both graveyard writes live in one test so they don't race on the file.
+#[test]
+fn hardcore_death_buries_the_character_and_starts_fresh() {
+    temp_graveyard();
+    reseed(39);
+    let mut inv = doomed(true);
+    let enc = lose(&mut inv);
+    let Fall::Perished(grave) = combat::settle_fall(&mut inv, &enc) else {
+        panic!("hardcore defeat should end the run");
+    };
+    assert_eq!(grave.killed_by, "Ogre Brute");
+    assert_eq!((grave.gold, grave.level, grave.items), (250, 2, 1));
+    assert_eq!(grave.difficulty, Difficulty::Hard);
+    // The graveyard file is only written by builds with a filesystem (the CLI)
+    #[cfg(feature = "cli")]
+    assert!(dungeon_core::graveyard::load().unwrap().contains(&grave));
+    // The replacement keeps the run settings but nothing else
+    assert!(inv.hardcore);
+    assert_eq!(inv.difficulty, Difficulty::Hard);
+    assert_eq!((inv.gold_pieces, inv.experience), (0, 0));
+    assert!(inv.items.is_empty());
+
+    let mut inv = doomed(true);
+    let outcome = loop {
+        inv.max_hp = 1;
+        inv.current_hp = 1;
+        let o = fight_monster_outcome(&mut inv);
+        if !o.victory {
+            break o;
+        }
+    };
+    let grave = outcome
+        .grave
+        .expect("auto-resolved fights respect hardcore");
+    assert_eq!(grave.killed_by, outcome.monster);
+    #[cfg(feature = "cli")]
+    assert!(dungeon_core::graveyard::load().unwrap().contains(&grave));
+}
+
+#[test]
+fn normal_defeat_only_knocks_you_out() {
+    reseed(40);
+    let mut inv = doomed(false);
+    let enc = lose(&mut inv);
+    match combat::settle_fall(&mut inv, &enc) {
+        Fall::KnockedOut { loss, .. } => assert!(loss > 0),
+        Fall::Perished(_) => panic!("only hardcore characters die"),
+    }
+    assert_eq!(inv.experience, 120);
+    assert_eq!(inv.current_hp, inv.max_hp);
+}
+
+#[test]
+fn hardcore_flag_is_saved_and_defaults_off() {
+    let mut inv = Inventory::new();
+    inv.hardcore = true;
+    let back: Inventory = serde_json::from_str(&serde_json::to_string(&inv).unwrap()).unwrap();
+    assert!(back.hardcore);
+    let legacy = r#"{"items":[],"copper_pieces":0,"silver_pieces":0,"gold_pieces":0}"#;
+    let old: Inventory = serde_json::from_str(legacy).unwrap();
+    assert!(!old.hardcore);
+}
@@ -0,0 +1,234 @@
+mod common;
+
+use common::temp_graveyard;
+use dungeon_core::{
+    bestiary::find,
+    character::{self, Background, CharacterClass},
+    clock::{LUCK_DURATION_MINUTES, MINUTES_PER_HOUR},
+    combat::{self, Encounter, Fall},
+    difficulty::Difficulty,
+    hotseat::{Bundle, HotSeat, TURN_MINUTES, Trade, TradeError},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+fn player(name: &str) -> Inventory {
+    let mut inv = character::create(
+        name,
+        CharacterClass::Rogue,
+        Background::Wanderer,
+        Difficulty::Normal,
+        false,
+    )
+    .unwrap();
+    inv.items.clear();
+    inv.gold_pieces = 0;
+    inv.silver_pieces = 0;
+    inv.copper_pieces = 0;
+    inv
+}
+
+fn table() -> HotSeat {
+    HotSeat::new(vec![player("Ada"), player("Brom"), player("Cress")])
+}
+
+fn offer(from: usize, to: usize, give: Bundle, take: Bundle) -> Trade {
+    Trade {
+        from,
+        to,
+        give,
+        take,
+    }
+}
+
+fn items(names: &[&str]) -> Vec<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn the_world_is_shared_but_inventories_are_not() {
+    reseed(50);
+    let mut t = table();
+    t.begin_turn();
+    {
+        let ada = t.current_mut();
+        ada.notoriety = 2;
+        ada.quests.refresh_board();
+        ada.items.push("Silver Ring".into());
+        ada.add_copper(250);
+        ada.advance_time(90);
+    }
+    let board: Vec<u32> = t.current().quests.board.iter().map(|q| q.id).collect();
+    let clock = t.current().clock.minutes;
+    assert!(!board.is_empty());
+    t.end_turn();
+    t.begin_turn();
+    let brom = t.current();
+    assert_eq!(t.name_of(t.turn), "Brom");
+    assert_eq!(brom.clock.minutes, clock);
+    assert_eq!(brom.notoriety, 2);
+    assert_eq!(
+        brom.quests.board.iter().map(|q| q.id).collect::<Vec<_>>(),
+        board
+    );
+    assert!(brom.items.is_empty());
+    assert_eq!(brom.total_cp(), 0);
+    assert_eq!(t.players[0].total_cp(), 250);
+}
+
+#[test]
+fn waiting_players_catch_up_on_the_time_others_spent() {
+    let mut t = HotSeat::new(vec![player("Ada"), player("Brom")]);
+    t.players[1].luck_boost = true;
+    t.begin_turn();
+    t.current_mut().advance_time(MINUTES_PER_HOUR);
+    t.end_turn();
+    assert!(t.begin_turn().is_empty());
+    assert!(t.current().luck_boost);
+    t.end_turn();
+    // Ada sleeps off a long day; Brom's luck fades while he waits
+    t.begin_turn();
+    t.current_mut().advance_time(LUCK_DURATION_MINUTES);
+    t.end_turn();
+    let notes = t.begin_turn();
+    assert!(notes.iter().any(|n| n.contains("luck has faded")));
+    assert!(!t.current().luck_boost);
+    assert_eq!(t.current().clock.minutes, t.players[0].clock.minutes);
+}
+
+#[test]
+fn turns_rotate_and_end_when_the_time_runs_out() {
+    let mut t = table();
+    assert_eq!((t.turn, t.round), (0, 1));
+    t.begin_turn();
+    assert_eq!(t.minutes_left(), TURN_MINUTES);
+    t.current_mut().advance_time(TURN_MINUTES - 30);
+    assert_eq!(t.minutes_left(), 30);
+    assert!(!t.turn_over());
+    t.current_mut().advance_time(45);
+    assert!(t.turn_over());
+    for expected in [1, 2, 0] {
+        t.end_turn();
+        assert_eq!(t.turn, expected);
+        t.begin_turn();
+        assert_eq!(t.minutes_left(), TURN_MINUTES);
+    }
+    assert_eq!(t.round, 2);
+}
+
+#[test]
+fn players_swap_coin_and_items_and_stolen_goods_stay_stolen() {
+    let mut t = table();
+    t.players[0].add_copper(500);
+    t.players[0].items = items(&["Silver Ring", "Torch"]);
+    t.players[0].push_item("Silver Ring".into(), true);
+    t.players[1].items = items(&["Rope", "Rope"]);
+    let deal = offer(
+        0,
+        1,
+        Bundle {
+            coin_cp: 120,
+            items: vec![2],
+        },
+        Bundle {
+            coin_cp: 0,
+            items: vec![0, 1],
+        },
+    );
+    assert_eq!(
+        deal.give.label(&t.players[0]),
+        "1 gp 2 sp and a Silver Ring"
+    );
+    assert_eq!(t.execute_trade(&deal), Ok(()));
+    assert_eq!(t.players[0].total_cp(), 380);
+    assert_eq!(t.players[1].total_cp(), 120);
+    assert_eq!(
+        t.players[0].items,
+        items(&["Silver Ring", "Torch", "Rope", "Rope"])
+    );
+    assert_eq!(t.players[1].items, items(&["Silver Ring"]));
+    // Only the lifted ring changed hands; the honest one stays clean
+    assert!((0..4).all(|i| !t.players[0].is_stolen_at(i)));
+    assert!(t.players[1].is_stolen_at(0));
+}
+
+#[test]
+fn trades_need_both_sides_to_hold_what_they_promise() {
+    let mut t = table();
+    t.players[1].items = items(&["Rope"]);
+    let ask = |items: Vec<usize>| Bundle { coin_cp: 0, items };
+    let coin = |cp: u32| Bundle {
+        coin_cp: cp,
+        items: Vec::new(),
+    };
+    let cases = [
+        (offer(0, 7, coin(0), ask(vec![0])), TradeError::NoSuchPlayer),
+        (offer(1, 1, coin(0), ask(vec![0])), TradeError::SamePlayer),
+        (
+            offer(0, 1, Bundle::default(), Bundle::default()),
+            TradeError::NothingOffered,
+        ),
+        (
+            offer(0, 1, coin(10), ask(vec![0])),
+            TradeError::CantAfford("Ada".into()),
+        ),
+        (
+            offer(0, 1, coin(0), ask(vec![1])),
+            TradeError::MissingItem("Brom".into()),
+        ),
+        (
+            offer(0, 1, coin(0), ask(vec![0, 0])),
+            TradeError::MissingItem("Brom".into()),
+        ),
+    ];
+    for (trade, err) in cases {
+        assert_eq!(t.execute_trade(&trade), Err(err));
+    }
+    assert_eq!(t.players[1].items, items(&["Rope"]));
+    assert_eq!(coin(250).label(&t.players[0]), "2 gp 5 sp");
+    assert_eq!(Bundle::default().label(&t.players[0]), "nothing");
+}
+
+#[test]
+fn a_hardcore_death_at_the_table_leaves_the_world_running() {
+    temp_graveyard();
+    reseed(50);
+    let mut t = table();
+    t.begin_turn();
+    let ogre = find("Ogre Brute").unwrap();
+    {
+        let ada = t.current_mut();
+        ada.hardcore = true;
+        ada.notoriety = 3;
+        ada.quests.refresh_board();
+        ada.advance_time(3 * MINUTES_PER_HOUR);
+        ada.max_hp = 1;
+        ada.current_hp = 0;
+    }
+    let board: Vec<u32> = t.current().quests.board.iter().map(|q| q.id).collect();
+    let clock = t.current().clock.minutes;
+    let enc = Encounter::new(vec![ogre]);
+    let Fall::Perished(_) = combat::settle_fall(t.current_mut(), &enc) else {
+        panic!("hardcore defeat should end the run");
+    };
+    character::take_over(
+        t.current_mut(),
+        "Edda",
+        CharacterClass::Fighter,
+        Background::Soldier,
+    )
+    .unwrap();
+    let edda = t.current();
+    assert!(edda.hot_seat && edda.hardcore);
+    assert_eq!(t.name_of(0), "Edda");
+    assert!(!edda.items.is_empty());
+    assert_eq!((edda.clock.minutes, edda.notoriety), (clock, 3));
+    t.end_turn();
+    // Time never runs backwards for the next player, and the rumors are still pinned up
+    assert_eq!(t.world.clock.minutes, clock);
+    assert_eq!(t.world.notoriety, 3);
+    assert_eq!(
+        t.world.rumors.iter().map(|q| q.id).collect::<Vec<_>>(),
+        board
+    );
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::temp_graveyard;
+use dungeon_core::{
+    bestiary::find,
+    combat::{self, Encounter},
+    graveyard,
+    inventory::Inventory,
+    rng::reseed,
+    shop::{self, ShopKind},
+    stats::{StatEvent, Stats},
+};
+
+#[test]
+fn slaying_the_dragon_wyrm_unlocks_its_achievement() {
+    reseed(41);
+    let mut inv = Inventory::new();
+    let mut enc = Encounter::new(vec![find("Dragon Wyrm").unwrap(), find("Ghoul").unwrap()]);
+    enc.foes[0].hp = 0;
+    enc.foes[1].hp = 0;
+    let spoils = combat::settle_victory(&mut inv, &enc);
+    assert_eq!(inv.stats.slain("Dragon Wyrm"), 1);
+    assert_eq!(inv.stats.total_slain(), 2);
+    assert!(inv.stats.has("first_blood") && inv.stats.has("dragon_slayer"));
+    assert!(inv.stats.coin_earned_cp >= spoils.gold as u64 * 100);
+    // Unlocks from a fight are reported with the spoils, not again afterwards
+    assert!(
+        spoils
+            .notes
+            .iter()
+            .any(|n| n.contains("Slay the Dragon Wyrm"))
+    );
+    assert!(inv.stats.take_announcements().is_empty());
+}
+
+#[test]
+fn achievements_unlock_once_and_are_announced_once() {
+    let mut stats = Stats::default();
+    stats.record(StatEvent::MysteriousFigure);
+    stats.record(StatEvent::MysteriousFigure);
+    assert!(!stats.has("shadow_friend"));
+    stats.record(StatEvent::MysteriousFigure);
+    stats.record(StatEvent::MysteriousFigure);
+    assert_eq!(stats.mysterious_figures, 4);
+    assert_eq!(stats.achievements, vec!["shadow_friend".to_string()]);
+    let notes = stats.take_announcements();
+    assert_eq!(notes.len(), 1);
+    assert!(notes[0].contains("Friend in the Shadows"));
+    assert!(stats.take_announcements().is_empty());
+}
+
+#[test]
+fn trading_counts_items_and_coin() {
+    let mut inv = Inventory::new();
+    inv.items = vec!["Lantern".into(), "Rope".into()];
+    let offers = vec![Some(120), Some(30)];
+    let (sold, paid) = shop::sell_selected(&mut inv, ShopKind::General, &[0, 1], &offers);
+    assert_eq!(inv.stats.items_sold, sold.len() as u32);
+    assert_eq!(inv.stats.coin_earned_cp, paid as u64);
+    shop::record_purchase(&mut inv, ShopKind::General, &["Torch".into()], 50);
+    assert_eq!(inv.stats.items_bought, 1);
+}
+
+#[test]
+fn stats_survive_death_and_old_saves() {
+    temp_graveyard();
+    let mut inv = Inventory::new();
+    inv.hardcore = true;
+    inv.stats.record(StatEvent::TavernTip);
+    graveyard::bury(&mut inv, "Ogre Brute");
+    assert_eq!((inv.stats.tavern_tips, inv.stats.deaths), (1, 1));
+    assert!(inv.stats.has("back_again"));
+
+    let mut json = serde_json::to_value(Inventory::new()).unwrap();
+    json.as_object_mut().unwrap().remove("stats");
+    let old: Inventory = serde_json::from_value(json).unwrap();
+    assert_eq!(old.stats, Stats::default());
+}
+
+#[test]
+fn losing_a_huge_purse_saturates_the_coin_lost_tally() {
+    reseed(41);
+    let mut inv = Inventory::new();
+    inv.gold_pieces = u32::MAX;
+    let (lost, _) = combat::settle_flee(&mut inv);
+    assert!(lost as u64 * 100 > u32::MAX as u64);
+    assert_eq!(inv.stats.coin_lost_cp, u32::MAX as u64);
+}
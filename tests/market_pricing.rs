@@ -0,0 +1,59 @@
+use dungeon_core::{
+    inventory::Inventory,
+    market::Market,
+    shop::{self, ShopKind},
+};
+
+#[test]
+fn dumping_copies_lowers_price_and_buying_raises_it() {
+    let mut m = Market::default();
+    assert_eq!(m.price_multiplier("Torch"), 1.0);
+    for _ in 0..4 {
+        m.record_sale("Torch");
+    }
+    assert!(m.price_multiplier("torch") < 0.6, "Glut should cut prices");
+    m.record_purchase("Lantern");
+    m.record_purchase("Lantern");
+    assert!(
+        m.price_multiplier("Lantern") > 1.2,
+        "Buying should push prices up"
+    );
+}
+
+#[test]
+fn prices_recover_over_time() {
+    let mut m = Market::default();
+    for _ in 0..5 {
+        m.record_sale("Shovel");
+    }
+    let depressed = m.price_multiplier("Shovel");
+    m.pass_time(10);
+    assert!(m.price_multiplier("Shovel") > depressed);
+    m.pass_time(100);
+    assert_eq!(m.price_multiplier("Shovel"), 1.0);
+}
+
+#[test]
+fn merchant_purse_caps_payout_and_refills() {
+    let mut inv = Inventory::new();
+    let full = ShopKind::Fence.catalog().purse_cp;
+    inv.items = vec!["Tiny idol".into(), "Opal shard".into()];
+    let offers = vec![Some(full), Some(full)];
+    let (_, paid) = shop::sell_selected(&mut inv, ShopKind::Fence, &[0, 1], &offers);
+    assert_eq!(paid, full, "Cannot pay more than the purse holds");
+    assert_eq!(inv.market.purse_cp(ShopKind::Fence), 0);
+    inv.market.pass_time(1);
+    let refill = ShopKind::Fence.catalog().purse_refill_cp;
+    assert_eq!(inv.market.purse_cp(ShopKind::Fence), refill);
+    inv.market.pass_time(1_000);
+    assert_eq!(inv.market.purse_cp(ShopKind::Fence), full);
+}
+
+#[test]
+fn purchases_fill_the_purse() {
+    let mut inv = Inventory::new();
+    let start = inv.market.purse_cp(ShopKind::Blacksmith);
+    shop::record_purchase(&mut inv, ShopKind::Blacksmith, &["Dagger".into()], 300);
+    assert_eq!(inv.market.purse_cp(ShopKind::Blacksmith), start + 300);
+    assert!(inv.market.price_multiplier("Dagger") > 1.0);
+}
@@ -0,0 +1,155 @@
+use dungeon_core::{
+    actions::{TAVERN_DRINK_PRICE, tavern_pay},
+    appraisal::{AppraisalError, appraise_at_shop},
+    companion::{self, CompanionKind, HireError},
+    gamble::{DiceWager, GambleError, play_rune_dice},
+    inventory::Inventory,
+    money::{Money, MoneyError, PayMode},
+    shop::{self, ShopKind},
+};
+use proptest::prelude::*;
+
+fn purse(pp: u32, gp: u32, ep: u32, sp: u32, cp: u32) -> Inventory {
+    let mut inv = Inventory::new();
+    for (n, code) in [(pp, "pp"), (gp, "gp"), (ep, "ep"), (sp, "sp"), (cp, "cp")] {
+        inv.add_coins(code, n);
+    }
+    inv
+}
+
+fn mix(inv: &Inventory) -> [u32; 5] {
+    ["pp", "gp", "ep", "sp", "cp"].map(|c| inv.coin_count(c))
+}
+
+#[test]
+fn huge_purses_do_not_overflow() {
+    let mut inv = purse(u32::MAX, u32::MAX, 0, u32::MAX, u32::MAX);
+    assert!(inv.money() > Money::cp(u32::MAX as u64));
+    assert_eq!(inv.total_cp(), u32::MAX);
+    inv.add_copper(u32::MAX);
+    assert_eq!(inv.gold_pieces, u32::MAX);
+    assert!(inv.try_spend_cp(u32::MAX));
+}
+
+#[test]
+fn spending_a_copper_keeps_the_silver() {
+    let mut inv = purse(0, 0, 0, 10, 0);
+    assert!(inv.try_spend_cp(1));
+    // One silver is broken; the other nine stay silver
+    assert_eq!(
+        (inv.gold_pieces, inv.silver_pieces, inv.copper_pieces),
+        (0, 9, 9)
+    );
+    assert_eq!(
+        inv.pay(Money::cp(5), PayMode::ExactChange)
+            .map(|p| p.change),
+        Ok(Money::ZERO)
+    );
+    assert_eq!(
+        purse(0, 1, 0, 0, 0).pay(Money::cp(5), PayMode::ExactChange),
+        Err(MoneyError::NoExactChange)
+    );
+}
+
+#[test]
+fn an_exact_change_player_is_never_handed_change() {
+    let mut inv = purse(1, 1, 0, 0, 0);
+    inv.pay_mode = PayMode::ExactChange;
+    let back: Inventory = serde_json::from_str(&serde_json::to_string(&inv).unwrap()).unwrap();
+    assert_eq!(back.pay_mode, PayMode::ExactChange);
+
+    assert!(!tavern_pay(&mut inv, TAVERN_DRINK_PRICE));
+    assert_eq!(
+        shop::purchase(&mut inv, ShopKind::General, &["Torch".into()], 150),
+        Err(MoneyError::NoExactChange)
+    );
+    assert_eq!(
+        companion::hire(&mut inv, CompanionKind::Sellsword),
+        Err(HireError::NoExactChange)
+    );
+    assert_eq!(mix(&inv), [1, 1, 0, 0, 0]);
+    assert!(inv.items.is_empty());
+    // The exact sum still goes through
+    assert!(shop::purchase(&mut inv, ShopKind::General, &["Torch".into()], 100).is_ok());
+    assert_eq!(inv.items, vec!["Torch".to_string()]);
+    // Only the platinum piece is left: no fee or stake can be counted out of it
+    inv.add_item("jeweled clasp");
+    assert_eq!(
+        appraise_at_shop(&mut inv, ShopKind::MagicEmporium, 1),
+        Err(AppraisalError::NoExactChange)
+    );
+    assert_eq!(
+        play_rune_dice(&mut inv, 50, DiceWager::Over, false).unwrap_err(),
+        GambleError::NoExactChange
+    );
+    assert_eq!(mix(&inv), [1, 0, 0, 0, 0]);
+
+    inv.pay_mode = PayMode::MakeChange;
+    assert!(tavern_pay(&mut inv, TAVERN_DRINK_PRICE));
+}
+
+proptest! {
+    #[test]
+    fn checked_and_saturating_agree(a in any::<u64>(), b in any::<u64>()) {
+        let (x, y) = (Money::cp(a), Money::cp(b));
+        match x.checked_add(y) {
+            Some(sum) => prop_assert_eq!(sum, x.saturating_add(y)),
+            None => prop_assert_eq!(x.saturating_add(y), Money::MAX),
+        }
+        match x.checked_sub(y) {
+            Some(diff) => prop_assert_eq!(diff.saturating_add(y), x),
+            None => prop_assert_eq!(x.saturating_sub(y), Money::ZERO),
+        }
+    }
+
+    #[test]
+    fn paying_removes_exactly_the_price(
+        pp in 0u32..5, gp in 0u32..50, ep in 0u32..5, sp in 0u32..50, cp in 0u32..50,
+        price in 0u64..10_000,
+    ) {
+        let mut inv = purse(pp, gp, ep, sp, cp);
+        let before = inv.money();
+        let price = Money::cp(price);
+        match inv.pay(price, PayMode::MakeChange) {
+            Ok(p) => {
+                prop_assert_eq!(inv.money(), before.checked_sub(price).unwrap());
+                prop_assert_eq!(p.handed_over.checked_sub(p.change), Some(price));
+            }
+            Err(e) => {
+                prop_assert_eq!(e, MoneyError::InsufficientFunds);
+                prop_assert!(before < price);
+                prop_assert_eq!(inv.money(), before);
+            }
+        }
+    }
+
+    #[test]
+    fn exact_change_only_ever_removes_coins(
+        pp in 0u32..5, gp in 0u32..50, ep in 0u32..5, sp in 0u32..50, cp in 0u32..50,
+        price in 0u64..10_000,
+    ) {
+        let mut inv = purse(pp, gp, ep, sp, cp);
+        let before = mix(&inv);
+        let money_before = inv.money();
+        match inv.pay(Money::cp(price), PayMode::ExactChange) {
+            Ok(p) => {
+                prop_assert_eq!(p.change, Money::ZERO);
+                let after = mix(&inv);
+                prop_assert!(after.iter().zip(before.iter()).all(|(a, b)| a <= b));
+                prop_assert_eq!(inv.money(), money_before.checked_sub(Money::cp(price)).unwrap());
+            }
+            Err(_) => prop_assert_eq!(mix(&inv), before),
+        }
+    }
+
+    #[test]
+    fn making_change_breaks_at_most_one_coin(
+        gp in 0u32..20, sp in 0u32..20, cp in 0u32..20, price in 0u64..3_000,
+    ) {
+        let mut inv = purse(0, gp, 0, sp, cp);
+        if let Ok(p) = inv.pay(Money::cp(price), PayMode::MakeChange) {
+            // Change is always less than the coin that was broken
+            prop_assert!(p.change < Money::gp(1));
+        }
+    }
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use common::tough_player;
+use dungeon_core::{
+    bestiary::find,
+    combat::{Encounter, FightStatus},
+    rng::reseed,
+};
+
+fn fight(names: &[&str]) -> Encounter {
+    Encounter::new(names.iter().map(|n| find(n).unwrap()).collect())
+}
+
+#[test]
+fn stone_golem_shrugs_off_light_weapons() {
+    reseed(35);
+    let mut inv = tough_player();
+    let mut enc = fight(&["Stone Golem"]);
+    let mut strikes = 0;
+    for _ in 0..10 {
+        let (lines, _) = enc.round(&mut inv, None);
+        for (i, l) in lines.iter().enumerate() {
+            if l.starts_with("You strike") {
+                strikes += 1;
+                assert!(lines[i - 1].contains("stone hide"));
+            }
+        }
+    }
+    assert!(strikes > 0);
+
+    inv.add_item("Warhammer");
+    let mut enc = fight(&["Stone Golem"]);
+    for _ in 0..10 {
+        let (lines, _) = enc.round(&mut inv, None);
+        assert!(lines.iter().all(|l| !l.contains("stone hide")));
+    }
+    assert!(enc.foes[0].hp < enc.foes[0].monster.max_hp());
+}
+
+#[test]
+fn dragon_breathes_fire_every_third_turn() {
+    reseed(7);
+    let mut inv = tough_player();
+    let mut enc = fight(&["Dragon Wyrm"]);
+    for turn in 1..=6 {
+        let (lines, _) = enc.round(&mut inv, None);
+        let breathed = lines.iter().any(|l| l.contains("breathes fire"));
+        assert_eq!(breathed, turn % 3 == 0, "turn {}", turn);
+    }
+}
+
+#[test]
+fn vampire_drains_and_ghoul_paralyzes() {
+    reseed(11);
+    let mut inv = tough_player();
+    let mut enc = fight(&["Vampire Stalker"]);
+    let mut drained = false;
+    for _ in 0..20 {
+        enc.foes[0].hp = 20;
+        let (lines, _) = enc.round(&mut inv, None);
+        if lines.iter().any(|l| l.contains("drains")) {
+            drained = true;
+            break;
+        }
+    }
+    assert!(drained);
+
+    let mut enc = fight(&["Ghoul"]);
+    let mut paralyzed = false;
+    for _ in 0..40 {
+        let (lines, _) = enc.round(&mut inv, None);
+        if lines[0].contains("paralyzed and cannot act") {
+            paralyzed = true;
+            break;
+        }
+        enc.foes[0].hp = enc.foes[0].monster.max_hp();
+    }
+    assert!(paralyzed);
+}
+
+#[test]
+fn wounded_cowards_flee_and_brutes_enrage() {
+    reseed(5);
+    let mut inv = tough_player();
+    let mut enc = fight(&["Goblin Sneak", "Orc Marauder"]);
+    enc.foes[0].hp = 2;
+    enc.foes[1].hp = 3;
+    enc.player_paralyzed = 1; // let the monsters act first
+    let (lines, status) = enc.round(&mut inv, None);
+    assert_eq!(status, FightStatus::Ongoing);
+    assert!(enc.foes[0].fled && !enc.foes[0].slain());
+    assert!(enc.foes[1].enraged);
+    assert!(lines.iter().any(|l| l.contains("rage")));
+    assert_eq!(enc.living(), vec![1]);
+}
+
+#[test]
+fn cave_rat_steals_and_runs() {
+    reseed(9);
+    let mut inv = tough_player();
+    inv.items = vec!["Silver Ring".into(), "Torch".into()];
+    let mut enc = fight(&["Cave Rat"]);
+    let status = loop {
+        enc.player_paralyzed = 1;
+        let (_, status) = enc.round(&mut inv, None);
+        if status != FightStatus::Ongoing {
+            break status;
+        }
+    };
+    // The rat leaves the field with the loot, so the fight ends without a kill
+    assert_eq!(status, FightStatus::Escaped);
+    assert!(enc.foes[0].fled);
+    assert_eq!(inv.items.len(), 1);
+}
+
+#[test]
+fn a_fight_is_only_won_once_something_falls() {
+    reseed(5);
+    let mut inv = tough_player();
+    let mut enc = fight(&["Goblin Sneak"]);
+    enc.foes[0].hp = 2;
+    enc.player_paralyzed = 1;
+    let (_, status) = enc.round(&mut inv, None);
+    assert_eq!(status, FightStatus::Escaped);
+
+    let mut enc = fight(&["Goblin Sneak", "Goblin Sneak"]);
+    enc.foes[0].hp = 0;
+    enc.foes[1].hp = 2;
+    enc.player_paralyzed = 1;
+    let (_, status) = enc.round(&mut inv, None);
+    assert!(enc.foes[1].fled);
+    assert_eq!(status, FightStatus::Victory);
+}
@@ -0,0 +1,72 @@
+use dungeon_core::{
+    bestiary::{self, Drop, MONSTERS, find},
+    combat::{self, Encounter},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+#[test]
+fn every_monster_has_a_sane_drop_table() {
+    for m in MONSTERS {
+        let table = bestiary::drop_table(m.name);
+        assert!(!table.is_empty(), "{} drops nothing", m.name);
+        for d in table {
+            let chance = match *d {
+                Drop::Item(_, c) => c,
+                Drop::Coins(min, max, denom, c) => {
+                    assert!(min <= max && ["cp", "sp", "gp"].contains(&denom));
+                    c
+                }
+            };
+            assert!(chance > 0.0 && chance <= 1.0, "{}", m.name);
+        }
+    }
+}
+
+#[test]
+fn drops_come_from_the_table_and_replay_under_a_seed() {
+    let wyvern = find("Wyvern").unwrap();
+    let roll = |seed| {
+        reseed(seed);
+        (0..50)
+            .flat_map(|_| bestiary::roll_drops(&wyvern))
+            .collect::<Vec<_>>()
+    };
+    let drops = roll(38);
+    assert!(drops.iter().any(|d| d == "Wyvern Scale"));
+    assert!(
+        drops
+            .iter()
+            .all(|d| d == "Wyvern Scale" || d == "Wyvern Stinger")
+    );
+    assert_eq!(drops, roll(38));
+}
+
+#[test]
+fn victory_adds_drops_to_the_inventory() {
+    reseed(12);
+    let mut inv = Inventory::new();
+    let mut enc = Encounter::new(vec![find("Orc Marauder").unwrap(); 6]);
+    for f in &mut enc.foes {
+        f.hp = 0;
+    }
+    let spoils = combat::settle_victory(&mut inv, &enc);
+    assert!(!spoils.drops.is_empty());
+    assert_eq!(inv.items, spoils.items());
+    let coins = spoils.drops.len() - spoils.items().len();
+    assert!(coins > 0);
+    assert!(inv.silver_pieces > 0);
+    assert_eq!(inv.gold_pieces, spoils.gold);
+}
+
+#[test]
+fn foes_that_ran_off_drop_nothing() {
+    reseed(3);
+    let mut inv = Inventory::new();
+    let mut enc = Encounter::new(vec![find("Dragon Wyrm").unwrap()]);
+    enc.foes[0].fled = true;
+    let spoils = combat::settle_victory(&mut inv, &enc);
+    assert!(spoils.drops.is_empty());
+    assert_eq!(spoils.gold, 0);
+    assert!(inv.items.is_empty());
+}
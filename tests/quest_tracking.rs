@@ -0,0 +1,131 @@
+use dungeon_core::{
+    inventory::Inventory,
+    quest::{self, Objective, Quest, QuestEvent, QuestLog, QuestStatus},
+    rng::reseed,
+};
+
+fn pinned(log: &mut QuestLog, objective: Objective, reward_cp: u32) -> u32 {
+    log.next_id += 1;
+    let id = log.next_id;
+    log.board.push(Quest {
+        id,
+        objective,
+        progress: 0,
+        reward_cp,
+        reward_item: None,
+        hours_left: 10,
+        status: QuestStatus::Available,
+    });
+    id
+}
+
+#[test]
+fn board_refreshes_without_duplicates() {
+    reseed(30);
+    let mut log = QuestLog::default();
+    log.refresh_board();
+    assert!(!log.board.is_empty());
+    for (i, a) in log.board.iter().enumerate() {
+        for b in log.board.iter().skip(i + 1) {
+            assert_ne!(a.objective, b.objective);
+        }
+    }
+}
+
+#[test]
+fn slaying_progresses_and_rewards_at_turn_in() {
+    let mut inv = Inventory::new();
+    let id = pinned(
+        &mut inv.quests,
+        Objective::Slay {
+            monster: "Ogre Brute".into(),
+            count: 1,
+        },
+        5_000,
+    );
+    inv.quests.accept(id).unwrap();
+    assert!(
+        inv.quests
+            .record(QuestEvent::MonsterSlain("Cave Rat"))
+            .is_empty()
+    );
+    let notes = inv.quests.record(QuestEvent::MonsterSlain("Ogre Brute"));
+    assert_eq!(notes.len(), 1);
+    assert_eq!(inv.quests.active[0].status, QuestStatus::Complete);
+    let claimed = quest::claim_rewards(&mut inv);
+    assert!(!claimed.is_empty());
+    assert_eq!(inv.total_cp(), 5_000);
+    assert!(inv.quests.active.is_empty());
+    assert_eq!(inv.quests.completed_count(), 1);
+}
+
+#[test]
+fn delivery_consumes_the_item_and_lift_tracks_pickpocketing() {
+    let mut inv = Inventory::new();
+    let deliver = pinned(
+        &mut inv.quests,
+        Objective::Deliver {
+            item: "blood-stained map".into(),
+            recipient: "a cartographer".into(),
+        },
+        4_000,
+    );
+    let lift = pinned(
+        &mut inv.quests,
+        Objective::Lift {
+            item: "signet ring".into(),
+            mark: "a noble".into(),
+        },
+        3_000,
+    );
+    inv.quests.accept(deliver).unwrap();
+    inv.quests.accept(lift).unwrap();
+    // Nothing carried yet: nothing to claim
+    assert!(quest::claim_rewards(&mut inv).is_empty());
+    inv.add_item("Blood-stained map of the Dark Catacombs of Frica");
+    inv.quests
+        .record(QuestEvent::ItemStolen("Signet ring (5 gp)"));
+    quest::claim_rewards(&mut inv);
+    assert_eq!(inv.total_cp(), 7_000);
+    assert!(inv.items.is_empty(), "Map handed over to the cartographer");
+}
+
+#[test]
+fn unfinished_quests_expire() {
+    let mut log = QuestLog::default();
+    let id = pinned(
+        &mut log,
+        Objective::Slay {
+            monster: "Wyvern".into(),
+            count: 1,
+        },
+        8_000,
+    );
+    let limit = log.accept(id).unwrap().hours_left;
+    assert!(log.pass_time(limit - 1).is_empty());
+    let notes = log.pass_time(1);
+    assert_eq!(notes.len(), 1);
+    assert!(log.active.is_empty());
+    assert_eq!(log.history[0].status, QuestStatus::Expired);
+}
+
+#[test]
+fn the_completed_tally_outgrows_the_history() {
+    let mut inv = Inventory::new();
+    let turn_ins = quest::QUEST_HISTORY_LEN as u32 + 5;
+    for _ in 0..turn_ins {
+        let id = pinned(
+            &mut inv.quests,
+            Objective::Slay {
+                monster: "Cave Rat".into(),
+                count: 1,
+            },
+            10,
+        );
+        inv.quests.accept(id).unwrap();
+        inv.quests.record(QuestEvent::MonsterSlain("Cave Rat"));
+        quest::claim_rewards(&mut inv);
+    }
+    assert_eq!(inv.quests.history.len(), quest::QUEST_HISTORY_LEN);
+    assert_eq!(inv.quests.completed_count(), turn_ins as usize);
+}
@@ -0,0 +1,70 @@
+use dungeon_core::{
+    actions::Rarity,
+    inventory::Inventory,
+    market::Market,
+    rng::reseed,
+    shop::{self, ShopKind},
+};
+
+#[test]
+fn every_catalog_loads_and_stocks_its_own_items() {
+    reseed(5);
+    for kind in ShopKind::ALL {
+        let cat = kind.catalog();
+        assert!(!cat.items.is_empty(), "{} has no items", cat.name);
+        let stock = shop::generate_stock(kind, &Market::default());
+        let [lo, _] = cat.stock_size;
+        assert!(stock.len() >= (lo as usize).min(cat.items.len()));
+        for it in &stock {
+            assert!(cat.stocks(&it.name), "{} not in {}", it.name, cat.name);
+        }
+    }
+}
+
+#[test]
+fn magic_emporium_never_stocks_common_goods() {
+    reseed(21);
+    for _ in 0..20 {
+        let stock = shop::generate_stock(ShopKind::MagicEmporium, &Market::default());
+        assert!(stock.iter().all(|it| it.rarity != Rarity::Common));
+    }
+}
+
+#[test]
+fn only_the_fence_buys_stolen_goods() {
+    let mut inv = Inventory::new();
+    inv.push_item("Signet ring".into(), true);
+    inv.add_item("Torch");
+    for kind in ShopKind::ALL {
+        let hot = shop::sell_offer_cp(kind, &inv, 0);
+        assert_eq!(hot.is_some(), kind == ShopKind::Fence, "{:?}", kind);
+        assert!(shop::sell_offer_cp(kind, &inv, 1).is_some());
+    }
+}
+
+#[test]
+fn selling_pays_out_and_clears_stolen_marker() {
+    let mut inv = Inventory::new();
+    inv.push_item("Brass key".into(), true);
+    inv.add_item("Torch");
+    let offers = vec![Some(120), Some(30)];
+    let (removed, total) = shop::sell_selected(&mut inv, ShopKind::Fence, &[0], &offers);
+    assert_eq!(removed, vec!["Brass key".to_string()]);
+    assert_eq!(total, 120);
+    assert_eq!(inv.total_cp(), 120);
+    assert!(!inv.is_stolen_at(0));
+    assert_eq!(inv.items, vec!["Torch".to_string()]);
+}
+
+#[test]
+fn a_stolen_copy_does_not_taint_an_honest_one() {
+    let mut inv = Inventory::new();
+    inv.push_item("Silver Ring".into(), true);
+    inv.add_item("Silver Ring");
+    assert_eq!(shop::sell_offer_cp(ShopKind::General, &inv, 0), None);
+    assert!(shop::sell_offer_cp(ShopKind::General, &inv, 1).is_some());
+    // Selling the honest ring leaves the stolen one marked
+    shop::sell_selected(&mut inv, ShopKind::General, &[1], &[None, Some(50)]);
+    assert_eq!(inv.items, vec!["Silver Ring".to_string()]);
+    assert!(inv.is_stolen_at(0));
+}
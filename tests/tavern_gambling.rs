@@ -0,0 +1,76 @@
+use dungeon_core::{
+    gamble::{DiceWager, GAMBLE_MAX_BET_CP, GambleError, play_rune_dice},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+#[test]
+fn bet_limits_and_funds_are_enforced() {
+    let mut inv = Inventory::new();
+    inv.add_copper(100);
+    assert_eq!(
+        play_rune_dice(&mut inv, 1, DiceWager::Over, false).unwrap_err(),
+        GambleError::BetTooLow
+    );
+    assert_eq!(
+        play_rune_dice(&mut inv, GAMBLE_MAX_BET_CP + 1, DiceWager::Over, false).unwrap_err(),
+        GambleError::BetTooHigh
+    );
+    assert_eq!(
+        play_rune_dice(&mut inv, 200, DiceWager::Over, false).unwrap_err(),
+        GambleError::InsufficientFunds
+    );
+    assert_eq!(inv.total_cp(), 100, "Rejected bets cost nothing");
+}
+
+#[test]
+fn winnings_and_losses_settle_in_coin() {
+    reseed(17);
+    let mut inv = Inventory::new();
+    inv.add_copper(10_000);
+    for _ in 0..30 {
+        let before = inv.total_cp() as i64;
+        let o = play_rune_dice(&mut inv, 50, DiceWager::Seven, false).unwrap();
+        assert_eq!(o.won, o.total() == 7);
+        assert_eq!(inv.total_cp() as i64, before + o.net_cp);
+        if o.won {
+            assert_eq!(o.net_cp, 200);
+        }
+    }
+}
+
+#[test]
+fn house_keeps_an_edge_over_many_rounds() {
+    reseed(2024);
+    let mut inv = Inventory::new();
+    inv.add_copper(1_000_000);
+    let start = inv.total_cp();
+    for _ in 0..1_000 {
+        play_rune_dice(&mut inv, 100, DiceWager::Over, false).unwrap();
+    }
+    assert!(
+        inv.total_cp() < start,
+        "Over Seven should lose in the long run"
+    );
+}
+
+#[test]
+fn luck_rerolls_a_loss_once_and_is_consumed() {
+    reseed(8);
+    let mut inv = Inventory::new();
+    inv.add_copper(5_000);
+    // Keep playing until a losing first throw triggers the reroll
+    loop {
+        inv.luck_boost = true;
+        let o = play_rune_dice(&mut inv, 20, DiceWager::Seven, true).unwrap();
+        if o.luck_spent {
+            assert!(o.rerolled_from.is_some());
+            assert!(!inv.luck_boost);
+            break;
+        }
+        assert!(
+            o.won && inv.luck_boost,
+            "Luck is kept when the first throw wins"
+        );
+    }
+}
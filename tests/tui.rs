@@ -0,0 +1,144 @@
+#![cfg(feature = "tui")]
+use dungeon_core::{
+    inventory::Inventory,
+    market::MerchantState,
+    rng::reseed,
+    shop::ShopKind,
+    tui::{App, Pane, ShopMode},
+};
+use ratatui::{Terminal, backend::TestBackend, crossterm::event::KeyCode};
+
+fn app_at(hour: u64) -> App {
+    let mut inv = Inventory::new();
+    inv.gold_pieces = 50;
+    inv.clock.minutes = hour * 60;
+    App::new(inv, vec!["5 gp and a silk ribbon".into()])
+}
+
+#[test]
+fn shops_open_with_stock_and_close_back_to_town() {
+    reseed(49);
+    let mut app = app_at(12);
+    app.handle_key(KeyCode::Char('s'));
+    assert_eq!(app.pane, Pane::Shop);
+    assert!(!app.stock.is_empty());
+    // Deep enough pockets that the first item on the shelf is always affordable
+    app.inv.gold_pieces = 100_000;
+    let before = app.inv.total_cp();
+    let wanted = app.stock[0].clone();
+    app.handle_key(KeyCode::Enter);
+    assert!(app.log.last().unwrap().contains("Bought"));
+    assert_eq!(app.inv.items, vec![wanted.name.clone()]);
+    assert_eq!(app.inv.total_cp(), before - wanted.price_cp);
+    app.handle_key(KeyCode::Tab);
+    assert_eq!(app.shop_mode, ShopMode::Sell);
+    app.handle_key(KeyCode::Right);
+    assert_eq!(app.shop_mode, ShopMode::Buy);
+    app.handle_key(KeyCode::Esc);
+    assert_eq!(app.pane, Pane::Town);
+    assert!(!app.quit);
+}
+
+#[test]
+fn the_tavern_serves_only_while_open() {
+    let mut closed = app_at(4);
+    closed.handle_key(KeyCode::Char('t'));
+    assert_eq!(closed.pane, Pane::Town);
+    assert!(closed.log.last().unwrap().contains("shuttered"));
+
+    let mut app = app_at(12);
+    app.handle_key(KeyCode::Char('t'));
+    assert_eq!(app.pane, Pane::Tavern);
+    let before = app.inv.total_cp();
+    app.handle_key(KeyCode::Enter); // the first order is a drink
+    assert!(app.inv.total_cp() < before);
+    app.handle_key(KeyCode::Esc);
+    assert_eq!(app.pane, Pane::Town);
+}
+
+#[test]
+fn fights_run_round_by_round_until_settled() {
+    reseed(5);
+    let mut app = app_at(12);
+    app.handle_key(KeyCode::Char('f'));
+    assert_eq!(app.pane, Pane::Fight);
+    assert!(app.fight.is_some());
+    for _ in 0..200 {
+        if app.pane != Pane::Fight {
+            break;
+        }
+        app.handle_key(KeyCode::Char('a'));
+    }
+    assert_eq!(app.pane, Pane::Town);
+    assert!(app.fight.is_none());
+    app.handle_key(KeyCode::Char('q'));
+    assert!(app.quit);
+}
+
+#[test]
+fn both_panels_render_into_a_terminal() {
+    let mut app = app_at(12);
+    app.inv.items.push("Murky potion".into());
+    let mut terminal = Terminal::new(TestBackend::new(110, 32)).unwrap();
+    terminal.draw(|f| app.draw(f)).unwrap();
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    for part in [
+        "HERO",
+        "INVENTORY",
+        "Murky potion ?",
+        "TOWN",
+        "LOG",
+        "Pickpocket",
+    ] {
+        assert!(screen.contains(part), "missing {part}");
+    }
+    app.handle_key(KeyCode::Char('f'));
+    terminal.draw(|f| app.draw(f)).unwrap();
+}
+
+#[test]
+fn a_sale_the_merchant_cant_cover_needs_a_second_enter() {
+    reseed(28);
+    let mut app = app_at(12);
+    app.inv.items = vec!["Silver Ring".into()];
+    app.inv.market.merchants.push(MerchantState {
+        kind: ShopKind::General,
+        purse_cp: 1,
+    });
+    app.handle_key(KeyCode::Char('s'));
+    app.handle_key(KeyCode::Tab);
+    let before = app.inv.total_cp();
+    app.handle_key(KeyCode::Enter);
+    assert!(app.log.last().unwrap().contains("only has 1 cp to spare"));
+    assert_eq!(app.inv.items.len(), 1);
+    // Moving away cancels; confirming sells for what the purse holds
+    app.handle_key(KeyCode::Down);
+    app.handle_key(KeyCode::Enter);
+    assert_eq!(app.inv.items.len(), 1);
+    app.handle_key(KeyCode::Enter);
+    assert!(app.inv.items.is_empty());
+    assert_eq!(app.inv.total_cp(), before + 1);
+}
+
+#[test]
+fn an_overloaded_player_cannot_withdraw_from_a_fight() {
+    reseed(45);
+    let mut app = app_at(12);
+    app.inv.items = vec!["Dwarven Plate".into(), "Shovel".into()];
+    app.handle_key(KeyCode::Char('f'));
+    for key in [KeyCode::Esc, KeyCode::Char('q'), KeyCode::Char('f')] {
+        app.handle_key(key);
+        assert_eq!(app.pane, Pane::Fight);
+        assert!(app.fight.is_some());
+        assert!(app.log.last().unwrap().contains("too much to run"));
+    }
+    app.inv.items.clear();
+    app.handle_key(KeyCode::Esc);
+    assert_eq!(app.pane, Pane::Town);
+}
@@ -0,0 +1,60 @@
+use dungeon_core::{
+    actions::{pick_pocket, rest_until_morning},
+    bestiary::{self, Activity},
+    clock::{LUCK_DURATION_MINUTES, MINUTES_PER_HOUR, PICKPOCKET_MINUTES, TimeOfDay, WorldClock},
+    inventory::Inventory,
+    rng::reseed,
+};
+
+#[test]
+fn clock_starts_in_the_morning_and_rolls_over_days() {
+    let mut clock = WorldClock::default();
+    assert_eq!((clock.day(), clock.hour(), clock.minute()), (1, 8, 0));
+    assert_eq!(clock.time_of_day(), TimeOfDay::Day);
+    let hours = clock.advance(17 * MINUTES_PER_HOUR + 30);
+    assert_eq!(hours, 17);
+    assert_eq!(clock.label(), "Day 2, 01:30 (Night)");
+    assert!(clock.tavern_open());
+    clock.advance(2 * MINUTES_PER_HOUR);
+    assert!(!clock.tavern_open(), "Tavern shuts in the small hours");
+}
+
+#[test]
+fn pickpocketing_takes_an_hour_and_resting_runs_to_morning() {
+    reseed(31);
+    let mut inv = Inventory::new();
+    pick_pocket(&mut inv, &["2 gp and a brass key".to_string()]);
+    assert_eq!(
+        inv.clock.minutes,
+        WorldClock::default().minutes + PICKPOCKET_MINUTES
+    );
+    inv.current_hp = 3;
+    rest_until_morning(&mut inv);
+    assert_eq!(
+        (inv.clock.day(), inv.clock.hour(), inv.clock.minute()),
+        (2, 8, 0)
+    );
+    assert_eq!(inv.current_hp, inv.max_hp);
+}
+
+#[test]
+fn stored_luck_fades_after_a_day() {
+    let mut inv = Inventory::new();
+    inv.luck_boost = true;
+    assert!(inv.advance_time(MINUTES_PER_HOUR).is_empty());
+    assert!(inv.luck_boost);
+    let notes = inv.advance_time(LUCK_DURATION_MINUTES);
+    assert!(!inv.luck_boost);
+    assert!(notes.iter().any(|n| n.contains("luck")));
+    assert_eq!(inv.luck_since, None);
+}
+
+#[test]
+fn undead_only_walk_at_night() {
+    let day = bestiary::encounter_table(TimeOfDay::Day);
+    let night = bestiary::encounter_table(TimeOfDay::Night);
+    assert!(day.iter().all(|m| m.activity != Activity::Nocturnal));
+    assert!(night.iter().all(|m| m.activity != Activity::Diurnal));
+    assert!(night.iter().any(|m| m.name == "Vampire Stalker"));
+    assert!(!day.iter().any(|m| m.name == "Vampire Stalker"));
+}